@@ -0,0 +1,149 @@
+//! 布局性能基准：生成不同规模的合成数据，测量空间索引下各布局步骤的耗时
+//! 运行：cargo bench --bench layout
+//! 输出每一步的总耗时和平均每个项目的耗时；每项目耗时随规模基本不变即为近似线性
+
+#![allow(dead_code)]
+
+#[path = "../src/models.rs"]
+mod models;
+#[path = "../src/layout.rs"]
+mod layout;
+
+use indexmap::IndexMap;
+use layout::SpatialIndex;
+use models::{default_field_fill_color, default_field_label_style, default_field_stroke_color, AppData, Project, ProjectRelation, ResearchField, RELATION_DIRECT};
+use std::time::{Duration, Instant};
+
+/// 项目规模
+const SIZES: [usize; 5] = [100, 200, 400, 800, 1600];
+/// 每个规模重复次数（取最短耗时）
+const REPEATS: usize = 3;
+
+/// 基准步骤（名称, 在数据上运行的布局操作）
+type Step = (&'static str, fn(&mut AppData));
+
+/// 简单的线性同余随机数（保证每次运行数据相同）
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> f32 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 40) as f32) / ((1u64 << 24) as f32)
+    }
+}
+
+/// 三个两两相交的领域，半径随项目数增长使项目密度保持不变；
+/// 项目随机分配到七个区域之一，关系数量约为项目数的一半
+fn synthetic_data(n: usize) -> AppData {
+    let mut rng = Lcg(n as u64);
+    let radius = 120.0 * (n as f32 / 20.0).sqrt();
+    let mut fields = IndexMap::new();
+    for i in 0..3 {
+        let angle = -std::f32::consts::FRAC_PI_2 + i as f32 * std::f32::consts::TAU / 3.0;
+        let id = format!("field_{}", i + 1);
+        fields.insert(id.clone(), ResearchField {
+            id,
+            name: format!("领域 {}", i + 1),
+            description: String::new(),
+            position: (radius * 0.6 * angle.cos(), radius * 0.6 * angle.sin()),
+            radius,
+            locked: false,
+            parent_id: None,
+            stroke_color: default_field_stroke_color(),
+            fill_color: default_field_fill_color(),
+            label_style: default_field_label_style(),
+        });
+    }
+    let field_ids: Vec<String> = fields.keys().cloned().collect();
+
+    let mut projects = IndexMap::new();
+    for i in 0..n {
+        let region = 1 + (rng.next() * 7.0) as usize % 7;
+        let id = format!("project_{}", i + 1);
+        projects.insert(id.clone(), Project {
+            id,
+            name: format!("项目 {}", i + 1),
+            field_ids: (0..3).filter(|b| region & (1 << b) != 0).map(|b| field_ids[b].clone()).collect(),
+            position: ((rng.next() - 0.5) * radius * 2.0, (rng.next() - 0.5) * radius * 2.0),
+            radius: 20.0,
+            ..Default::default()
+        });
+    }
+
+    let mut data = AppData {
+        fields,
+        projects,
+        ..AppData::default_empty()
+    };
+    // 先放置一次，再为每两个项目中的一个连一条指向附近项目的关系
+    // （与真实数据一样，箭头长度不随图的规模增长）
+    let ids: Vec<String> = data.projects.keys().cloned().collect();
+    layout::place_projects(&mut data, &ids);
+    let positions: Vec<(f32, f32)> = data.projects.values().map(|p| p.position).collect();
+    for (i, from) in positions.iter().enumerate().step_by(2) {
+        let nearby: Vec<usize> = (0..n)
+            .filter(|&j| j != i && (positions[j].0 - from.0).hypot(positions[j].1 - from.1) < 200.0)
+            .collect();
+        if nearby.is_empty() {
+            continue;
+        }
+        let j = nearby[(rng.next() * nearby.len() as f32) as usize % nearby.len()];
+        data.relations.push(ProjectRelation {
+            from_id: ids[i].clone(),
+            to_id: ids[j].clone(),
+            relation_type: RELATION_DIRECT.to_string(),
+            ..Default::default()
+        });
+    }
+    data
+}
+
+/// 重复运行取最短耗时
+fn measure(data: &AppData, run: impl Fn(&mut AppData)) -> Duration {
+    (0..REPEATS)
+        .map(|_| {
+            let mut data = data.clone();
+            let start = Instant::now();
+            run(&mut data);
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn main() {
+    let steps: [Step; 4] = [
+        ("建立索引", |data| {
+            std::hint::black_box(SpatialIndex::new(data));
+        }),
+        ("逐个放置项目", |data| {
+            let ids: Vec<String> = data.projects.keys().cloned().collect();
+            layout::place_projects(data, &ids);
+        }),
+        ("校验并修正位置", |data| {
+            layout::fix_positions(data);
+        }),
+        ("重叠检查", |data| {
+            std::hint::black_box(SpatialIndex::new(data).any_overlap(layout::PROJECT_SPACING));
+        }),
+    ];
+
+    println!("{:<14}{:>8}{:>14}{:>16}{:>10}", "步骤", "项目数", "总耗时(ms)", "每项目(µs)", "相对");
+    for (name, run) in steps {
+        let mut baseline = None;
+        for n in SIZES {
+            let data = synthetic_data(n);
+            let elapsed = measure(&data, run);
+            let per_project = elapsed.as_secs_f64() * 1e6 / n as f64;
+            let baseline = *baseline.get_or_insert(per_project);
+            println!(
+                "{:<14}{:>8}{:>14.2}{:>16.2}{:>10.2}",
+                name,
+                n,
+                elapsed.as_secs_f64() * 1e3,
+                per_project,
+                per_project / baseline
+            );
+        }
+    }
+}
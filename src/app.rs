@@ -1,142 +1,97 @@
+use crate::layout::{self, LabelObstacle, LayoutIssue, LayoutReport, LayoutTask, LayoutWorker, SpatialIndex};
 use crate::models::*;
+use crate::query::{self, Query};
+use crate::route;
+use crate::render::*;
+use crate::theme::*;
 use egui::*;
 use std::collections::HashMap;
 
 // 导入默认值函数
-use crate::models::{default_relation_color, default_relation_width};
-
-// IDE风格主题系统 - 参考VSCode/Cursor设计
-struct Theme {
-    // 主色调 - VSCode风格
-    primary: Color32,              // 主按钮颜色
-    primary_hover: Color32,        // 主按钮悬停
-    primary_active: Color32,       // 主按钮激活
-    
-    // 背景色 - 类似VSCode编辑器
-    background: Color32,            // 主背景
-    surface: Color32,              // 面板背景
-    surface_hover: Color32,        // 悬停背景
-    surface_selected: Color32,     // 选中背景
-    
-    // 文本颜色
-    text_primary: Color32,         // 主文本
-    text_secondary: Color32,       // 次要文本
-    text_disabled: Color32,        // 禁用文本
-    
-    // 边框和分隔线
-    border: Color32,               // 边框颜色
-    divider: Color32,              // 分隔线
-    
-    // 状态颜色
-    success: Color32,
-    warning: Color32,
-    error: Color32,
-    info: Color32,
-    
-    // 特殊用途
-    selection: Color32,            // 选中高亮
-    selection_inactive: Color32,   // 非活动选中
+use crate::models::{default_relation_route, default_relation_type};
+
+/// 登录验证结果：(数据存储路径, 最近编辑的文件路径)
+type UserLoginInfo = (Option<String>, Option<String>);
+
+/// 表格视图的列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TableColumn {
+    Number,
+    Name,
+    Status,
+    Fields,
+    Tags,
+    Completion,
+    Relations,
+    StartDate,
+    TargetDate,
+    SubmissionDate,
+    PublicationDate,
 }
 
-impl Theme {
-    // VSCode Light+ 风格主题
-    fn light() -> Self {
-        Self {
-            primary: Color32::from_rgb(0, 122, 204),        // VSCode蓝色
-            primary_hover: Color32::from_rgb(0, 102, 184),
-            primary_active: Color32::from_rgb(0, 82, 164),
-            
-            background: Color32::from_rgb(255, 255, 255),   // 纯白背景
-            surface: Color32::from_rgb(248, 248, 248),      // 浅灰面板
-            surface_hover: Color32::from_rgb(240, 240, 240),
-            surface_selected: Color32::from_rgb(230, 230, 230),
-            
-            text_primary: Color32::from_rgb(51, 51, 51),    // 深灰文本
-            text_secondary: Color32::from_rgb(102, 102, 102),
-            text_disabled: Color32::from_rgb(170, 170, 170),
-            
-            border: Color32::from_rgb(214, 214, 214),       // 浅灰边框
-            divider: Color32::from_rgb(214, 214, 214),
-            
-            success: Color32::from_rgb(89, 185, 89),
-            warning: Color32::from_rgb(252, 186, 3),
-            error: Color32::from_rgb(244, 63, 94),
-            info: Color32::from_rgb(0, 122, 204),
-            
-            selection: Color32::from_rgb(173, 214, 255),    // 浅蓝选中
-            selection_inactive: Color32::from_rgb(230, 230, 230),
-        }
+impl TableColumn {
+    fn all() -> [TableColumn; 11] {
+        [
+            TableColumn::Number,
+            TableColumn::Name,
+            TableColumn::Status,
+            TableColumn::Fields,
+            TableColumn::Tags,
+            TableColumn::Completion,
+            TableColumn::Relations,
+            TableColumn::StartDate,
+            TableColumn::TargetDate,
+            TableColumn::SubmissionDate,
+            TableColumn::PublicationDate,
+        ]
     }
-    
-    // VSCode Dark+ 风格主题
-    fn dark() -> Self {
-        Self {
-            primary: Color32::from_rgb(0, 122, 204),        // VSCode蓝色
-            primary_hover: Color32::from_rgb(38, 139, 210),
-            primary_active: Color32::from_rgb(0, 102, 184),
-            
-            background: Color32::from_rgb(30, 30, 30),      // 深灰背景
-            surface: Color32::from_rgb(37, 37, 38),        // 面板背景
-            surface_hover: Color32::from_rgb(45, 45, 45),
-            surface_selected: Color32::from_rgb(58, 58, 58),
-            
-            text_primary: Color32::from_rgb(212, 212, 212), // 浅灰文本
-            text_secondary: Color32::from_rgb(170, 170, 170),
-            text_disabled: Color32::from_rgb(113, 113, 113),
-            
-            border: Color32::from_rgb(60, 60, 60),         // 深灰边框
-            divider: Color32::from_rgb(60, 60, 60),
-            
-            success: Color32::from_rgb(89, 185, 89),
-            warning: Color32::from_rgb(252, 186, 3),
-            error: Color32::from_rgb(244, 63, 94),
-            info: Color32::from_rgb(0, 122, 204),
-            
-            selection: Color32::from_rgb(38, 79, 120),      // 深蓝选中
-            selection_inactive: Color32::from_rgb(58, 58, 58),
+
+    fn title(&self) -> &'static str {
+        match self {
+            TableColumn::Number => "#",
+            TableColumn::Name => "名称",
+            TableColumn::Status => "状态",
+            TableColumn::Fields => "领域",
+            TableColumn::Tags => "标签",
+            TableColumn::Completion => "完成度",
+            TableColumn::Relations => "关系(出/入)",
+            TableColumn::StartDate => "开始",
+            TableColumn::TargetDate => "目标",
+            TableColumn::SubmissionDate => "投稿",
+            TableColumn::PublicationDate => "发表",
         }
     }
-    
-    fn apply_visuals(&self, ctx: &Context) {
-        let mut style = (*ctx.style()).clone();
-        
-        // 按钮样式 - 更扁平化，小圆角
-        style.visuals.widgets.inactive.bg_fill = self.primary;
-        style.visuals.widgets.inactive.weak_bg_fill = self.surface;
-        style.visuals.widgets.inactive.fg_stroke = Stroke::new(1.0, Color32::WHITE);
-        style.visuals.widgets.inactive.rounding = Rounding::same(3.0); // 小圆角
-        
-        style.visuals.widgets.hovered.bg_fill = self.primary_hover;
-        style.visuals.widgets.hovered.rounding = Rounding::same(3.0);
-        
-        style.visuals.widgets.active.bg_fill = self.primary_active;
-        style.visuals.widgets.active.rounding = Rounding::same(3.0);
-        
-        // 文本样式 - 更专业的字体大小
-        style.text_styles.get_mut(&TextStyle::Heading).unwrap().size = 20.0;
-        style.text_styles.get_mut(&TextStyle::Body).unwrap().size = 13.0;
-        style.text_styles.get_mut(&TextStyle::Button).unwrap().size = 13.0;
-        style.text_styles.get_mut(&TextStyle::Small).unwrap().size = 11.0;
-        
-        // 间距 - 更紧凑
-        style.spacing.item_spacing = vec2(6.0, 6.0);
-        style.spacing.button_padding = vec2(12.0, 6.0);
-        style.spacing.menu_margin = Margin::same(4.0);
-        style.spacing.window_margin = Margin::same(8.0);
-        
-        // 交互
-        style.interaction.resize_grab_radius_side = 6.0;
-        
-        // 禁用Tab键的默认焦点导航，由应用自行处理
-        style.interaction.selectable_labels = false;
-        
-        // 整体视觉
-        style.visuals.override_text_color = Some(self.text_primary);
-        style.visuals.faint_bg_color = self.surface;
-        style.visuals.extreme_bg_color = self.surface_hover;
-        
-        ctx.set_style(style);
-    }
+}
+
+/// 表格中的单元格编辑（在绘制完成后统一应用）
+enum TableEdit {
+    Name(String, String),
+    Status(String, String),
+    Fields(String, Vec<String>),
+    Completion(String, f32),
+    Date(String, TableColumn, Option<chrono::NaiveDate>),
+}
+
+/// 批量编辑中的完成度修改方式
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CompletionEdit {
+    #[default]
+    Keep,    // 不修改
+    Set,     // 设为指定值
+    Adjust,  // 增减指定值
+}
+
+/// 批量编辑对话框的输入（应用时对所有选中项目生效）
+#[derive(Debug, Clone, Default)]
+pub struct BulkEdit {
+    pub status: Option<String>,  // 设置的状态（None 表示不修改）
+    pub completion_mode: CompletionEdit,
+    pub completion_value: f32,
+    pub add_field_ids: Vec<String>,  // 加入的领域
+    pub remove_field_ids: Vec<String>,  // 移出的领域
+    pub relation_tag: String,  // 添加到关系的标签（空表示不添加）
+    pub tag_all_relations: bool,  // 标签添加到选中项目的所有关系（否则只添加到选中项目之间的关系）
+    pub radius: Option<f32>,  // 设置的半径（None 表示不修改）
 }
 
 pub struct VennCVApp {
@@ -152,6 +107,18 @@ pub struct VennCVApp {
     pub new_username: String,  // 新用户名
     pub new_password: String,  // 新密码
     pub create_user_error: String,  // 创建用户错误信息
+    
+    // 用户管理状态
+    pub show_user_admin_dialog: bool,  // 是否显示用户管理对话框
+    pub user_admin_selected: Option<String>,  // 用户管理中选中的用户
+    pub user_admin_rename: String,  // 新用户名输入
+    pub user_admin_old_password: String,  // 当前密码输入
+    pub user_admin_new_password: String,  // 新密码输入
+    pub user_admin_confirm_password: String,  // 确认新密码输入
+    pub user_admin_pending_delete: Option<String>,  // 等待确认删除的用户
+    pub user_admin_delete_files: bool,  // 删除用户时是否同时删除数据目录
+    pub user_admin_message: String,  // 操作结果提示
+    pub user_admin_error: String,  // 操作错误信息
 
     // 应用数据
     pub data: AppData,
@@ -162,18 +129,41 @@ pub struct VennCVApp {
 
     // UI 状态
     pub selected_project: Option<String>,
+    #[allow(dead_code)]
     pub selected_field: Option<String>,
     pub show_project_table: bool,
     pub show_visualization: bool,
+    pub central_view: CentralView,  // 中央区域当前视图
+    pub selected_project_ids: Vec<String>,  // 多选的项目（包含 selected_project），用于画布高亮
+    pub selection_anchor: Option<String>,  // Shift 范围选择的起点
+    pub table_sort: (TableColumn, bool),  // 表格排序列和是否升序
+    pub table_filters: HashMap<TableColumn, String>,  // 表格各列的筛选条件
+    
+    // 搜索
+    pub search_query: String,  // 搜索框内容
+    pub search_results: Option<Vec<String>>,  // 匹配的项目ID（未搜索时为 None）
+    pub search_filter: Option<Query>,  // 解析后的搜索查询（未搜索或语法错误时为 None）
+    pub search_error: Option<String>,  // 搜索查询的语法错误
+    pub search_focus_requested: bool,  // 下一帧聚焦搜索框（Ctrl+F）
+    
+    // 视图筛选（作用于维恩图和图片导出）
+    pub view_filter: String,  // 筛选表达式（只显示匹配的项目）
+    pub hidden_field_ids: Vec<String>,  // 隐藏的领域
+    pub hidden_status_ids: Vec<String>,  // 隐藏的状态
+    pub hidden_relation_tags: Vec<String>,  // 隐藏的关系标签（带有任一未隐藏标签或没有标签的关系仍然显示）
+    pub active_view: Option<String>,  // 当前应用的保存视图名称
+    pub new_view_name: String,  // 保存视图的名称输入
     pub show_property_panel: bool,
     pub visualization_zoom: f32,
     pub visualization_offset: Vec2,
 
     // 编辑状态
     pub editing_project: Option<Project>,
+    #[allow(dead_code)]
     pub editing_field: Option<ResearchField>,
     pub expanded_relations: std::collections::HashMap<usize, bool>,  // 关系展开状态
     pub relation_tag_inputs: std::collections::HashMap<usize, String>,  // 每个关系的标签输入框内容
+    pub project_tag_input: String,  // 项目标签输入框内容
     
     // 设置
     pub show_settings_dialog: bool,  // 是否显示设置对话框
@@ -184,13 +174,53 @@ pub struct VennCVApp {
     pub legend_dragging: bool,  // 是否正在拖拽图例
     pub show_legend_settings: bool,  // 是否显示图例设置对话框
     
+    // 复制粘贴
+    pub project_clipboard: Option<String>,  // 最近复制的项目 JSON（菜单粘贴时使用）
+    
+    // 批量编辑
+    pub show_bulk_edit_dialog: bool,  // 是否显示批量编辑对话框
+    pub bulk_edit: BulkEdit,  // 批量编辑的输入
+    
+    // 状态管理
+    pub show_status_dialog: bool,  // 是否显示状态管理对话框
+    pub new_status_name: String,  // 新状态名称输入
+    
+    // 领域配色
+    pub show_field_style_dialog: bool,  // 是否显示领域配色对话框
+    
+    // 关系类型管理
+    pub show_relation_type_dialog: bool,  // 是否显示关系类型管理对话框
+    pub new_relation_type_name: String,  // 新关系类型名称输入
+    
+    // 关系标签管理
+    pub show_relation_tag_dialog: bool,  // 是否显示关系标签管理对话框
+    pub relation_tag_rename: Option<(String, String)>,  // 正在重命名的标签（原名称, 新名称输入）
+    pub new_relation_tag_name: String,  // 新标签名称输入
+    
     // 项目拖拽状态
     pub dragging_project: Option<String>,  // 正在拖拽的项目ID
+    pub drag_group: Vec<(String, (f32, f32))>,  // 随拖拽移动的项目及拖拽前的位置
+    pub box_select_start: Option<Pos2>,  // 框选起点（屏幕坐标）
+    
+    // 后台布局任务
+    pub layout_worker: Option<LayoutWorker>,  // 正在运行或等待接受的布局任务
+    
+    // 布局诊断
+    pub show_layout_diagnostics: bool,  // 是否显示布局诊断（问题列表和画布标记）
+    pub layout_report: Option<LayoutReport>,  // 当前位置的诊断结果（显示诊断时每帧更新）
+    pub focus_request: Option<(f32, f32)>,  // 下一帧把维恩图视图中心移到该点（数据坐标）
     
     // 领域列表拖拽状态
     pub dragging_field_idx: Option<usize>,  // 正在拖拽的领域索引
     pub field_drag_target_idx: Option<usize>,  // 拖拽目标位置
     
+    // 主题状态
+    pub system_dark_mode: bool,  // 系统是否为深色模式
+    pub custom_themes: Vec<Theme>,  // 从 themes 目录加载的自定义主题
+    pub theme_load_errors: Vec<String>,  // 自定义主题加载错误
+    pub applied_theme_name: String,  // 当前已应用到界面的主题名称
+    pub theme_message: String,  // 主题操作提示信息
+    
     // 面板宽度状态
     pub property_panel_width: f32,  // 属性面板宽度
     
@@ -215,6 +245,16 @@ impl Default for VennCVApp {
             new_username: String::new(),
             new_password: String::new(),
             create_user_error: String::new(),
+            show_user_admin_dialog: false,
+            user_admin_selected: None,
+            user_admin_rename: String::new(),
+            user_admin_old_password: String::new(),
+            user_admin_new_password: String::new(),
+            user_admin_confirm_password: String::new(),
+            user_admin_pending_delete: None,
+            user_admin_delete_files: false,
+            user_admin_message: String::new(),
+            user_admin_error: String::new(),
             data: AppData::default(),
             current_file_path: None,
             history: vec![AppData::default()],
@@ -224,6 +264,22 @@ impl Default for VennCVApp {
             selected_field: None,
             show_project_table: true,
             show_visualization: true,
+            central_view: CentralView::Venn,
+            selected_project_ids: Vec::new(),
+            selection_anchor: None,
+            table_sort: (TableColumn::Number, true),
+            table_filters: HashMap::new(),
+            search_query: String::new(),
+            search_results: None,
+            search_filter: None,
+            search_error: None,
+            search_focus_requested: false,
+            view_filter: String::new(),
+            hidden_field_ids: Vec::new(),
+            hidden_status_ids: Vec::new(),
+            hidden_relation_tags: Vec::new(),
+            active_view: None,
+            new_view_name: String::new(),
             show_property_panel: true,
             visualization_zoom: 1.0,
             visualization_offset: Vec2::ZERO,
@@ -231,15 +287,38 @@ impl Default for VennCVApp {
             editing_field: None,
             expanded_relations: std::collections::HashMap::new(),
             relation_tag_inputs: std::collections::HashMap::new(),
+            project_tag_input: String::new(),
             show_settings_dialog: false,
             settings: AppSettings::default(),
             legend_position: Vec2::ZERO,  // 默认在右上角
             legend_dragging: false,
             dragging_project: None,
+            drag_group: Vec::new(),
+            box_select_start: None,
+            layout_worker: None,
+            show_layout_diagnostics: false,
+            layout_report: None,
+            focus_request: None,
             dragging_field_idx: None,
             field_drag_target_idx: None,
+            system_dark_mode: false,
+            custom_themes: Vec::new(),
+            theme_load_errors: Vec::new(),
+            applied_theme_name: String::new(),
+            theme_message: String::new(),
             property_panel_width: 250.0,  // 默认宽度
             show_legend_settings: false,
+            project_clipboard: None,
+            show_bulk_edit_dialog: false,
+            bulk_edit: BulkEdit::default(),
+            show_status_dialog: false,
+            new_status_name: String::new(),
+            show_field_style_dialog: false,
+            show_relation_type_dialog: false,
+            new_relation_type_name: String::new(),
+            show_relation_tag_dialog: false,
+            relation_tag_rename: None,
+            new_relation_tag_name: String::new(),
             field_section_height: 100.0,  // 领域区域默认高度
             toolbar_focus_index: None,  // 工具栏按钮无选中
         }
@@ -248,19 +327,90 @@ impl Default for VennCVApp {
 
 impl VennCVApp {
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        // 应用IDE风格主题（默认浅色主题）
-        let theme = Theme::light();
-        theme.apply_visuals(&cc.egui_ctx);
-        
-        let mut app = Self::default();
+        let mut app = Self {
+            system_dark_mode: cc.integration_info.system_theme == Some(eframe::Theme::Dark),
+            ..Self::default()
+        };
         app.load_available_users();
         app.load_settings();
+        app.reload_custom_themes();
         // 应用设置到应用状态
         app.max_history_size = app.settings.max_history_size;
         app.visualization_zoom = app.settings.default_zoom;
+        // 应用IDE风格主题
+        app.apply_theme(&cc.egui_ctx);
         app
     }
 
+    /// 根据设置获取当前主题，并应用设置中的配色方案
+    fn theme(&self) -> Theme {
+        self.base_theme().with_palettes(self.settings.status_palette, self.settings.completion_palette)
+    }
+
+    /// 根据设置选择主题（选择了自定义主题时优先使用）
+    fn base_theme(&self) -> Theme {
+        if let Some(name) = &self.settings.custom_theme {
+            if let Some(theme) = self.custom_themes.iter().find(|t| &t.name == name) {
+                return theme.clone();
+            }
+        }
+        match self.settings.theme_mode {
+            ThemeMode::Light => Theme::light(),
+            ThemeMode::Dark => Theme::dark(),
+            ThemeMode::System => {
+                if self.system_dark_mode {
+                    Theme::dark()
+                } else {
+                    Theme::light()
+                }
+            }
+        }
+    }
+
+    /// 应用当前主题到界面
+    /// 只在主题变化或样式被外部重置（如系统主题切换时eframe重设视觉）时重新应用
+    fn apply_theme(&mut self, ctx: &Context) {
+        let theme = self.theme();
+        let visuals = &ctx.style().visuals;
+        if self.applied_theme_name != theme.name
+            || visuals.dark_mode != theme.is_dark
+            || visuals.override_text_color != Some(theme.text_primary)
+        {
+            theme.apply_visuals(ctx);
+            self.applied_theme_name = theme.name;
+        }
+    }
+
+    /// 重新从 themes 目录加载自定义主题
+    fn reload_custom_themes(&mut self) {
+        let (themes, errors) = load_custom_themes();
+        for error in &errors {
+            eprintln!("加载主题失败: {}", error);
+        }
+        self.custom_themes = themes;
+        self.theme_load_errors = errors;
+        // 强制下一帧重新应用主题
+        self.applied_theme_name.clear();
+    }
+
+    /// 项目状态的显示颜色（考虑配色方案和主题）
+    fn status_color(&self, theme: &Theme, status_id: &str) -> Color32 {
+        match self.data.status(status_id) {
+            Some(status) => theme.status_color(status, self.data.status_index(status_id)),
+            None => Color32::GRAY,
+        }
+    }
+
+    /// 可视化区域背景颜色
+    fn canvas_background(&self, theme: &Theme) -> Color32 {
+        if self.settings.canvas_bg_follow_theme {
+            theme.background
+        } else {
+            let c = self.settings.visualization_bg_color;
+            Color32::from_rgba_unmultiplied(c[0], c[1], c[2], c[3])
+        }
+    }
+
     /// 加载可用用户列表
     fn load_available_users(&mut self) {
         self.available_users.clear();
@@ -272,7 +422,7 @@ impl VennCVApp {
     }
 
     fn login_ui(&mut self, ctx: &Context) {
-        let theme = Theme::light();
+        let theme = self.theme();
         
         // 创建用户对话框
         if self.show_create_user_dialog {
@@ -476,7 +626,8 @@ impl VennCVApp {
                                                 if path.exists() {
                                                     if let Ok(content) = std::fs::read_to_string(&path) {
                                                         match serde_json::from_str::<AppData>(&content) {
-                                                            Ok(data) => {
+                                                            Ok(mut data) => {
+                                                                data.migrate();
                                                                 self.data = data.clone();
                                                                 self.current_file_path = Some(path);
                                                                 self.selected_project = None;
@@ -545,14 +696,19 @@ impl VennCVApp {
     fn main_ui(&mut self, ctx: &Context) {
         // 处理快捷键
         self.handle_shortcuts(ctx);
+        self.poll_layout_task(ctx);
+        self.layout_report = self.show_layout_diagnostics.then(|| layout::diagnose(&self.data));
+        self.sync_selection();
+        self.update_search();
         
-        let theme = Theme::light();
+        let theme = self.theme();
         
         // 顶部菜单栏
         TopBottomPanel::top("menu_bar")
             .frame(Frame::none().fill(theme.surface).inner_margin(8.0))
             .show(ctx, |ui| {
                 menu::bar(ui, |ui| {
+                    ui.set_enabled(self.layout_worker.is_none());
                     ui.menu_button("文件", |ui| {
                     if ui.button("新建 (Ctrl+N)").clicked() {
                         self.new_file();
@@ -574,10 +730,19 @@ impl VennCVApp {
                     if ui.button("导出数据").clicked() {
                         self.export_data();
                     }
+                    if ui.add_enabled(self.search_results.is_some(), Button::new("导出搜索结果"))
+                        .on_hover_text("只导出匹配搜索查询的项目及其领域和关系")
+                        .clicked()
+                    {
+                        self.export_search_results();
+                    }
                     ui.separator();
                     if ui.button("设置").clicked() {
                         self.show_settings_dialog = true;
                     }
+                    if ui.button("用户管理").clicked() {
+                        self.open_user_admin_dialog();
+                    }
                     if ui.button("切换用户").clicked() {
                         self.is_logged_in = false;
                         self.current_user = None;
@@ -602,12 +767,47 @@ impl VennCVApp {
                     if ui.button("重做 (Ctrl+Shift+Z)").clicked() {
                         self.redo();
                     }
+                    ui.separator();
+                    let selected_count = self.selected_project_ids.len();
+                    if ui.add_enabled(selected_count > 0, Button::new("复制项目 (Ctrl+C)")).clicked() {
+                        self.copy_to_clipboard(ctx);
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(self.project_clipboard.is_some(), Button::new("粘贴项目 (Ctrl+V)")).clicked() {
+                        if let Some(json) = self.project_clipboard.clone() {
+                            self.paste_projects(&json);
+                        }
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(selected_count > 0, Button::new("创建副本 (Ctrl+D)")).clicked() {
+                        self.duplicate_selected_projects();
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.add_enabled(selected_count > 0, Button::new(format!("批量编辑 ({})", selected_count))).clicked() {
+                        self.open_bulk_edit_dialog();
+                        ui.close_menu();
+                    }
+                    if ui.button("状态管理").clicked() {
+                        self.show_status_dialog = true;
+                    }
+                    if ui.button("领域配色").clicked() {
+                        self.show_field_style_dialog = true;
+                    }
+                    if ui.button("关系类型管理").clicked() {
+                        self.show_relation_type_dialog = true;
+                    }
+                    if ui.button("关系标签管理").clicked() {
+                        self.show_relation_tag_dialog = true;
+                    }
                 });
 
                 ui.menu_button("视图", |ui| {
                     ui.checkbox(&mut self.show_project_table, "数据表");
                     ui.checkbox(&mut self.show_visualization, "可视化");
                     ui.checkbox(&mut self.show_property_panel, "属性面板");
+                    ui.checkbox(&mut self.show_layout_diagnostics, "布局诊断")
+                        .on_hover_text("列出违反布局规则的项目和领域名称，并在维恩图中标记");
                     ui.separator();
                     if ui.button("放大 (Ctrl+Plus)").clicked() {
                         self.zoom_in();
@@ -618,11 +818,38 @@ impl VennCVApp {
                     if ui.button("重置缩放 (Ctrl+0)").clicked() {
                         self.zoom_reset();
                     }
+                    ui.separator();
+                    self.saved_views_menu(ui);
                 });
 
                 ui.menu_button("布局", |ui| {
                     if ui.button("自动调整领域布局").clicked() {
-                        self.adjust_field_layout();
+                        layout::adjust_field_layout(&mut self.data);
+                    }
+                    if ui.button("修正位置").on_hover_text("在后台修正位置不正确和重叠的项目").clicked() {
+                        self.start_layout_task(LayoutTask::FixPositions, self.data.clone());
+                        ui.close_menu();
+                    }
+                    if ui.button("重新布局").on_hover_text("在后台重新计算领域布局并重新放置全部项目").clicked() {
+                        self.start_layout_task(LayoutTask::Relayout, self.data.clone());
+                        ui.close_menu();
+                    }
+                    ui.menu_button("全部关系走线", |ui| {
+                        for route in RelationRoute::all() {
+                            if ui.button(route.name()).clicked() {
+                                self.set_all_relation_routes(route);
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                    ui.separator();
+                    if ui.button("锁定全部").on_hover_text("锁定所有项目和领域，自动布局不再改变它们的位置和大小").clicked() {
+                        self.set_all_locked(true);
+                        ui.close_menu();
+                    }
+                    if ui.button("解锁全部").clicked() {
+                        self.set_all_locked(false);
+                        ui.close_menu();
                     }
                 });
 
@@ -658,6 +885,10 @@ impl VennCVApp {
                     // 工具栏按钮选中高亮色
                     let focus_fill = theme.primary;
                     
+                    // 后台布局任务进度，任务期间其他按钮不可用
+                    self.layout_task_controls(ui);
+                    ui.set_enabled(self.layout_worker.is_none());
+                    
                     // 新建项目按钮（使用图标）- 索引0
                     let new_btn = ui.add_sized(
                         [32.0, 28.0],
@@ -682,19 +913,10 @@ impl VennCVApp {
                         .fill(if self.toolbar_focus_index == Some(1) { focus_fill } else { theme.surface })
                     );
                     if delete_btn.clicked() {
-                        if let Some(id) = &self.selected_project {
-                            // 删除项目
-                            self.data.projects.shift_remove(id);
-                            // 删除相关关系
-                            self.data.relations.retain(|r| r.from_id != *id && r.to_id != *id);
-                            // 清除选中状态
-                            self.selected_project = None;
-                            self.editing_project = None;
-                            // 保存到历史
-                            self.save_to_history();
-                        }
+                        // 删除选中的项目（多选时一并删除）及相关关系
+                        self.delete_selected_projects();
                     }
-                    delete_btn.on_hover_text("删除项目");
+                    delete_btn.on_hover_text("删除选中的项目");
                     
                     ui.separator();
                     
@@ -754,16 +976,8 @@ impl VennCVApp {
                         .fill(if self.toolbar_focus_index == Some(5) { focus_fill } else { theme.surface })
                     );
                     if relayout_btn.clicked() {
-                        // 只修正位置不正确的项目
-                        self.validate_and_fix_project_positions();
-                        // 更新编辑中的项目位置
-                        if let Some(id) = &self.selected_project {
-                            if let Some(project) = self.data.projects.get(id) {
-                                if let Some(editing) = &mut self.editing_project {
-                                    editing.position = project.position;
-                                }
-                            }
-                        }
+                        // 在后台修正位置不正确的项目
+                        self.start_layout_task(LayoutTask::FixPositions, self.data.clone());
                     }
                     relayout_btn.on_hover_text("重新布局（修正位置不正确的项目）");
                     
@@ -821,6 +1035,7 @@ impl VennCVApp {
                 .width_range(150.0..=400.0)
                 .frame(Frame::side_top_panel(&ctx.style()).fill(theme.surface).stroke(Stroke::new(1.0, theme.divider)))
                 .show(ctx, |ui| {
+                    ui.set_enabled(self.layout_worker.is_none());
                     // === 领域管理区域（可折叠） ===
                     // 限制领域区域高度在20到200之间
                     let field_height = self.field_section_height.clamp(20.0, 200.0);
@@ -845,6 +1060,11 @@ impl VennCVApp {
                                             description: String::new(),
                                             position: (400.0, 400.0),
                                             radius: 200.0,
+                                            locked: false,
+                                            parent_id: None,
+                                            stroke_color: default_field_stroke_color(),
+                                            fill_color: default_field_fill_color(),
+                                            label_style: default_field_label_style(),
                                         };
                                         self.data.fields.insert(new_id, new_field);
                                         self.save_to_history();
@@ -857,6 +1077,8 @@ impl VennCVApp {
                             let field_ids_left: Vec<String> = self.data.fields.keys().cloned().collect();
                             let mut fields_to_remove_left: Vec<String> = Vec::new();
                             let mut fields_to_update_left: HashMap<String, String> = HashMap::new();
+                            let mut field_lock_toggle: Option<String> = None;
+                            let mut field_parent_change: Option<(String, Option<String>)> = None;
                             let mut field_reorder: Option<(usize, usize)> = None;  // (from, to)
                             
                             ScrollArea::vertical()
@@ -905,7 +1127,11 @@ impl VennCVApp {
                                                             .monospace()
                                                     );
                                                     
-                                                    let input_width = (ui.available_width() - 55.0).max(40.0);
+                                                    // 子领域按层级缩进
+                                                    let depth = self.data.field_depth(field_id);
+                                                    ui.add_space(depth as f32 * 12.0);
+                                                    
+                                                    let input_width = (ui.available_width() - 130.0).max(40.0);
                                                     
                                                     let mut field_name = field.name.clone();
                                                     let name_response = ui.add(
@@ -916,6 +1142,50 @@ impl VennCVApp {
                                                         fields_to_update_left.insert(field_id.clone(), field_name);
                                                     }
                                                     
+                                                    // 领域配色按钮以边框颜色显示，点击打开领域配色对话框
+                                                    let (stroke_preview, _) = field_colors(field);
+                                                    if ui.small_button(RichText::new("●").color(stroke_preview))
+                                                        .on_hover_text("领域配色：边框、半透明填充和名称样式")
+                                                        .clicked()
+                                                    {
+                                                        self.show_field_style_dialog = true;
+                                                    }
+                                                    
+                                                    // 上级领域：不能选自身和自身的下级领域
+                                                    let parent_name = field.parent_id.as_ref()
+                                                        .and_then(|p| self.data.fields.get(p))
+                                                        .map(|p| p.name.clone());
+                                                    let hover = match &parent_name {
+                                                        Some(name) => format!("上级领域：{}", name),
+                                                        None => "设置上级领域（顶层领域）".to_string(),
+                                                    };
+                                                    ui.menu_button("↳", |ui| {
+                                                        if ui.radio(field.parent_id.is_none(), "无（顶层领域）").clicked() {
+                                                            field_parent_change = Some((field_id.clone(), None));
+                                                            ui.close_menu();
+                                                        }
+                                                        let descendants = self.data.field_descendants(field_id);
+                                                        for (candidate, candidate_depth) in self.data.field_tree() {
+                                                            if candidate.id == *field_id || descendants.contains(&candidate.id) {
+                                                                continue;
+                                                            }
+                                                            let selected = field.parent_id.as_ref() == Some(&candidate.id);
+                                                            let label = format!("{}{}", "  ".repeat(candidate_depth), candidate.name);
+                                                            if ui.radio(selected, label).clicked() {
+                                                                field_parent_change = Some((field_id.clone(), Some(candidate.id.clone())));
+                                                                ui.close_menu();
+                                                            }
+                                                        }
+                                                    }).response.on_hover_text(hover);
+                                                    
+                                                    let lock_icon = if field.locked { "🔒" } else { "🔓" };
+                                                    if ui.small_button(lock_icon)
+                                                        .on_hover_text(if field.locked { "解锁领域" } else { "锁定领域：自动布局不再改变其位置和大小" })
+                                                        .clicked()
+                                                    {
+                                                        field_lock_toggle = Some(field_id.clone());
+                                                    }
+                                                    
                                                     if ui.small_button("×").clicked() {
                                                         fields_to_remove_left.push(field_id.clone());
                                                     }
@@ -979,6 +1249,7 @@ impl VennCVApp {
                             
                             // 处理领域重排序
                             if let Some((from_idx, to_idx)) = field_reorder {
+                                let before = self.data.clone();
                                 // 获取所有键值对
                                 let entries: Vec<_> = self.data.fields.drain(..).collect();
                                 let mut new_entries = Vec::with_capacity(entries.len());
@@ -1009,12 +1280,27 @@ impl VennCVApp {
                                 for (k, v) in new_entries {
                                     self.data.fields.insert(k, v);
                                 }
-                                // 重新计算领域布局
-                                self.adjust_field_layout();
-                                // 重新计算所有项目位置（领域位置变了），加入随机抖动避免重叠
-                                self.recalculate_all_project_positions_with_jitter();
-                                // 二次检验并修正重叠
-                                self.validate_and_fix_project_positions();
+                                // 在后台重新计算领域布局和所有项目位置（领域位置变了），放弃时连同排序一起恢复
+                                self.start_layout_task(LayoutTask::Relayout, before);
+                            }
+                            
+                            // 更改上级领域：子领域收回上级领域内，再在后台修正项目位置
+                            if let Some((fid, parent_id)) = field_parent_change {
+                                let before = self.data.clone();
+                                match self.data.set_field_parent(&fid, parent_id) {
+                                    Ok(()) => {
+                                        layout::contain_sub_fields(&mut self.data);
+                                        self.start_layout_task(LayoutTask::FixPositions, before);
+                                    }
+                                    Err(e) => eprintln!("设置上级领域失败: {}", e),
+                                }
+                            }
+
+                            // 锁定或解锁领域
+                            if let Some(fid) = field_lock_toggle {
+                                if let Some(f) = self.data.fields.get_mut(&fid) {
+                                    f.locked = !f.locked;
+                                }
                                 self.save_to_history();
                             }
                             
@@ -1028,13 +1314,10 @@ impl VennCVApp {
                                 self.save_to_history();
                             }
                             
-                            // 删除领域
+                            // 删除领域（下级领域改挂到其上级领域）
                             if !fields_to_remove_left.is_empty() {
                                 for fid in fields_to_remove_left {
-                                    for project in self.data.projects.values_mut() {
-                                        project.field_ids.retain(|id| id != &fid);
-                                    }
-                                    self.data.fields.remove(&fid);
+                                    self.data.delete_field(&fid);
                                 }
                                 self.save_to_history();
                             }
@@ -1079,12 +1362,77 @@ impl VennCVApp {
                     ui.add_space(4.0);
                     
                     // === 项目列表区域（中间） ===
+                    // 搜索框：上下方向键在结果中切换，回车选中第一个结果，Esc 清空
+                    let search_id = Id::new("project_search");
+                    if ui.memory(|m| m.has_focus(search_id)) {
+                        let (down, up) = ui.input_mut(|i| (
+                            i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                            i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                        ));
+                        if down {
+                            self.next_project();
+                        }
+                        if up {
+                            self.previous_project();
+                        }
+                    }
+                    if self.search_focus_requested {
+                        ui.memory_mut(|m| m.request_focus(search_id));
+                        self.search_focus_requested = false;
+                    }
+                    ui.horizontal(|ui| {
+                        let search_response = ui.add(
+                            TextEdit::singleline(&mut self.search_query)
+                                .id(search_id)
+                                .hint_text("🔍 搜索项目 (Ctrl+F)")
+                                .desired_width(ui.available_width() - 100.0)
+                        );
+                        if search_response.lost_focus() {
+                            if ui.input(|i| i.key_pressed(Key::Escape)) {
+                                self.search_query.clear();
+                            } else if ui.input(|i| i.key_pressed(Key::Enter)) {
+                                // 当前选中的项目不在结果中时，选中第一个结果
+                                if let Some(results) = &self.search_results {
+                                    let in_results = self.selected_project.as_ref().is_some_and(|id| results.contains(id));
+                                    if !in_results {
+                                        self.next_project();
+                                    }
+                                }
+                            }
+                        }
+                        search_response.on_hover_text(query::QUERY_HELP);
+                        if let Some(results) = self.search_results.clone() {
+                            ui.label(
+                                RichText::new(format!("{} 个结果", results.len()))
+                                    .size(10.0)
+                                    .color(theme.text_secondary)
+                            );
+                            // 选中全部结果（供批量操作使用）
+                            if ui.small_button("全选").on_hover_text("选中全部搜索结果").clicked() {
+                                self.selected_project = results.first().cloned();
+                                self.editing_project = results.first().and_then(|id| self.data.projects.get(id)).cloned();
+                                self.selected_project_ids = results;
+                                self.selection_anchor = self.selected_project.clone();
+                            }
+                        }
+                    });
+                    if let Some(error) = &self.search_error {
+                        ui.label(RichText::new(error).size(10.0).color(theme.error));
+                    }
+                    ui.add_space(4.0);
+                    
+                    let done_count = self.data.projects.values().filter(|p| self.data.is_done(p)).count();
                     ui.horizontal(|ui| {
                         ui.label(
                             RichText::new("项目")
                                 .size(11.0)
                                 .color(theme.text_secondary)
                         );
+                        ui.label(
+                            RichText::new(format!("已完成 {}/{}", done_count, self.data.projects.len()))
+                                .size(10.0)
+                                .color(theme.text_disabled)
+                        );
                         ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                             if ui.small_button("+").clicked() {
                                 self.create_new_project_shortcut();
@@ -1100,7 +1448,11 @@ impl VennCVApp {
                         .show(ui, |ui| {
                             // 显示项目列表 - VSCode文件树风格
                             for (idx, (id, project)) in self.data.projects.iter().enumerate() {
-                                let is_selected = self.selected_project.as_ref() == Some(id);
+                                // 搜索时只显示匹配的项目（编号保持不变）
+                                if self.search_results.as_ref().is_some_and(|r| !r.contains(id)) {
+                                    continue;
+                                }
+                                let is_selected = self.selected_project.as_ref() == Some(id) || self.selected_project_ids.contains(id);
                                 
                                 // 绘制背景
                                 let item_rect = ui.available_rect_before_wrap();
@@ -1141,11 +1493,11 @@ impl VennCVApp {
                                         );
                                         ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                                             // 状态标签
-                                            let status_color = project.status.color();
+                                            let status_color = self.status_color(&theme, &project.status);
                                             ui.label(
-                                                RichText::new(project.status.name())
+                                                RichText::new(self.data.status_name(&project.status))
                                                     .size(10.0)
-                                                    .color(Color32::WHITE)
+                                                    .color(contrast_text_color(status_color))
                                                     .background_color(status_color)
                                             );
                                         });
@@ -1164,12 +1516,13 @@ impl VennCVApp {
         // 右侧属性编辑面板 - VSCode风格
         // 注意：属性面板在可视化面板之后渲染，确保左边界贴着可视化面板
         if self.show_property_panel {
-            let panel_response = SidePanel::right("property_panel")
+            SidePanel::right("property_panel")
                 .resizable(true)
                 .default_width(self.property_panel_width)
                 .width_range(200.0..=400.0)
                 .frame(Frame::side_top_panel(&ctx.style()).fill(theme.surface).stroke(Stroke::new(1.0, theme.divider)))
                 .show(ctx, |ui| {
+                    ui.set_enabled(self.layout_worker.is_none());
                     // 记录当前面板宽度
                     self.property_panel_width = ui.available_width();
                     // 标题栏
@@ -1233,6 +1586,72 @@ impl VennCVApp {
                                     
                                     ui.add_space(12.0);
                                     
+                                    // 项目标签（关键词）：已有标签显示为小块，输入框可从标签库选择或回车新建
+                                    ui.vertical(|ui| {
+                                        ui.label(
+                                            RichText::new("标签").size(11.0).color(theme.text_secondary)
+                                        );
+                                        ui.add_space(4.0);
+                                        let mut remove_idx = None;
+                                        ui.horizontal_wrapped(|ui| {
+                                            for (idx, tag) in project_mut.tags.iter().enumerate() {
+                                                if ui.small_button(format!("{} ✕", tag)).on_hover_text("移除标签").clicked() {
+                                                    remove_idx = Some(idx);
+                                                }
+                                            }
+                                        });
+                                        if let Some(idx) = remove_idx {
+                                            project_mut.tags.remove(idx);
+                                            project_changed = true;
+                                        }
+                                        let response = ui.add(
+                                            TextEdit::singleline(&mut self.project_tag_input)
+                                                .hint_text("输入标签名或选择现有标签...")
+                                                .desired_width(ui.available_width())
+                                        );
+                                        // 输入框获得焦点时打开候选列表，点击列表外或选择后关闭
+                                        let popup_id = egui::Id::new("project_tag_popup");
+                                        if response.has_focus() {
+                                            ui.memory_mut(|mem| mem.open_popup(popup_id));
+                                        }
+                                        let term = crate::search::normalize(&self.project_tag_input);
+                                        let candidates: Vec<&String> = self.data.project_tags.iter()
+                                            .filter(|t| !project_mut.tags.contains(t))
+                                            .filter(|t| crate::search::normalize(t).contains(&term))
+                                            .collect();
+                                        egui::popup::popup_below_widget(ui, popup_id, &response, |ui| {
+                                            ui.set_width(response.rect.width());
+                                            ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                                                if candidates.is_empty() {
+                                                    ui.label(RichText::new("暂无可用标签，回车新建").size(11.0).color(theme.text_secondary));
+                                                }
+                                                for tag in candidates {
+                                                    if ui.selectable_label(false, tag).clicked() {
+                                                        project_mut.tags.push(tag.clone());
+                                                        project_changed = true;
+                                                        self.project_tag_input.clear();
+                                                        ui.memory_mut(|mem| mem.close_popup());
+                                                    }
+                                                }
+                                            });
+                                        });
+                                        // 回车添加输入的标签（不在标签库中时加入标签库）
+                                        let tag = self.project_tag_input.trim().to_string();
+                                        if response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) && !tag.is_empty() {
+                                            if !self.data.project_tags.contains(&tag) {
+                                                self.data.project_tags.push(tag.clone());
+                                            }
+                                            if !project_mut.tags.contains(&tag) {
+                                                project_mut.tags.push(tag);
+                                                project_changed = true;
+                                            }
+                                            self.project_tag_input.clear();
+                                            response.request_focus();
+                                        }
+                                    });
+                                    
+                                    ui.add_space(12.0);
+                                    
                                     ui.vertical(|ui| {
                                         ui.label(
                                             RichText::new("状态").size(11.0).color(theme.text_secondary)
@@ -1240,44 +1659,18 @@ impl VennCVApp {
                                         ui.add_space(4.0);
                                         let mut status_changed = false;
                                         ComboBox::from_id_source("status")
-                                            .selected_text(project_mut.status.name())
+                                            .selected_text(self.data.status_name(&project_mut.status))
                                             .width(150.0) // 固定宽度
                                             .show_ui(ui, |ui| {
-                                        if ui.selectable_value(
-                                            &mut project_mut.status,
-                                            ProjectStatus::Published,
-                                            ProjectStatus::Published.name(),
-                                        ).clicked() {
-                                            status_changed = true;
-                                        }
-                                        if ui.selectable_value(
-                                            &mut project_mut.status,
-                                            ProjectStatus::Submitted,
-                                            ProjectStatus::Submitted.name(),
-                                        ).clicked() {
-                                            status_changed = true;
-                                        }
-                                        if ui.selectable_value(
-                                            &mut project_mut.status,
-                                            ProjectStatus::HighPriority,
-                                            ProjectStatus::HighPriority.name(),
-                                        ).clicked() {
-                                            status_changed = true;
-                                        }
-                                        if ui.selectable_value(
-                                            &mut project_mut.status,
-                                            ProjectStatus::SteadyProgress,
-                                            ProjectStatus::SteadyProgress.name(),
-                                        ).clicked() {
-                                            status_changed = true;
-                                        }
-                                        if ui.selectable_value(
-                                            &mut project_mut.status,
-                                            ProjectStatus::ToBeStarted,
-                                            ProjectStatus::ToBeStarted.name(),
-                                        ).clicked() {
-                                            status_changed = true;
-                                        }
+                                                for status in self.data.sorted_statuses() {
+                                                    if ui.selectable_value(
+                                                        &mut project_mut.status,
+                                                        status.id.clone(),
+                                                        &status.name,
+                                                    ).clicked() {
+                                                        status_changed = true;
+                                                    }
+                                                }
                                             });
                                         if status_changed {
                                             project_changed = true;
@@ -1299,6 +1692,95 @@ impl VennCVApp {
                                         }
                                     });
                                     
+                                    ui.add_space(12.0);
+                                    
+                                    if ui.checkbox(&mut project_mut.locked, "锁定位置")
+                                        .on_hover_text("自动布局不再移动该项目，画布上也不能拖动")
+                                        .changed()
+                                    {
+                                        project_changed = true;
+                                    }
+                                    
+                                    ui.add_space(12.0);
+                                    
+                                    // 日期（格式 YYYY-MM-DD，留空表示未设置）
+                                    ui.vertical(|ui| {
+                                        ui.label(
+                                            RichText::new("日期").size(11.0).color(theme.text_secondary)
+                                        );
+                                        ui.add_space(4.0);
+                                        let project_id = project_mut.id.clone();
+                                        egui::Grid::new("project_dates")
+                                            .num_columns(2)
+                                            .spacing([8.0, 4.0])
+                                            .show(ui, |ui| {
+                                                let dates = [
+                                                    ("开始", "start_date", &mut project_mut.start_date),
+                                                    ("目标", "target_date", &mut project_mut.target_date),
+                                                    ("投稿", "submission_date", &mut project_mut.submission_date),
+                                                    ("发表", "publication_date", &mut project_mut.publication_date),
+                                                ];
+                                                for (label, key, value) in dates {
+                                                    ui.label(RichText::new(label).size(11.0).color(theme.text_secondary));
+                                                    if date_edit(ui, (&project_id, key), value, theme.error) {
+                                                        project_changed = true;
+                                                    }
+                                                    ui.end_row();
+                                                }
+                                            });
+                                    });
+                                    
+                                    ui.add_space(12.0);
+                                    
+                                    // 里程碑
+                                    ui.vertical(|ui| {
+                                        ui.horizontal(|ui| {
+                                            ui.label(
+                                                RichText::new("里程碑").size(11.0).color(theme.text_secondary)
+                                            );
+                                            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                                if ui.small_button("+").on_hover_text("添加里程碑").clicked() {
+                                                    project_mut.milestones.push(Milestone {
+                                                        name: "新里程碑".to_string(),
+                                                        ..Default::default()
+                                                    });
+                                                    project_changed = true;
+                                                }
+                                            });
+                                        });
+                                        ui.add_space(4.0);
+                                        let project_id = project_mut.id.clone();
+                                        let mut remove_idx = None;
+                                        for (idx, milestone) in project_mut.milestones.iter_mut().enumerate() {
+                                            ui.horizontal(|ui| {
+                                                if ui.checkbox(&mut milestone.done, "").on_hover_text("已完成").changed() {
+                                                    project_changed = true;
+                                                }
+                                                if ui.add(
+                                                    TextEdit::singleline(&mut milestone.name)
+                                                        .desired_width(ui.available_width() - 24.0)
+                                                ).changed() {
+                                                    project_changed = true;
+                                                }
+                                                if ui.small_button("✕").on_hover_text("删除里程碑").clicked() {
+                                                    remove_idx = Some(idx);
+                                                }
+                                            });
+                                            ui.horizontal(|ui| {
+                                                ui.add_space(24.0);
+                                                ui.label(RichText::new("截止").size(11.0).color(theme.text_secondary));
+                                                if date_edit(ui, (&project_id, "milestone", idx), &mut milestone.due_date, theme.error) {
+                                                    project_changed = true;
+                                                }
+                                            });
+                                            ui.add_space(4.0);
+                                        }
+                                        if let Some(idx) = remove_idx {
+                                            project_mut.milestones.remove(idx);
+                                            project_changed = true;
+                                        }
+                                    });
+                                    
                                     // 如果项目属性发生变化，实时更新
                                     if project_changed {
                                         // 更新编辑中的项目
@@ -1323,11 +1805,21 @@ impl VennCVApp {
                                             RichText::new("所属领域").size(11.0).color(theme.text_secondary)
                                         );
                                         ui.add_space(4.0);
-                                        let field_ids: Vec<String> = self.data.fields.keys().cloned().collect();
-                                        for field_id in field_ids {
-                                            let field_name = self.data.fields.get(&field_id).map(|f| f.name.clone()).unwrap_or_default();
+                                        // 按层级列出领域，子领域缩进；选择子领域的项目也属于其上级领域
+                                        let field_tree: Vec<(String, String, usize)> = self.data.field_tree().iter()
+                                            .map(|(f, depth)| (f.id.clone(), f.name.clone(), *depth))
+                                            .collect();
+                                        let member_ids = self.data.effective_field_ids(&project_mut);
+                                        for (field_id, field_name, depth) in field_tree {
                                             let mut is_selected = project_mut.field_ids.contains(&field_id);
-                                            let checkbox_response = ui.checkbox(&mut is_selected, &field_name);
+                                            let checkbox_response = ui.horizontal(|ui| {
+                                                ui.add_space(depth as f32 * 16.0);
+                                                let response = ui.checkbox(&mut is_selected, &field_name);
+                                                if !is_selected && member_ids.contains(&field_id) {
+                                                    ui.label(RichText::new("（经子领域）").size(11.0).color(theme.text_secondary));
+                                                }
+                                                response
+                                            }).inner;
                                             if checkbox_response.changed() {
                                                 if is_selected {
                                                     if !project_mut.field_ids.contains(&field_id) {
@@ -1352,7 +1844,7 @@ impl VennCVApp {
                                     // 勾选领域后自动更新项目位置并解决重叠
                                     if field_changed {
                                         if let Some(id) = &selected_project_id {
-                                            let new_position = self.calculate_project_position(&project_mut);
+                                            let new_position = self.calculate_project_position(&SpatialIndex::new(&self.data), &project_mut);
                                             project_mut.position = new_position;
                                             // 更新编辑中的项目
                                             self.editing_project = Some(project_mut.clone());
@@ -1407,6 +1899,10 @@ impl VennCVApp {
                                     ScrollArea::vertical().show(ui, |ui| {
                             // 显示关系编辑界面
                             let mut needs_save_after = false;
+                            // 关系类型目录（ID, 名称, 默认颜色, 默认线宽），编辑关系时不能再借用 self.data
+                            let relation_types: Vec<(String, String, [u8; 4], f32)> = self.data.sorted_relation_types().iter()
+                                .map(|t| (t.id.clone(), t.name.clone(), t.color, t.width))
+                                .collect();
                             for (idx, from_name, to_name, is_outgoing) in relation_info {
                                 if let Some(relation) = self.data.relations.get_mut(idx) {
                                     let is_expanded = self.expanded_relations.get(&idx).copied().unwrap_or(false);
@@ -1441,10 +1937,12 @@ impl VennCVApp {
                                         if !relation.tags.is_empty() {
                                             // 只显示第一个标签，紧跟在方向文字后面
                                             if let Some(first_tag) = relation.tags.first() {
+                                                let background = tag_chip_color(self.data.relation_tag_colors.get(first_tag));
                                                 ui.label(
                                                     RichText::new(first_tag)
                                                         .size(10.0)
-                                                        .background_color(Color32::from_rgb(200, 200, 200))
+                                                        .color(contrast_text_color(background))
+                                                        .background_color(background)
                                                 );
                                             }
                                             if relation.tags.len() > 1 {
@@ -1474,14 +1972,12 @@ impl VennCVApp {
                                                         .width(120.0) // 固定宽度
                                                         .show_ui(ui, |ui| {
                                                             for (id, proj) in &self.data.projects {
-                                                                if *id != relation.from_id {
-                                                                    if ui.selectable_label(
-                                                                        relation.to_id == *id,
-                                                                        &proj.name
-                                                                    ).clicked() {
-                                                                        new_to_id = id.clone();
-                                                                        to_id_changed = true;
-                                                                    }
+                                                                if *id != relation.from_id && ui.selectable_label(
+                                                                    relation.to_id == *id,
+                                                                    &proj.name
+                                                                ).clicked() {
+                                                                    new_to_id = id.clone();
+                                                                    to_id_changed = true;
                                                                 }
                                                             }
                                                         });
@@ -1496,25 +1992,53 @@ impl VennCVApp {
                                             
                                             ui.add_space(5.0);
                                             
-                                            // 关系类型（虚实）
-                                            let mut relation_type_changed = false;
-                                            let mut new_relation_type = relation.relation_type;
+                                            // 关系类型（线型和箭头样式由类型决定）
+                                            let mut new_relation_type = relation.relation_type.clone();
                                             ui.horizontal(|ui| {
                                                 ui.label("类型: ");
-                                                if ui.radio_value(&mut new_relation_type, RelationType::Direct, "实线").clicked() {
-                                                    relation_type_changed = true;
-                                                }
-                                                if ui.radio_value(&mut new_relation_type, RelationType::Indirect, "虚线").clicked() {
-                                                    relation_type_changed = true;
-                                                }
+                                                let selected_name = relation_types.iter()
+                                                    .find(|(id, ..)| *id == relation.relation_type)
+                                                    .map(|(_, name, ..)| name.clone())
+                                                    .unwrap_or_else(|| relation.relation_type.clone());
+                                                ComboBox::from_id_source(format!("relation_type_{}", idx))
+                                                    .selected_text(selected_name)
+                                                    .show_ui(ui, |ui| {
+                                                        for (id, name, ..) in &relation_types {
+                                                            ui.selectable_value(&mut new_relation_type, id.clone(), name);
+                                                        }
+                                                    });
                                             });
-                                            if relation_type_changed {
+                                            if new_relation_type != relation.relation_type {
+                                                // 换成新类型的默认颜色和线宽
+                                                if let Some((_, _, color, width)) = relation_types.iter().find(|(id, ..)| *id == new_relation_type) {
+                                                    relation.color = *color;
+                                                    relation.width = *width;
+                                                }
                                                 relation.relation_type = new_relation_type;
                                                 needs_save_after = true;
                                             }
                                             
                                             ui.add_space(5.0);
                                             
+                                            // 走线方式
+                                            let mut new_route = relation.route;
+                                            ui.horizontal(|ui| {
+                                                ui.label("走线: ");
+                                                ComboBox::from_id_source(format!("relation_route_{}", idx))
+                                                    .selected_text(new_route.name())
+                                                    .show_ui(ui, |ui| {
+                                                        for route in RelationRoute::all() {
+                                                            ui.selectable_value(&mut new_route, route, route.name());
+                                                        }
+                                                    });
+                                            });
+                                            if new_route != relation.route {
+                                                relation.route = new_route;
+                                                needs_save_after = true;
+                                            }
+                                            
+                                            ui.add_space(5.0);
+                                            
                                             // 线宽
                                             let mut width_changed = false;
                                             let mut new_width = relation.width;
@@ -1568,7 +2092,16 @@ impl VennCVApp {
                                                 ui.set_max_width(200.0); // 固定最大宽度
                                                 // 显示已有标签
                                                 for (tag_idx, tag) in relation.tags.iter().enumerate() {
-                                                    if ui.small_button(format!("{} ✕", tag)).clicked() {
+                                                    // 设置了颜色的标签以该颜色填充
+                                                    let chip = match self.data.relation_tag_colors.get(tag) {
+                                                        Some(color) => {
+                                                            let background = tag_chip_color(Some(color));
+                                                            Button::new(RichText::new(format!("{} ✕", tag)).color(contrast_text_color(background)))
+                                                                .fill(background)
+                                                        }
+                                                        None => Button::new(format!("{} ✕", tag)),
+                                                    };
+                                                    if ui.add(chip.small()).clicked() {
                                                         tags_to_remove.push(tag_idx);
                                                     }
                                                 }
@@ -1586,7 +2119,7 @@ impl VennCVApp {
                                             ui.horizontal(|ui| {
                                                 ui.label("添加标签: ");
                                                 // 获取或初始化输入框内容
-                                                let tag_input = self.relation_tag_inputs.entry(idx).or_insert_with(String::new);
+                                                let tag_input = self.relation_tag_inputs.entry(idx).or_default();
                                                 let response = ui.add(
                                                     TextEdit::singleline(tag_input)
                                                         .hint_text("输入标签名或选择现有标签...")
@@ -1623,25 +2156,23 @@ impl VennCVApp {
                                                 }
                                                 
                                                 // 处理回车键创建新标签 - 类似飞书的效果
-                                                if response.has_focus() {
-                                                    if ctx.input(|i| i.key_pressed(Key::Enter)) && !tag_input.trim().is_empty() {
-                                                        let tag_to_add = tag_input.trim().to_string();
-                                                        if !tag_to_add.is_empty() {
-                                                            // 如果标签不存在于全局标签库，则添加
-                                                            if !self.data.relation_tags.contains(&tag_to_add) {
-                                                                self.data.relation_tags.push(tag_to_add.clone());
-                                                            }
-                                                            // 如果标签不存在于当前关系，则添加
-                                                            if !relation.tags.contains(&tag_to_add) {
-                                                                relation.tags.push(tag_to_add);
-                                                                needs_save_after = true;
-                                                            }
-                                                            // 清空输入框
-                                                            tag_input.clear();
-                                                            ui.memory_mut(|mem| mem.close_popup());
-                                                            // 请求重新绘制
-                                                            ctx.request_repaint();
+                                                if response.has_focus() && ctx.input(|i| i.key_pressed(Key::Enter)) && !tag_input.trim().is_empty() {
+                                                    let tag_to_add = tag_input.trim().to_string();
+                                                    if !tag_to_add.is_empty() {
+                                                        // 如果标签不存在于全局标签库，则添加
+                                                        if !self.data.relation_tags.contains(&tag_to_add) {
+                                                            self.data.relation_tags.push(tag_to_add.clone());
+                                                        }
+                                                        // 如果标签不存在于当前关系，则添加
+                                                        if !relation.tags.contains(&tag_to_add) {
+                                                            relation.tags.push(tag_to_add);
+                                                            needs_save_after = true;
                                                         }
+                                                        // 清空输入框
+                                                        tag_input.clear();
+                                                        ui.memory_mut(|mem| mem.close_popup());
+                                                        // 请求重新绘制
+                                                        ctx.request_repaint();
                                                     }
                                                 }
                                                 // 也处理失去焦点时的回车（如果输入框有内容）
@@ -1673,7 +2204,6 @@ impl VennCVApp {
                                         // 统一保存更改
                                         if needs_save_after {
                                             self.save_to_history();
-                                            return; // 提前返回，下一帧再渲染
                                         }
                                     });
                                     
@@ -1700,17 +2230,13 @@ impl VennCVApp {
                                         .fill(theme.surface)
                                     ).clicked() {
                                         if let Some(id) = &project_id {
-                                            let new_relation = ProjectRelation {
-                                                from_id: id.clone(),
-                                                to_id: String::new(),
-                                                relation_type: RelationType::Direct,
-                                                tags: Vec::new(),
-                                                color: default_relation_color(),
-                                                width: default_relation_width(),
-                                            };
+                                            // 使用目录中的第一种关系类型
+                                            let relation_type = self.data.sorted_relation_types().first()
+                                                .map(|t| t.id.clone())
+                                                .unwrap_or_else(default_relation_type);
+                                            let new_relation = self.data.new_relation(id, "", &relation_type);
                                             self.data.relations.push(new_relation);
                                             self.save_to_history();
-                                            return; // 提前返回，下一帧再渲染
                                         }
                                     }
                                 });
@@ -1741,525 +2267,1803 @@ impl VennCVApp {
                                 .size(11.0)
                                 .color(theme.text_secondary)
                         );
+                        ui.add_space(12.0);
+                        // 视图切换
+                        for view in CentralView::all() {
+                            ui.selectable_value(&mut self.central_view, view, view.name());
+                        }
+                        // 视图筛选提示
+                        if self.view_filtered() || self.active_view.is_some() {
+                            ui.add_space(12.0);
+                            let label = match &self.active_view {
+                                Some(name) => format!("视图: {}", name),
+                                None => "已筛选".to_string(),
+                            };
+                            ui.label(RichText::new(label).size(11.0).color(theme.info));
+                            if self.view_filtered() && ui.small_button("显示全部").clicked() {
+                                self.clear_view_filter();
+                            }
+                        }
                     });
                     ui.add_space(4.0);
                     ui.separator();
                     ui.add_space(4.0);
 
-                // 获取可用区域（排除标题和分隔符）
-                let available_rect = ui.available_rect_before_wrap();
-                let response = ui.allocate_response(available_rect.size(), Sense::click_and_drag());
-                
-                // 绘制可视化
-                let painter = ui.painter();
-                let rect = response.rect;
-                
-                // 使用设置中的背景颜色
-                let bg_color = Color32::from_rgba_unmultiplied(
-                    self.settings.visualization_bg_color[0],
-                    self.settings.visualization_bg_color[1],
-                    self.settings.visualization_bg_color[2],
-                    self.settings.visualization_bg_color[3],
-                );
-                painter.rect_filled(rect, 0.0, bg_color);
+                    // 布局任务期间维恩图只可平移缩放（显示中间结果），其他视图不可编辑
+                    if self.layout_worker.is_some() && self.central_view != CentralView::Venn {
+                        ui.set_enabled(false);
+                    }
+                    match self.central_view {
+                        CentralView::Venn => self.venn_canvas(ui, ctx),
+                        CentralView::Timeline => self.timeline_view(ui, ctx),
+                        CentralView::Kanban => self.kanban_view(ui),
+                        CentralView::Table => self.table_view(ui),
+                    }
+            });
+        }
+    }
 
-                // 计算缩放比例，使可视化图适应窗口大小
-                // 获取所有项目的位置范围
-                let mut min_x = f32::MAX;
-                let mut max_x = f32::MIN;
-                let mut min_y = f32::MAX;
-                let mut max_y = f32::MIN;
-                let mut has_content = false;
-
-                // 考虑所有领域的位置和半径
-                for field in self.data.fields.values() {
-                    min_x = min_x.min(field.position.0 - field.radius);
-                    max_x = max_x.max(field.position.0 + field.radius);
-                    min_y = min_y.min(field.position.1 - field.radius);
-                    max_y = max_y.max(field.position.1 + field.radius);
-                    has_content = true;
-                }
+    /// 维恩图画布：绘制领域、项目和关系，处理选中、拖拽、平移和缩放
+    fn venn_canvas(&mut self, ui: &mut Ui, ctx: &Context) {
+        let theme = self.theme();
+        // 视图筛选：只绘制显示的项目和领域
+        let view_filter = self.view_query();
+        let visible_project_ids = self.visible_project_ids(&view_filter);
+        let visible_field_ids = self.visible_field_ids(&visible_project_ids);
+        let visible_relations = self.visible_relation_indices(&visible_project_ids, &view_filter);
+        
+        // 获取可用区域（排除标题和分隔符）
+        let available_rect = ui.available_rect_before_wrap();
+        let response = ui.allocate_response(available_rect.size(), Sense::click_and_drag());
+        let layout_running = self.layout_worker.is_some();
+        
+        // 绘制可视化
+        let painter = ui.painter();
+        let rect = response.rect;
+        
+        // 背景颜色：跟随主题或使用设置中的颜色
+        let bg_color = self.canvas_background(&theme);
+        painter.rect_filled(rect, 0.0, bg_color);
 
-                // 考虑所有项目的位置
-                for project in self.data.projects.values() {
-                    min_x = min_x.min(project.position.0 - project.radius);
-                    max_x = max_x.max(project.position.0 + project.radius);
-                    min_y = min_y.min(project.position.1 - project.radius);
-                    max_y = max_y.max(project.position.1 + project.radius);
-                    has_content = true;
-                }
+        // 计算缩放比例，使可视化图适应窗口大小
+        // 获取所有项目的位置范围
+        let mut min_x = f32::MAX;
+        let mut max_x = f32::MIN;
+        let mut min_y = f32::MAX;
+        let mut max_y = f32::MIN;
+        let mut has_content = false;
 
-                // 计算内容范围和缩放
-                let (content_width, content_height, content_center_x, content_center_y, auto_scale) = if has_content {
-                    let width = (max_x - min_x).max(100.0);
-                    let height = (max_y - min_y).max(100.0);
-                    let center_x = (min_x + max_x) / 2.0;
-                    let center_y = (min_y + max_y) / 2.0;
-                    
-                    // 计算适合窗口的缩放比例（留出边距）
-                    let margin = 40.0;
-                    let scale_x = (rect.width() - margin * 2.0) / width;
-                    let scale_y = (rect.height() - margin * 2.0) / height;
-                    let scale = scale_x.min(scale_y).min(2.0).max(0.1); // 限制缩放范围
-                    
-                    (width, height, center_x, center_y, scale)
-                } else {
-                    // 没有内容时使用默认值
-                    (800.0, 600.0, 400.0, 400.0, 1.0)
-                };
+        // 考虑显示的领域的位置和半径
+        for field in self.data.fields.values().filter(|f| visible_field_ids.contains(&f.id)) {
+            min_x = min_x.min(field.position.0 - field.radius);
+            max_x = max_x.max(field.position.0 + field.radius);
+            min_y = min_y.min(field.position.1 - field.radius);
+            max_y = max_y.max(field.position.1 + field.radius);
+            has_content = true;
+        }
 
-                // 计算偏移，使内容居中
-                let window_center_x = rect.center().x;
-                let window_center_y = rect.center().y;
-                let offset_x = window_center_x - content_center_x * auto_scale * self.visualization_zoom;
-                let offset_y = window_center_y - content_center_y * auto_scale * self.visualization_zoom;
-
-                // 绘制研究领域（大圈）- 只绘制有项目的领域
-                for field in self.data.fields.values() {
-                    // 检查是否有项目属于该领域
-                    let has_projects = self.data.projects.values().any(|p| p.field_ids.contains(&field.id));
-                    if !has_projects {
-                        continue; // 跳过没有项目的领域
-                    }
-                    
-                    let center = pos2(
-                        offset_x + field.position.0 * auto_scale * self.visualization_zoom
-                            + self.visualization_offset.x,
-                        offset_y + field.position.1 * auto_scale * self.visualization_zoom
-                            + self.visualization_offset.y,
-                    );
-                    let radius = field.radius * auto_scale * self.visualization_zoom;
+        // 考虑显示的项目的位置
+        for project in self.data.projects.values().filter(|p| visible_project_ids.contains(&p.id)) {
+            min_x = min_x.min(project.position.0 - project.radius);
+            max_x = max_x.max(project.position.0 + project.radius);
+            min_y = min_y.min(project.position.1 - project.radius);
+            max_y = max_y.max(project.position.1 + project.radius);
+            has_content = true;
+        }
+
+        // 计算内容范围和缩放
+        let (content_width, content_height, content_center_x, content_center_y, auto_scale) = if has_content {
+            let width = (max_x - min_x).max(100.0);
+            let height = (max_y - min_y).max(100.0);
+            let center_x = (min_x + max_x) / 2.0;
+            let center_y = (min_y + max_y) / 2.0;
+            
+            // 计算适合窗口的缩放比例（留出边距）
+            let margin = 40.0;
+            let scale_x = (rect.width() - margin * 2.0) / width;
+            let scale_y = (rect.height() - margin * 2.0) / height;
+            let scale = scale_x.min(scale_y).clamp(0.1, 2.0); // 限制缩放范围
+            
+            (width, height, center_x, center_y, scale)
+        } else {
+            // 没有内容时使用默认值
+            (800.0, 600.0, 400.0, 400.0, 1.0)
+        };
 
-                    // 绘制透明圆圈（只绘制边框，不填充）
-                    painter.circle_stroke(center, radius, (self.settings.field_border_width, Color32::GRAY));
+        // 计算偏移，使内容居中
+        let window_center_x = rect.center().x;
+        let window_center_y = rect.center().y;
+        let offset_x = window_center_x - content_center_x * auto_scale * self.visualization_zoom;
+        let offset_y = window_center_y - content_center_y * auto_scale * self.visualization_zoom;
 
-                    // 绘制领域名称 - 确保文字在外侧，避免重叠
-                    // 计算文字位置，确保在不同角度，避免全部在上方
-                    // 找到与其他领域圆圈最近的交点，在相反方向放置文字
-                    let mut best_angle = -std::f32::consts::PI / 2.0; // 默认上方
-                    let mut min_distance = f32::MAX;
-                    
-                    // 检查与其他领域圆圈的距离（使用原始坐标，因为我们需要计算相对位置）
-                    let field_center_scaled = pos2(
-                        offset_x + field.position.0 * auto_scale * self.visualization_zoom + self.visualization_offset.x,
-                        offset_y + field.position.1 * auto_scale * self.visualization_zoom + self.visualization_offset.y,
-                    );
-                    
-                    for other_field in self.data.fields.values() {
-                        if other_field.id != field.id {
-                            let other_center_scaled = pos2(
-                                offset_x + other_field.position.0 * auto_scale * self.visualization_zoom + self.visualization_offset.x,
-                                offset_y + other_field.position.1 * auto_scale * self.visualization_zoom + self.visualization_offset.y,
-                            );
-                            let other_radius_scaled = other_field.radius * auto_scale * self.visualization_zoom;
-                            
-                            let dx = other_center_scaled.x - field_center_scaled.x;
-                            let dy = other_center_scaled.y - field_center_scaled.y;
-                            let distance = (dx * dx + dy * dy).sqrt();
+        // 定位请求：平移视图使该点位于窗口中心
+        if let Some((x, y)) = self.focus_request.take() {
+            self.visualization_offset = vec2(
+                window_center_x - offset_x - x * auto_scale * self.visualization_zoom,
+                window_center_y - offset_y - y * auto_scale * self.visualization_zoom,
+            );
+        }
+
+        // 先绘制领域的半透明填充，再绘制边框和名称，相交处填充颜色叠加且不遮住边框
+        for field in self.data.fields.values().filter(|f| visible_field_ids.contains(&f.id)) {
+            let (_, fill_color) = field_colors(field);
+            if fill_color.a() == 0 {
+                continue;
+            }
+            let center = pos2(
+                offset_x + field.position.0 * auto_scale * self.visualization_zoom
+                    + self.visualization_offset.x,
+                offset_y + field.position.1 * auto_scale * self.visualization_zoom
+                    + self.visualization_offset.y,
+            );
+            painter.circle_filled(center, field.radius * auto_scale * self.visualization_zoom, fill_color);
+        }
+
+        // 绘制研究领域（大圈）- 只绘制有显示项目的领域
+        for field in self.data.fields.values() {
+            if !visible_field_ids.contains(&field.id) {
+                continue; // 跳过隐藏或没有显示项目的领域
+            }
+            
+            let center = pos2(
+                offset_x + field.position.0 * auto_scale * self.visualization_zoom
+                    + self.visualization_offset.x,
+                offset_y + field.position.1 * auto_scale * self.visualization_zoom
+                    + self.visualization_offset.y,
+            );
+            let radius = field.radius * auto_scale * self.visualization_zoom;
+            // 子领域边框更细、名称更小（与图片导出一致）
+            let (border_width, font_size) = field_stroke_and_font(self.settings.field_border_width, self.data.field_depth(&field.id));
+
+            // 绘制边框（使用领域设置的颜色）
+            let (stroke_color, _) = field_colors(field);
+            painter.circle_stroke(center, radius, (border_width, stroke_color));
+
+            // 绘制领域名称 - 确保文字在外侧，避免重叠
+            // 找到与其他领域圆圈最近的交点，在相反方向放置文字（与布局诊断的估算一致）
+            let best_angle = layout::field_label_angle(
+                field,
+                self.data.fields.values().filter(|f| visible_field_ids.contains(&f.id)),
+            );
+            
+            // 计算文字位置（在圆圈外侧，距离圆心 radius + 25 的位置）
+            let text_distance = radius + 25.0;
+            let mut text_x = center.x + text_distance * best_angle.cos();
+            let mut text_y = center.y + text_distance * best_angle.sin();
+            
+            // 估算文字尺寸（每个字符约10像素宽，高度约20像素）
+            let text_width = field.name.chars().count() as f32 * 10.0;
+            let text_height = 20.0;
+            let half_width = text_width / 2.0;
+            let half_height = text_height / 2.0;
+            
+            // 确保文字不超出可视化窗口边界
+            let margin = 5.0;
+            text_x = text_x.clamp(rect.left() + half_width + margin, rect.right() - half_width - margin);
+            text_y = text_y.clamp(rect.top() + half_height + margin, rect.bottom() - half_height - margin);
+            
+            // 确保文字不进入圆圈内（检查文字中心到圆心距离）
+            let dx = text_x - center.x;
+            let dy = text_y - center.y;
+            let dist_to_center = (dx * dx + dy * dy).sqrt();
+            let min_dist = radius + half_height + 5.0;  // 至少在圆圈外 + 文字半高 + 5像素
+            if dist_to_center < min_dist && dist_to_center > 0.0 {
+                // 将文字推到圆圈外
+                let scale = min_dist / dist_to_center;
+                text_x = center.x + dx * scale;
+                text_y = center.y + dy * scale;
+                // 再次检查边界
+                text_x = text_x.clamp(rect.left() + half_width + margin, rect.right() - half_width - margin);
+                text_y = text_y.clamp(rect.top() + half_height + margin, rect.bottom() - half_height - margin);
+            }
+            
+            // 锁定的领域在名称前显示锁形图标；色块样式在名称后面垫一块边框颜色
+            let label = if field.locked { format!("🔒 {}", field.name) } else { field.name.clone() };
+            let (label_color, label_background) = field_label_colors(&theme, field);
+            let galley = painter.layout_no_wrap(label, FontId::proportional(font_size), label_color);
+            let label_rect = Align2::CENTER_CENTER.anchor_size(pos2(text_x, text_y), galley.size());
+            if let Some(background) = label_background {
+                painter.rect_filled(label_rect.expand2(vec2(5.0, 2.0)), 3.0, background);
+            }
+            painter.galley(label_rect.min, galley, label_color);
+        }
+
+        // 绘制项目关系（箭头），走线与图片导出共用 route 模块的计算结果
+        let relation_paths = route::relation_paths(&self.data, &visible_relations, &visible_project_ids);
+        let to_screen = |(x, y): (f32, f32)| pos2(
+            offset_x + x * auto_scale * self.visualization_zoom + self.visualization_offset.x,
+            offset_y + y * auto_scale * self.visualization_zoom + self.visualization_offset.y,
+        );
+        for (relation_index, relation) in self.data.relations.iter().enumerate() {
+            let Some(path) = relation_paths.get(&relation_index) else {
+                continue;
+            };
+            let points: Vec<Pos2> = path.points.iter().map(|p| to_screen(*p)).collect();
+
+            // 使用关系设置的颜色和宽度（默认黑色跟随主题，保证深色背景下可见）
+            let mut color = relation_display_color(&theme, relation);
+            // 搜索时淡化与不匹配项目相连的关系
+            if self.search_filter.as_ref().is_some_and(|q| !q.matches_relation(&self.data, relation)) {
+                color = color.gamma_multiply(0.2);
+            }
+
+            // 按关系类型的线型和箭头样式绘制
+            let (line_style, arrow_head) = self.data.relation_style(relation);
+            paint_relation_line(painter, &points, line_style, arrow_head, Stroke::new(relation.width, color), 10.0);
+
+            // 绘制关系标签（在走线中点旁）
+            if !relation.tags.is_empty() {
+                let (mid_point, (mx, my)) = path.midpoint();
+                let text_pos = to_screen(mid_point) + vec2(-my, mx) * 15.0;  // 偏移一点距离
+                let tags_text = relation.tags.join(", ");
+                painter.text(
+                    text_pos,
+                    Align2::CENTER_CENTER,
+                    &tags_text,
+                    FontId::proportional(10.0),
+                    relation_label_color(&theme, &self.data, relation),
+                );
+            }
+        }
+
+        // 收集所有项目位置用于点击检测
+        let mut project_centers = Vec::new();
+        
+        // 绘制项目（小圈）
+        for (idx, project) in self.data.projects.values().enumerate() {
+            if !visible_project_ids.contains(&project.id) {
+                continue;  // 编号仍按数据顺序，与数据表一致
+            }
+            let center = pos2(
+                offset_x + project.position.0 * auto_scale * self.visualization_zoom
+                    + self.visualization_offset.x,
+                offset_y + project.position.1 * auto_scale * self.visualization_zoom
+                    + self.visualization_offset.y,
+            );
+            let radius = project.radius * auto_scale * self.visualization_zoom;
+            project_centers.push((project.id.clone(), center, radius, project.clone()));
+
+            let color = self.status_color(&theme, &project.status);
+            let status_index = self.data.status_index(&project.status);
+            let is_selected = self.selected_project.as_ref() == Some(&project.id)
+                || self.selected_project_ids.contains(&project.id);
+
+            // 根据完成度百分比计算边界颜色
+            let border_color = theme.completion_percentage_to_color(project.completion_percentage);
+
+            // 绘制项目圆圈
+            painter.circle_filled(center, radius, color);
+            if is_selected {
+                // 选中时显示蓝色边框
+                painter.circle_stroke(center, radius + 3.0, (3.0, Color32::BLUE));
+                // 在蓝色边框内绘制完成度颜色边框
+                painter.circle_stroke(center, radius, (self.settings.project_border_width, border_color));
+            } else {
+                // 使用完成度百分比颜色作为边框
+                painter.circle_stroke(center, radius, (self.settings.project_border_width, border_color));
+            }
+
+            // 非颜色编码：状态图案/符号和完成度弧线
+            match self.settings.status_marker {
+                StatusMarker::None => {}
+                StatusMarker::Pattern => paint_status_pattern(painter, center, radius, status_index, color),
+                StatusMarker::Glyph => paint_status_glyph(painter, center, radius, status_index, theme.canvas_text, self.canvas_background(&theme)),
+            }
+            if self.settings.show_completion_ring {
+                let ring_radius = radius + self.settings.project_border_width / 2.0 + if is_selected { 7.0 } else { 3.0 };
+                paint_completion_ring(painter, center, ring_radius, project.completion_percentage, theme.canvas_text);
+            }
+
+            // 绘制项目编号（在圆圈中心）
+            if self.settings.show_project_numbers {
+                let project_num = idx + 1;
+                let text_color = contrast_text_color(color);
+                painter.text(
+                    center,
+                    Align2::CENTER_CENTER,
+                    project_num.to_string(),
+                    FontId::proportional(10.0),
+                    text_color,
+                );
+            }
+
+            // 锁定的项目在右上方显示锁形图标
+            if project.locked {
+                painter.text(
+                    center + vec2(radius, -radius) * 0.8,
+                    Align2::CENTER_CENTER,
+                    "🔒",
+                    FontId::proportional(10.0),
+                    theme.canvas_text,
+                );
+            }
+
+            // 搜索时淡化不匹配的项目：覆盖一层半透明背景色
+            let dimmed = self.search_results.as_ref().is_some_and(|r| !r.contains(&project.id));
+            if dimmed {
+                let cover_radius = radius + self.settings.project_border_width / 2.0 + 10.0;
+                painter.circle_filled(center, cover_radius, self.canvas_background(&theme).gamma_multiply(0.75));
+            }
+
+            // 绘制项目名称
+            if self.settings.show_project_names {
+                painter.text(
+                    pos2(center.x, center.y + radius + 15.0),
+                    Align2::CENTER_CENTER,
+                    &project.name,
+                    FontId::proportional(12.0),
+                    if dimmed { theme.canvas_text.gamma_multiply(0.25) } else { theme.canvas_text },
+                );
+            }
+
+            // 项目标签徽章（在名称下方，没有名称时紧贴圆圈）
+            if let Some(badge) = self.settings.show_project_tags.then(|| project_tag_badge(&project.tags)).flatten() {
+                let badge_y = center.y + radius + if self.settings.show_project_names { 30.0 } else { 12.0 };
+                let galley = painter.layout_no_wrap(badge, FontId::proportional(9.0), theme.canvas_text_secondary);
+                let badge_rect = Rect::from_center_size(pos2(center.x, badge_y), galley.size() + vec2(8.0, 2.0));
+                let alpha = if dimmed { 0.25 } else { 1.0 };
+                painter.rect_filled(badge_rect, badge_rect.height() / 2.0, theme.canvas_text.gamma_multiply(0.1 * alpha));
+                painter.galley_with_override_text_color(
+                    badge_rect.center() - galley.size() / 2.0,
+                    galley,
+                    theme.canvas_text_secondary.gamma_multiply(alpha),
+                );
+            }
+        }
+
+        // 布局诊断标记
+        if let Some(report) = &self.layout_report {
+            let scale = auto_scale * self.visualization_zoom;
+            let origin = vec2(offset_x, offset_y) + self.visualization_offset;
+            self.paint_layout_issues(painter, report, &visible_project_ids, &visible_field_ids, origin, scale);
+        }
+
+        // 绘制图例（在右上角）
+        self.draw_legend(painter, rect, &theme, &visible_field_ids, &visible_relations);
+        
+        // 获取图例矩形用于交互检测
+        let legend_rect = self.get_legend_rect(rect, &visible_field_ids, &visible_relations);
+        let mouse_pos = ctx.input(|i| i.pointer.hover_pos());
+        let is_over_legend = mouse_pos.map(|p| legend_rect.contains(p)).unwrap_or(false);
+        
+        // 处理图例双击（打开设置）
+        if response.double_clicked() {
+            if let Some(click_pos) = response.interact_pointer_pos() {
+                if legend_rect.contains(click_pos) {
+                    self.show_legend_settings = true;
+                }
+            }
+        }
+        
+        // 处理图例拖拽
+        if is_over_legend && response.drag_started() {
+            self.legend_dragging = true;
+        }
+        
+        if self.legend_dragging {
+            if response.dragged() {
+                self.legend_position += response.drag_delta();
+            }
+            if response.drag_stopped() {
+                self.legend_dragging = false;
+            }
+        }
+
+        // 右键点击未选中的项目时先选中它，再弹出菜单
+        if response.secondary_clicked() && !layout_running {
+            if let Some(click_pos) = response.interact_pointer_pos() {
+                let hit = project_centers.iter().find(|(_, center, radius, _)| (click_pos - *center).length() <= *radius);
+                if let Some((id, _, _, project)) = hit {
+                    if !self.selected_project_ids.contains(id) {
+                        self.selected_project = Some(id.clone());
+                        self.selected_project_ids = vec![id.clone()];
+                        self.editing_project = Some(project.clone());
+                    }
+                }
+            }
+        }
+
+        // 处理点击（在绘制完成后）- 排除图例区域
+        if response.clicked() && !is_over_legend && !layout_running {
+            if let Some(click_pos) = response.interact_pointer_pos() {
+                let modifiers = ui.input(|i| i.modifiers);
+                let cmd_pressed = modifiers.command; // macOS: Command, Windows/Linux: Ctrl
+                let shift_pressed = modifiers.shift;
+                let mut hit_project = false;
+                
+                for (id, center, radius, project) in &project_centers {
+                    let distance = (click_pos - *center).length();
+                    if distance <= *radius {
+                        hit_project = true;
+                        // 如果按住 Command 且已有选中项目，则建立关系
+                        if cmd_pressed && self.selected_project.is_some() {
+                            let from_id = self.selected_project.clone().unwrap();
+                            let to_id = id.clone();
                             
-                            // 如果两个圆圈有交集或很接近，计算角度
-                            if distance < (radius + other_radius_scaled) * 1.5 {
-                                let angle = dy.atan2(dx);
+                            // 不能建立到自己的关系
+                            if from_id != to_id {
+                                // 检查是否已存在相同的关系
+                                let relation_exists = self.data.relations.iter().any(|r| {
+                                    r.from_id == from_id && r.to_id == to_id
+                                });
                                 
-                                // 如果这个角度对应的位置更外侧，使用它
-                                if distance < min_distance {
-                                    min_distance = distance;
-                                    // 文字应该在相反方向（远离其他领域的方向）
-                                    best_angle = angle + std::f32::consts::PI;
+                                if !relation_exists {
+                                    self.save_to_history();
+                                    // Command: 目录中第一种关系类型（默认为直接），Command+Shift: 第二种（默认为间接）
+                                    let types = self.data.sorted_relation_types();
+                                    let relation_type = types.get(usize::from(shift_pressed))
+                                        .or(types.first())
+                                        .map(|t| t.id.clone())
+                                        .unwrap_or_else(default_relation_type);
+                                    
+                                    let relation = self.data.new_relation(&from_id, &to_id, &relation_type);
+                                    self.data.relations.push(relation);
+                                }
+                            }
+                        } else if shift_pressed {
+                            // Shift+点击：加入或移出多选
+                            if let Some(pos) = self.selected_project_ids.iter().position(|p| p == id) {
+                                self.selected_project_ids.remove(pos);
+                                if self.selected_project.as_ref() == Some(id) {
+                                    self.selected_project = self.selected_project_ids.last().cloned();
+                                    self.editing_project = self.selected_project.as_ref()
+                                        .and_then(|p| self.data.projects.get(p))
+                                        .cloned();
                                 }
+                            } else {
+                                self.selected_project_ids.push(id.clone());
+                                self.selected_project = Some(id.clone());
+                                self.editing_project = Some(project.clone());
                             }
+                        } else {
+                            // 普通点击：只选中该项目
+                            self.selected_project = Some(id.clone());
+                            self.selected_project_ids = vec![id.clone()];
+                            self.editing_project = Some(project.clone());
                         }
+                        break;
                     }
-                    
-                    // 计算文字位置（在圆圈外侧，距离圆心 radius + 25 的位置）
-                    let text_distance = radius + 25.0;
-                    let mut text_x = center.x + text_distance * best_angle.cos();
-                    let mut text_y = center.y + text_distance * best_angle.sin();
-                    
-                    // 估算文字尺寸（每个字符约10像素宽，高度约20像素）
-                    let text_width = field.name.chars().count() as f32 * 10.0;
-                    let text_height = 20.0;
-                    let half_width = text_width / 2.0;
-                    let half_height = text_height / 2.0;
-                    
-                    // 确保文字不超出可视化窗口边界
-                    let margin = 5.0;
-                    text_x = text_x.clamp(rect.left() + half_width + margin, rect.right() - half_width - margin);
-                    text_y = text_y.clamp(rect.top() + half_height + margin, rect.bottom() - half_height - margin);
-                    
-                    // 确保文字不进入圆圈内（检查文字中心到圆心距离）
-                    let dx = text_x - center.x;
-                    let dy = text_y - center.y;
-                    let dist_to_center = (dx * dx + dy * dy).sqrt();
-                    let min_dist = radius + half_height + 5.0;  // 至少在圆圈外 + 文字半高 + 5像素
-                    if dist_to_center < min_dist && dist_to_center > 0.0 {
-                        // 将文字推到圆圈外
-                        let scale = min_dist / dist_to_center;
-                        text_x = center.x + dx * scale;
-                        text_y = center.y + dy * scale;
-                        // 再次检查边界
-                        text_x = text_x.clamp(rect.left() + half_width + margin, rect.right() - half_width - margin);
-                        text_y = text_y.clamp(rect.top() + half_height + margin, rect.bottom() - half_height - margin);
+                }
+                
+                // 点击空白处：取消选中
+                if !hit_project && !cmd_pressed && !shift_pressed {
+                    self.selected_project = None;
+                    self.editing_project = None;
+                }
+            }
+        }
+        
+        // 处理拖拽开始：Shift+拖拽空白处框选，拖拽项目移动（拖拽多选中的项目时整组移动）
+        if response.drag_started() && !is_over_legend && !layout_running {
+            if let Some(drag_pos) = response.interact_pointer_pos() {
+                let hit = project_centers.iter().find(|(_, center, radius, _)| (drag_pos - *center).length() <= *radius);
+                match hit {
+                    Some((id, _, _, project)) => {
+                        if !self.selected_project_ids.contains(id) {
+                            self.selected_project_ids = vec![id.clone()];
+                        }
+                        self.selected_project = Some(id.clone());
+                        self.editing_project = Some(project.clone());
+                        // 锁定的项目不能拖动，整组拖动时锁定的项目留在原位
+                        if !project.locked {
+                            self.dragging_project = Some(id.clone());
+                            self.drag_group = self.selected_project_ids.iter()
+                                .filter_map(|p| self.data.projects.get(p))
+                                .filter(|p| !p.locked)
+                                .map(|p| (p.id.clone(), p.position))
+                                .collect();
+                        }
                     }
-                    
-                    painter.text(
-                        pos2(text_x, text_y),
-                        Align2::CENTER_CENTER,
-                        &field.name,
-                        FontId::proportional(16.0),
-                        Color32::BLACK,
-                    );
+                    None if ui.input(|i| i.modifiers.shift) => {
+                        self.box_select_start = Some(ctx.input(|i| i.pointer.press_origin()).unwrap_or(drag_pos));
+                    }
+                    None => {}
                 }
-
-                // 绘制项目关系（箭头）
-                for relation in &self.data.relations {
-                    if let (Some(from), Some(to)) = (
-                        self.data.projects.get(&relation.from_id),
-                        self.data.projects.get(&relation.to_id),
-                    ) {
-                        let from_pos = pos2(
-                            offset_x + from.position.0 * auto_scale * self.visualization_zoom
-                                + self.visualization_offset.x,
-                            offset_y + from.position.1 * auto_scale * self.visualization_zoom
-                                + self.visualization_offset.y,
-                        );
-                        let to_pos = pos2(
-                            offset_x + to.position.0 * auto_scale * self.visualization_zoom
-                                + self.visualization_offset.x,
-                            offset_y + to.position.1 * auto_scale * self.visualization_zoom
-                                + self.visualization_offset.y,
-                        );
-
-                        // 使用关系设置的颜色和宽度
-                        let color = Color32::from_rgba_unmultiplied(
-                            relation.color[0],
-                            relation.color[1],
-                            relation.color[2],
-                            relation.color[3],
-                        );
-                        // 绘制箭头
-                        let dir = (to_pos - from_pos).normalized();
-                        let from_radius_scaled = from.radius * auto_scale * self.visualization_zoom;
-                        let to_radius_scaled = to.radius * auto_scale * self.visualization_zoom;
-                        let arrow_start = from_pos + dir * from_radius_scaled;
-                        let arrow_end = to_pos - dir * to_radius_scaled;
-
-                        let stroke = match relation.relation_type {
-                            RelationType::Direct => {
-                                // 实线：直接绘制
-                                let stroke = Stroke::new(relation.width, color);
-                                painter.line_segment([arrow_start, arrow_end], stroke);
-                                stroke
-                            }
-                            RelationType::Indirect => {
-                                // 虚线：手动绘制多个小线段
-                                let stroke = Stroke::new(relation.width * 0.7, color);
-                                let dash_length: f32 = 5.0;
-                                let gap_length: f32 = 5.0;
-                                let total_length = (arrow_end - arrow_start).length();
-                                let mut current_pos = arrow_start;
-                                let mut distance: f32 = 0.0;
-                                
-                                while distance < total_length {
-                                    let remaining = total_length - distance;
-                                    let segment_length = dash_length.min(remaining);
-                                    let next_pos = current_pos + dir * segment_length;
-                                    painter.line_segment([current_pos, next_pos], stroke);
-                                    
-                                    distance += segment_length + gap_length;
-                                    current_pos = arrow_start + dir * distance.min(total_length);
-                                    
-                                    if distance >= total_length {
-                                        break;
-                                    }
-                                }
-                                stroke
+            }
+        }
+        
+        // 处理项目拖拽中
+        if response.dragged() && self.dragging_project.is_some() {
+            let delta = response.drag_delta();
+            // 转换屏幕坐标变化到数据坐标变化
+            let scale = auto_scale * self.visualization_zoom;
+            let data_delta_x = delta.x / scale;
+            let data_delta_y = delta.y / scale;
+            
+            for (project_id, _) in &self.drag_group {
+                if let Some(project) = self.data.projects.get_mut(project_id) {
+                    project.position.0 += data_delta_x;
+                    project.position.1 += data_delta_y;
+                }
+                // 同步更新编辑中的项目
+                if let Some(editing) = &mut self.editing_project {
+                    if &editing.id == project_id {
+                        editing.position.0 += data_delta_x;
+                        editing.position.1 += data_delta_y;
+                    }
+                }
+            }
+        }
+        
+        // 处理项目拖拽结束
+        if response.drag_stopped() && self.dragging_project.is_some() {
+            let group = std::mem::take(&mut self.drag_group);
+            if group.len() > 1 {
+                self.settle_group_move(&group);
+            } else if let Some(project_id) = &self.dragging_project.clone() {
+                // 检查位置是否正确，不正确则移动到最近的正确位置
+                if let Some(project) = self.data.projects.get(project_id).cloned() {
+                    let index = SpatialIndex::new(&self.data);
+                    if !self.is_project_position_valid(&index, &project) {
+                        let new_pos = self.find_nearest_valid_position(&index, &project);
+                        if let Some(p) = self.data.projects.get_mut(project_id) {
+                            p.position = new_pos;
+                        }
+                        // 同步更新编辑中的项目
+                        if let Some(editing) = &mut self.editing_project {
+                            if &editing.id == project_id {
+                                editing.position = new_pos;
                             }
-                        };
-
-                        // 绘制箭头头部
-                        let arrow_size = 10.0;
-                        let arrow_dir = (arrow_end - arrow_start).normalized();
-                        let perp = vec2(-arrow_dir.y, arrow_dir.x);
-                        let arrow_tip1 = arrow_end - arrow_dir * arrow_size + perp * arrow_size * 0.5;
-                        let arrow_tip2 = arrow_end - arrow_dir * arrow_size - perp * arrow_size * 0.5;
-                        painter.line_segment([arrow_end, arrow_tip1], stroke);
-                        painter.line_segment([arrow_end, arrow_tip2], stroke);
-                        
-                        // 绘制关系标签（在箭头中点）
-                        if !relation.tags.is_empty() {
-                            let mid_point = (arrow_start.to_vec2() + arrow_end.to_vec2()) / 2.0;
-                            let text_pos = pos2(mid_point.x, mid_point.y) + perp * 15.0;  // 偏移一点距离
-                            let tags_text = relation.tags.join(", ");
-                            painter.text(
-                                text_pos,
-                                Align2::CENTER_CENTER,
-                                &tags_text,
-                                FontId::proportional(10.0),
-                                Color32::DARK_GRAY,
-                            );
                         }
                     }
                 }
+            }
+            // 保存历史记录
+            self.save_to_history();
+            // 保存数据
+            self.save_data();
+            self.dragging_project = None;
+        }
 
-                // 收集所有项目位置用于点击检测
-                let mut project_centers = Vec::new();
-                
-                // 绘制项目（小圈）
-                for (idx, project) in self.data.projects.values().enumerate() {
-                    let center = pos2(
-                        offset_x + project.position.0 * auto_scale * self.visualization_zoom
-                            + self.visualization_offset.x,
-                        offset_y + project.position.1 * auto_scale * self.visualization_zoom
-                            + self.visualization_offset.y,
-                    );
-                    let radius = project.radius * auto_scale * self.visualization_zoom;
-                    project_centers.push((project.id.clone(), center, radius, project.clone()));
-
-                    let color = project.status.color();
-                    let is_selected = self.selected_project.as_ref() == Some(&project.id);
-
-                    // 根据完成度百分比计算边界颜色
-                    let border_color = self.completion_percentage_to_color(project.completion_percentage);
-
-                    // 绘制项目圆圈
-                    painter.circle_filled(center, radius, color);
-                    if is_selected {
-                        // 选中时显示蓝色边框
-                        painter.circle_stroke(center, radius + 3.0, (3.0, Color32::BLUE));
-                        // 在蓝色边框内绘制完成度颜色边框
-                        painter.circle_stroke(center, radius, (self.settings.project_border_width, border_color));
-                    } else {
-                        // 使用完成度百分比颜色作为边框
-                        painter.circle_stroke(center, radius, (self.settings.project_border_width, border_color));
+        // 框选：绘制选框，松开时将框内的项目加入多选
+        if let Some(start) = self.box_select_start {
+            let current = ctx.input(|i| i.pointer.interact_pos()).unwrap_or(start);
+            let selection_rect = Rect::from_two_pos(start, current);
+            ui.painter().rect(selection_rect, 0.0, theme.selection.gamma_multiply(0.2), (1.0, theme.selection));
+            if response.drag_stopped() || !response.dragged() {
+                for (id, center, _, _) in &project_centers {
+                    if selection_rect.contains(*center) && !self.selected_project_ids.contains(id) {
+                        self.selected_project_ids.push(id.clone());
                     }
+                }
+                let primary_selected = self.selected_project.as_ref().is_some_and(|id| self.selected_project_ids.contains(id));
+                if !primary_selected {
+                    self.selected_project = self.selected_project_ids.last().cloned();
+                }
+                self.editing_project = self.selected_project.as_ref()
+                    .and_then(|id| self.data.projects.get(id))
+                    .cloned();
+                self.box_select_start = None;
+            }
+        }
 
-                    // 绘制项目编号（在圆圈中心）
-                    if self.settings.show_project_numbers {
-                        let project_num = idx + 1;
-                        let text_color = if color == Color32::from_rgb(255, 255, 255) {
-                            Color32::BLACK
+        // 处理拖拽和缩放 - 排除图例拖拽和项目拖拽
+        if response.dragged() && !self.legend_dragging && self.dragging_project.is_none() && self.box_select_start.is_none() {
+            // 计算内容的边界（考虑缩放）
+            let scaled_content_width = content_width * auto_scale * self.visualization_zoom;
+            let scaled_content_height = content_height * auto_scale * self.visualization_zoom;
+            
+            // 边距
+            let margin = 20.0;
+            
+            // 计算新的偏移量
+            let new_offset_x = self.visualization_offset.x + response.drag_delta().x;
+            let new_offset_y = self.visualization_offset.y + response.drag_delta().y;
+            
+            // 限制X方向：确保内容边界在窗口内
+            let final_offset_x = if scaled_content_width <= rect.width() {
+                // 内容小于窗口，允许在窗口内移动，但限制中心在窗口内
+                let max_offset_x = (rect.width() - scaled_content_width) / 2.0;
+                new_offset_x.clamp(-max_offset_x, max_offset_x)
+            } else {
+                // 内容大于窗口，限制边界在窗口内
+                let min_offset_x = rect.left() + margin - offset_x - scaled_content_width / 2.0;
+                let max_offset_x = rect.right() - margin - offset_x + scaled_content_width / 2.0;
+                new_offset_x.clamp(min_offset_x, max_offset_x)
+            };
+            
+            // 限制Y方向：确保内容边界在窗口内
+            let final_offset_y = if scaled_content_height <= rect.height() {
+                // 内容小于窗口，允许在窗口内移动，但限制中心在窗口内
+                let max_offset_y = (rect.height() - scaled_content_height) / 2.0;
+                new_offset_y.clamp(-max_offset_y, max_offset_y)
+            } else {
+                // 内容大于窗口，限制边界在窗口内
+                let min_offset_y = rect.top() + margin - offset_y - scaled_content_height / 2.0;
+                let max_offset_y = rect.bottom() - margin - offset_y + scaled_content_height / 2.0;
+                new_offset_y.clamp(min_offset_y, max_offset_y)
+            };
+            
+            // 应用限制后的偏移量
+            self.visualization_offset = Vec2::new(final_offset_x, final_offset_y);
+        }
+
+        // 鼠标滚轮缩放
+        if response.hovered() {
+            let scroll_delta = ctx.input(|i| i.raw_scroll_delta.y);
+            if scroll_delta != 0.0 {
+                let zoom_factor = 1.0 + scroll_delta * 0.001;
+                self.visualization_zoom *= zoom_factor;
+                self.visualization_zoom = self.visualization_zoom.clamp(0.1, 3.0);
+            }
+        }
+
+        // 右键菜单：多选项目的批量操作
+        if !layout_running {
+            response.context_menu(|ui| self.selection_context_menu(ui));
+        }
+    }
+
+    /// 时间线视图：按领域分组，以横条显示项目的时间跨度
+    /// 横条颜色为项目状态颜色，底部细条为完成度，菱形为里程碑，箭头为项目关系
+    fn timeline_view(&mut self, ui: &mut Ui, ctx: &Context) {
+        use chrono::{Datelike, NaiveDate};
+        
+        let theme = self.theme();
+        let bg_color = self.canvas_background(&theme);
+        
+        // 布局参数
+        let label_width = 180.0;
+        let axis_height = 28.0;
+        let group_height = 24.0;
+        let row_height = 26.0;
+        let bar_height = 14.0;
+        let right_margin = 20.0;
+        
+        // 分组：项目归入其第一个领域，没有领域的项目归入"未分配领域"
+        let first_field = |project: &Project| -> Option<String> {
+            project.field_ids.iter().find(|f| self.data.fields.contains_key(*f)).cloned()
+        };
+        let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+        for field in self.data.fields.values() {
+            let ids: Vec<String> = self.data.projects.values()
+                .filter(|p| p.date_span().is_some() && first_field(p).as_ref() == Some(&field.id))
+                .map(|p| p.id.clone())
+                .collect();
+            if !ids.is_empty() {
+                groups.push((field.name.clone(), ids));
+            }
+        }
+        let unassigned: Vec<String> = self.data.projects.values()
+            .filter(|p| p.date_span().is_some() && first_field(p).is_none())
+            .map(|p| p.id.clone())
+            .collect();
+        if !unassigned.is_empty() {
+            groups.push(("未分配领域".to_string(), unassigned));
+        }
+        let undated_count = self.data.projects.values().filter(|p| p.date_span().is_none()).count();
+        
+        // 时间范围（前后各留一周）
+        let spans: Vec<(NaiveDate, NaiveDate)> = self.data.projects.values().filter_map(|p| p.date_span()).collect();
+        let (Some(min_date), Some(max_date)) = (
+            spans.iter().map(|s| s.0).min(),
+            spans.iter().map(|s| s.1).max(),
+        ) else {
+            let rect = ui.available_rect_before_wrap();
+            ui.painter().rect_filled(rect, 0.0, bg_color);
+            ui.vertical_centered(|ui| {
+                ui.add_space(40.0);
+                ui.label(
+                    RichText::new("项目尚未设置日期，请在属性面板中设置开始、目标等日期")
+                        .size(12.0)
+                        .color(theme.text_secondary)
+                );
+            });
+            return;
+        };
+        let range_start = min_date - chrono::Duration::days(7);
+        let range_end = (max_date + chrono::Duration::days(7)).max(range_start + chrono::Duration::days(30));
+        let total_days = (range_end - range_start).num_days() as f32;
+        
+        let mut clicked_project: Option<String> = None;
+        
+        ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                let row_count: usize = groups.iter().map(|(_, ids)| ids.len()).sum();
+                let content_height = axis_height + groups.len() as f32 * group_height + row_count as f32 * row_height + 30.0;
+                let width = ui.available_width();
+                let height = content_height.max(ui.available_height());
+                let (rect, response) = ui.allocate_exact_size(vec2(width, height), Sense::click());
+                let painter = ui.painter_at(rect);
+                painter.rect_filled(rect, 0.0, bg_color);
+                
+                let chart_left = rect.left() + label_width;
+                let chart_width = (rect.width() - label_width - right_margin).max(50.0);
+                let x_of = |date: NaiveDate| chart_left + (date - range_start).num_days() as f32 / total_days * chart_width;
+                
+                // 时间轴：按月刻度，根据宽度自动调整间隔
+                let px_per_month = chart_width * 30.4 / total_days;
+                let step_months = [1, 2, 3, 6, 12, 24, 60]
+                    .into_iter()
+                    .find(|m| *m as f32 * px_per_month >= 70.0)
+                    .unwrap_or(120);
+                let mut tick = NaiveDate::from_ymd_opt(range_start.year(), range_start.month(), 1).unwrap_or(range_start);
+                while tick <= range_end {
+                    let months_since_epoch = tick.year() * 12 + tick.month0() as i32;
+                    if months_since_epoch % step_months == 0 && tick >= range_start {
+                        let x = x_of(tick);
+                        painter.line_segment(
+                            [pos2(x, rect.top() + axis_height - 6.0), pos2(x, rect.bottom())],
+                            Stroke::new(1.0, theme.divider.gamma_multiply(0.6)),
+                        );
+                        let label = if step_months >= 12 {
+                            tick.format("%Y").to_string()
                         } else {
-                            Color32::WHITE
+                            tick.format("%Y-%m").to_string()
                         };
                         painter.text(
-                            center,
-                            Align2::CENTER_CENTER,
-                            project_num.to_string(),
+                            pos2(x + 3.0, rect.top() + axis_height / 2.0),
+                            Align2::LEFT_CENTER,
+                            label,
                             FontId::proportional(10.0),
-                            text_color,
+                            theme.canvas_text_secondary,
                         );
                     }
-
-                    // 绘制项目名称
-                    if self.settings.show_project_names {
+                    tick = tick.checked_add_months(chrono::Months::new(1)).unwrap_or(range_end + chrono::Duration::days(1));
+                }
+                painter.line_segment(
+                    [pos2(rect.left(), rect.top() + axis_height), pos2(rect.right(), rect.top() + axis_height)],
+                    Stroke::new(1.0, theme.divider),
+                );
+                
+                // 今天
+                let today = chrono::Local::now().date_naive();
+                if today >= range_start && today <= range_end {
+                    let x = x_of(today);
+                    painter.line_segment(
+                        [pos2(x, rect.top() + axis_height), pos2(x, rect.bottom())],
+                        Stroke::new(1.5, theme.error),
+                    );
+                    painter.text(
+                        pos2(x, rect.top() + axis_height + 2.0),
+                        Align2::CENTER_TOP,
+                        "今天",
+                        FontId::proportional(9.0),
+                        theme.error,
+                    );
+                }
+                
+                // 分组和项目行
+                let pointer = response.hover_pos();
+                let mut bars: HashMap<String, Rect> = HashMap::new();
+                let mut tooltip: Option<String> = None;
+                let mut y = rect.top() + axis_height;
+                for (group_name, ids) in &groups {
+                    let group_rect = Rect::from_min_size(pos2(rect.left(), y), vec2(rect.width(), group_height));
+                    painter.rect_filled(group_rect, 0.0, theme.surface);
+                    painter.text(
+                        pos2(rect.left() + 8.0, group_rect.center().y),
+                        Align2::LEFT_CENTER,
+                        group_name,
+                        FontId::proportional(12.0),
+                        theme.canvas_text,
+                    );
+                    y += group_height;
+                    
+                    for id in ids {
+                        let Some(project) = self.data.projects.get(id) else { continue };
+                        let Some((start, end)) = project.date_span() else { continue };
+                        let row_rect = Rect::from_min_size(pos2(rect.left(), y), vec2(rect.width(), row_height));
+                        y += row_height;
+                        
+                        if self.selected_project.as_ref() == Some(id) {
+                            painter.rect_filled(row_rect, 0.0, theme.surface_selected);
+                        }
+                        if pointer.is_some_and(|p| row_rect.contains(p)) && response.clicked() {
+                            clicked_project = Some(id.clone());
+                        }
+                        
                         painter.text(
-                            pos2(center.x, center.y + radius + 15.0),
-                            Align2::CENTER_CENTER,
+                            pos2(rect.left() + 16.0, row_rect.center().y),
+                            Align2::LEFT_CENTER,
                             &project.name,
                             FontId::proportional(12.0),
-                            Color32::BLACK,
+                            theme.canvas_text,
+                        );
+                        
+                        // 项目横条（只有一个日期时绘制为窄条）
+                        let x0 = x_of(start);
+                        let x1 = x_of(end).max(x0 + 6.0);
+                        let bar_rect = Rect::from_min_max(
+                            pos2(x0, row_rect.center().y - bar_height / 2.0),
+                            pos2(x1, row_rect.center().y + bar_height / 2.0),
                         );
+                        let fill = self.status_color(&theme, &project.status);
+                        painter.rect_filled(bar_rect, 3.0, fill);
+                        let outline = if self.selected_project.as_ref() == Some(id) {
+                            Stroke::new(2.0, theme.primary)
+                        } else {
+                            Stroke::new(1.0, theme.border)
+                        };
+                        painter.rect_stroke(bar_rect, 3.0, outline);
+                        // 完成度：底部细条
+                        let progress_width = bar_rect.width() * project.completion_percentage.clamp(0.0, 100.0) / 100.0;
+                        if progress_width > 0.0 {
+                            painter.rect_filled(
+                                Rect::from_min_size(
+                                    pos2(bar_rect.left(), bar_rect.bottom() - 3.0),
+                                    vec2(progress_width, 3.0),
+                                ),
+                                0.0,
+                                theme.completion_percentage_to_color(project.completion_percentage),
+                            );
+                        }
+                        bars.insert(id.clone(), bar_rect);
+                        
+                        if pointer.is_some_and(|p| bar_rect.contains(p)) {
+                            tooltip = Some(format!(
+                                "{}\n{} ~ {}\n{} · 完成度 {:.0}%",
+                                project.name,
+                                start.format("%Y-%m-%d"),
+                                end.format("%Y-%m-%d"),
+                                self.data.status_name(&project.status),
+                                project.completion_percentage,
+                            ));
+                        }
+                        
+                        // 里程碑：已完成为实心菱形，未完成为空心菱形
+                        for milestone in &project.milestones {
+                            let Some(due) = milestone.due_date else { continue };
+                            let c = pos2(x_of(due), row_rect.center().y);
+                            let r = 5.0;
+                            let points = vec![c + vec2(0.0, -r), c + vec2(r, 0.0), c + vec2(0.0, r), c + vec2(-r, 0.0)];
+                            if milestone.done {
+                                painter.add(Shape::convex_polygon(points, theme.canvas_text, Stroke::new(1.0, bg_color)));
+                            } else {
+                                painter.add(Shape::convex_polygon(points, bg_color, Stroke::new(1.5, theme.canvas_text)));
+                            }
+                            if pointer.is_some_and(|p| (p - c).length() <= r + 2.0) {
+                                tooltip = Some(format!(
+                                    "{}\n{}{}",
+                                    milestone.name,
+                                    due.format("%Y-%m-%d"),
+                                    if milestone.done { " · 已完成" } else { "" },
+                                ));
+                            }
+                        }
                     }
                 }
-
-                // 绘制图例（在右上角）
-                self.draw_legend(painter, rect, &theme);
                 
-                // 获取图例矩形用于交互检测
-                let legend_rect = self.get_legend_rect(rect);
-                let mouse_pos = ctx.input(|i| i.pointer.hover_pos());
-                let is_over_legend = mouse_pos.map(|p| legend_rect.contains(p)).unwrap_or(false);
+                // 项目关系：从前一项目横条末端指向后一项目横条起点
+                for relation in &self.data.relations {
+                    let (Some(from), Some(to)) = (bars.get(&relation.from_id), bars.get(&relation.to_id)) else {
+                        continue;
+                    };
+                    let color = relation_display_color(&theme, relation);
+                    let stroke = Stroke::new(relation.width.min(2.0), color);
+                    let p0 = pos2(from.right(), from.center().y);
+                    let p3 = pos2(to.left(), to.center().y);
+                    let points = vec![p0, p0 + vec2(8.0, 0.0), p3 - vec2(8.0, 0.0), p3];
+                    let (line_style, arrow_head) = self.data.relation_style(relation);
+                    paint_relation_line(&painter, &points, line_style, arrow_head, stroke, 7.0);
+                }
+                
+                if undated_count > 0 {
+                    painter.text(
+                        pos2(rect.left() + 8.0, y + 15.0),
+                        Align2::LEFT_CENTER,
+                        format!("另有 {} 个项目未设置日期", undated_count),
+                        FontId::proportional(11.0),
+                        theme.canvas_text_secondary,
+                    );
+                }
                 
-                // 处理图例双击（打开设置）
-                if response.double_clicked() {
-                    if let Some(click_pos) = response.interact_pointer_pos() {
-                        if legend_rect.contains(click_pos) {
-                            self.show_legend_settings = true;
+                if let Some(text) = tooltip {
+                    egui::show_tooltip_at_pointer(ctx, Id::new("timeline_tooltip"), |ui| {
+                        ui.label(text);
+                    });
+                }
+            });
+        
+        // 点击项目行：与其他视图共享选中状态
+        if let Some(id) = clicked_project {
+            self.editing_project = self.data.projects.get(&id).cloned();
+            self.selected_project = Some(id);
+        }
+    }
+
+    /// 看板视图：每个状态一列，项目为卡片；拖动卡片到其他列即修改项目状态（可撤销）
+    fn kanban_view(&mut self, ui: &mut Ui) {
+        let theme = self.theme();
+        let bg_color = self.canvas_background(&theme);
+        let column_width = 220.0;
+        
+        let statuses: Vec<(String, String, Color32)> = self.data.sorted_statuses().iter()
+            .map(|s| (s.id.clone(), s.name.clone(), self.status_color(&theme, &s.id)))
+            .collect();
+        let mut clicked_project: Option<String> = None;
+        let mut dropped: Option<(String, String)> = None;  // (项目ID, 目标状态ID)
+        
+        let rect = ui.available_rect_before_wrap();
+        ui.painter().rect_filled(rect, 0.0, bg_color);
+        
+        ScrollArea::horizontal()
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                ui.horizontal_top(|ui| {
+                    ui.add_space(8.0);
+                    for (status_id, status_name, status_color) in &statuses {
+                        let project_ids: Vec<String> = self.data.projects.values()
+                            .filter(|p| &p.status == status_id)
+                            .map(|p| p.id.clone())
+                            .collect();
+                        let column_height = ui.available_height() - 8.0;
+                        
+                        let frame = Frame::none()
+                            .fill(theme.surface)
+                            .stroke(Stroke::new(1.0, theme.border))
+                            .rounding(Rounding::same(4.0))
+                            .inner_margin(Margin::same(8.0));
+                        let (_, payload) = ui.dnd_drop_zone::<String, ()>(frame, |ui| {
+                            ui.set_width(column_width);
+                            ui.set_min_height(column_height);
+                            
+                            // 列标题：状态颜色、名称、项目数
+                            ui.horizontal(|ui| {
+                                let (dot_rect, _) = ui.allocate_exact_size(vec2(10.0, 10.0), Sense::hover());
+                                ui.painter().circle_filled(dot_rect.center(), 5.0, *status_color);
+                                ui.painter().circle_stroke(dot_rect.center(), 5.0, Stroke::new(1.0, theme.border));
+                                ui.label(RichText::new(status_name).size(13.0).color(theme.text_primary).strong());
+                                ui.label(RichText::new(project_ids.len().to_string()).size(11.0).color(theme.text_secondary));
+                            });
+                            ui.add_space(6.0);
+                            
+                            ScrollArea::vertical()
+                                .id_source(("kanban_column", status_id))
+                                .auto_shrink([false, true])
+                                .show(ui, |ui| {
+                                    for id in &project_ids {
+                                        let Some(project) = self.data.projects.get(id) else { continue };
+                                        let is_selected = self.selected_project.as_ref() == Some(id);
+                                        let card_id = Id::new(("kanban_card", id));
+                                        let card = ui.dnd_drag_source(card_id, id.clone(), |ui| {
+                                            Frame::none()
+                                                .fill(if is_selected { theme.surface_selected } else { theme.background })
+                                                .stroke(if is_selected {
+                                                    Stroke::new(2.0, theme.primary)
+                                                } else {
+                                                    Stroke::new(1.0, theme.border)
+                                                })
+                                                .rounding(Rounding::same(4.0))
+                                                .inner_margin(Margin::same(8.0))
+                                                .show(ui, |ui| {
+                                                    ui.set_width(column_width - 20.0);
+                                                    ui.label(RichText::new(&project.name).size(13.0).color(theme.text_primary));
+                                                    let field_names: Vec<&str> = project.field_ids.iter()
+                                                        .filter_map(|f| self.data.fields.get(f).map(|f| f.name.as_str()))
+                                                        .collect();
+                                                    if !field_names.is_empty() {
+                                                        ui.label(
+                                                            RichText::new(field_names.join(" · "))
+                                                                .size(11.0)
+                                                                .color(theme.text_secondary)
+                                                        );
+                                                    }
+                                                    ui.add(
+                                                        egui::ProgressBar::new(project.completion_percentage.clamp(0.0, 100.0) / 100.0)
+                                                            .desired_height(6.0)
+                                                            .fill(theme.completion_percentage_to_color(project.completion_percentage))
+                                                    );
+                                                    ui.label(
+                                                        RichText::new(format!("完成度 {:.0}%", project.completion_percentage))
+                                                            .size(10.0)
+                                                            .color(theme.text_secondary)
+                                                    );
+                                                });
+                                        });
+                                        // 拖动由 dnd_drag_source 处理，点击单独检测用于选中
+                                        if ui.interact(card.response.rect, card_id.with("click"), Sense::click()).clicked() {
+                                            clicked_project = Some(id.clone());
+                                        }
+                                        ui.add_space(6.0);
+                                    }
+                                });
+                        });
+                        if let Some(project_id) = payload {
+                            dropped = Some(((*project_id).clone(), status_id.clone()));
                         }
+                        ui.add_space(8.0);
                     }
+                });
+            });
+        
+        if let Some(id) = clicked_project {
+            self.editing_project = self.data.projects.get(&id).cloned();
+            self.selected_project = Some(id);
+        }
+        
+        // 放下卡片：修改状态并记录历史
+        if let Some((project_id, status_id)) = dropped {
+            let mut changed = false;
+            if let Some(project) = self.data.projects.get_mut(&project_id) {
+                if project.status != status_id {
+                    project.status = status_id;
+                    changed = true;
                 }
-                
-                // 处理图例拖拽
-                if is_over_legend && response.drag_started() {
-                    self.legend_dragging = true;
+            }
+            if changed {
+                if self.selected_project.as_ref() == Some(&project_id) {
+                    self.editing_project = self.data.projects.get(&project_id).cloned();
                 }
-                
-                if self.legend_dragging {
-                    if response.dragged() {
-                        self.legend_position += response.drag_delta();
+                self.save_to_history();
+            }
+        }
+    }
+
+    /// 同步多选与当前选中项目
+    /// 其他地方修改 selected_project 时（画布点击、Tab 切换、撤销等），多选随之变为单选或清空
+    fn sync_selection(&mut self) {
+        let projects = &self.data.projects;
+        self.selected_project_ids.retain(|id| projects.contains_key(id));
+        match &self.selected_project {
+            Some(id) if !self.selected_project_ids.contains(id) => {
+                self.selected_project_ids = vec![id.clone()];
+            }
+            None => self.selected_project_ids.clear(),
+            _ => {}
+        }
+    }
+
+    /// 从数据中刷新属性面板正在编辑的项目（批量修改后调用）
+    fn refresh_editing_project(&mut self) {
+        self.editing_project = self.selected_project.as_ref()
+            .and_then(|id| self.data.projects.get(id))
+            .cloned();
+    }
+
+    /// 整组拖动结束后的位置校验：所有项目位置都正确时保留；
+    /// 否则依次尝试把整组平移到某个不正确项目的最近正确位置，仍不行则整组退回拖动前的位置
+    fn settle_group_move(&mut self, group: &[(String, (f32, f32))]) {
+        // 每次检查整组时只按当前位置构建一次空间索引
+        let all_valid = |app: &Self| {
+            let index = SpatialIndex::new(&app.data);
+            group.iter()
+                .filter_map(|(id, _)| app.data.projects.get(id))
+                .all(|p| app.is_project_position_valid(&index, p))
+        };
+        let shift_group = |app: &mut Self, dx: f32, dy: f32| {
+            for (id, _) in group {
+                if let Some(p) = app.data.projects.get_mut(id) {
+                    p.position.0 += dx;
+                    p.position.1 += dy;
+                }
+            }
+        };
+
+        // 平移失败后会移回原处，因此原位置的索引可用于所有候选位置的计算
+        let index = SpatialIndex::new(&self.data);
+        let invalid: Vec<Project> = group.iter()
+            .filter_map(|(id, _)| self.data.projects.get(id))
+            .filter(|p| !self.is_project_position_valid(&index, p))
+            .cloned()
+            .collect();
+        if !invalid.is_empty() {
+            let mut settled = false;
+            for project in &invalid {
+                let (x, y) = self.find_nearest_valid_position(&index, project);
+                let (dx, dy) = (x - project.position.0, y - project.position.1);
+                shift_group(self, dx, dy);
+                if all_valid(self) {
+                    settled = true;
+                    break;
+                }
+                shift_group(self, -dx, -dy);
+            }
+            if !settled {
+                for (id, position) in group {
+                    if let Some(p) = self.data.projects.get_mut(id) {
+                        p.position = *position;
                     }
-                    if response.drag_stopped() {
-                        self.legend_dragging = false;
+                }
+            }
+        }
+        self.refresh_editing_project();
+    }
+
+    /// 删除选中的所有项目及其相关关系
+    fn delete_selected_projects(&mut self) {
+        let ids = self.selected_project_ids.clone();
+        if ids.is_empty() {
+            return;
+        }
+        for id in &ids {
+            self.data.projects.shift_remove(id);
+        }
+        self.data.relations.retain(|r| !ids.contains(&r.from_id) && !ids.contains(&r.to_id));
+        self.selected_project = None;
+        self.selected_project_ids.clear();
+        self.editing_project = None;
+        self.save_to_history();
+    }
+
+    /// 设置选中项目的状态
+    fn set_selected_status(&mut self, status_id: &str) {
+        for id in &self.selected_project_ids {
+            if let Some(project) = self.data.projects.get_mut(id) {
+                project.status = status_id.to_string();
+            }
+        }
+        self.refresh_editing_project();
+        self.save_to_history();
+    }
+
+    /// 锁定或解锁选中项目的位置
+    fn set_selected_locked(&mut self, locked: bool) {
+        for id in &self.selected_project_ids {
+            if let Some(project) = self.data.projects.get_mut(id) {
+                project.locked = locked;
+            }
+        }
+        self.refresh_editing_project();
+        self.save_to_history();
+    }
+
+    /// 锁定或解锁全部项目和领域
+    fn set_all_locked(&mut self, locked: bool) {
+        for project in self.data.projects.values_mut() {
+            project.locked = locked;
+        }
+        for field in self.data.fields.values_mut() {
+            field.locked = locked;
+        }
+        self.refresh_editing_project();
+        self.save_to_history();
+    }
+
+    /// 统一设置所有关系的走线方式
+    fn set_all_relation_routes(&mut self, route: RelationRoute) {
+        for relation in &mut self.data.relations {
+            relation.route = route;
+        }
+        self.save_to_history();
+    }
+
+    /// 将选中项目加入或移出领域，并重新计算它们的位置
+    fn set_selected_field_membership(&mut self, field_id: &str, member: bool) {
+        let mut changed = Vec::new();
+        for id in &self.selected_project_ids {
+            if let Some(project) = self.data.projects.get_mut(id) {
+                let has = project.field_ids.iter().any(|f| f == field_id);
+                if member && !has {
+                    project.field_ids.push(field_id.to_string());
+                    changed.push(id.clone());
+                } else if !member && has {
+                    project.field_ids.retain(|f| f != field_id);
+                    changed.push(id.clone());
+                }
+            }
+        }
+        if changed.is_empty() {
+            return;
+        }
+        // 直接修正位置而不单独记录历史，成员变化只记录一次，一次撤销即可恢复
+        layout::place_projects(&mut self.data, &changed);
+        layout::fix_positions(&mut self.data);
+        self.refresh_editing_project();
+        self.save_to_history();
+    }
+
+    /// 打开批量编辑对话框（清空上次的输入）
+    fn open_bulk_edit_dialog(&mut self) {
+        if self.selected_project_ids.is_empty() {
+            return;
+        }
+        self.bulk_edit = BulkEdit::default();
+        self.show_bulk_edit_dialog = true;
+    }
+
+    /// 批量编辑要添加标签的关系序号
+    fn bulk_edit_relation_indices(&self) -> Vec<usize> {
+        let tag = self.bulk_edit.relation_tag.trim();
+        if tag.is_empty() {
+            return Vec::new();
+        }
+        let selected = &self.selected_project_ids;
+        self.data.relations.iter()
+            .enumerate()
+            .filter(|(_, r)| {
+                let (from, to) = (selected.contains(&r.from_id), selected.contains(&r.to_id));
+                if self.bulk_edit.tag_all_relations { from || to } else { from && to }
+            })
+            .filter(|(_, r)| !r.tags.iter().any(|t| t == tag))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// 批量编辑后项目的完成度
+    fn bulk_edit_completion(&self, current: f32) -> f32 {
+        match self.bulk_edit.completion_mode {
+            CompletionEdit::Keep => current,
+            CompletionEdit::Set => self.bulk_edit.completion_value.clamp(0.0, 100.0),
+            CompletionEdit::Adjust => (current + self.bulk_edit.completion_value).clamp(0.0, 100.0),
+        }
+    }
+
+    /// 批量编辑会修改的项目ID
+    fn bulk_edit_affected_projects(&self) -> Vec<String> {
+        let edit = &self.bulk_edit;
+        self.selected_project_ids.iter()
+            .filter_map(|id| self.data.projects.get(id))
+            .filter(|p| {
+                edit.status.as_ref().is_some_and(|s| &p.status != s)
+                    || self.bulk_edit_completion(p.completion_percentage) != p.completion_percentage
+                    || edit.add_field_ids.iter().any(|f| !p.field_ids.contains(f))
+                    || edit.remove_field_ids.iter().any(|f| p.field_ids.contains(f))
+                    || edit.radius.is_some_and(|r| r != p.radius)
+            })
+            .map(|p| p.id.clone())
+            .collect()
+    }
+
+    /// 应用批量编辑（作为一次可撤销的操作）
+    fn apply_bulk_edit(&mut self) {
+        let affected = self.bulk_edit_affected_projects();
+        let relation_indices = self.bulk_edit_relation_indices();
+        if affected.is_empty() && relation_indices.is_empty() {
+            return;
+        }
+        let edit = self.bulk_edit.clone();
+        let mut field_changed = Vec::new();
+        for id in &affected {
+            let completion = self.data.projects.get(id).map(|p| self.bulk_edit_completion(p.completion_percentage));
+            let Some(project) = self.data.projects.get_mut(id) else {
+                continue;
+            };
+            if let Some(status) = &edit.status {
+                project.status = status.clone();
+            }
+            if let Some(completion) = completion {
+                project.completion_percentage = completion;
+            }
+            if let Some(radius) = edit.radius {
+                project.radius = radius;
+            }
+            let old_fields = project.field_ids.clone();
+            for field_id in &edit.add_field_ids {
+                if !project.field_ids.contains(field_id) {
+                    project.field_ids.push(field_id.clone());
+                }
+            }
+            project.field_ids.retain(|f| !edit.remove_field_ids.contains(f));
+            if project.field_ids != old_fields {
+                field_changed.push(id.clone());
+            }
+        }
+        let tag = edit.relation_tag.trim().to_string();
+        for index in &relation_indices {
+            self.data.relations[*index].tags.push(tag.clone());
+        }
+        if !relation_indices.is_empty() && !self.data.relation_tags.contains(&tag) {
+            self.data.relation_tags.push(tag);
+        }
+        // 领域或半径变化后重新摆放项目
+        // 直接修正位置而不单独记录历史，整个批量编辑只记录一次，一次撤销即可恢复
+        layout::place_projects(&mut self.data, &field_changed);
+        if !field_changed.is_empty() || edit.radius.is_some() {
+            layout::fix_positions(&mut self.data);
+        }
+        self.refresh_editing_project();
+        self.save_to_history();
+    }
+
+    /// 批量编辑对话框：对选中的所有项目统一修改状态、完成度、领域、关系标签和半径
+    fn bulk_edit_dialog(&mut self, ctx: &Context) {
+        let theme = self.theme();
+        let mut open = true;
+        let mut close = false;
+        let count = self.selected_project_ids.len();
+
+        egui::Window::new("批量编辑")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.set_width(420.0);
+                ui.label(
+                    RichText::new(format!("对选中的 {} 个项目统一修改，应用后可一次撤销", count))
+                        .size(11.0)
+                        .color(theme.text_secondary)
+                );
+                ui.add_space(8.0);
+
+                let statuses: Vec<(String, String)> = self.data.sorted_statuses().into_iter()
+                    .map(|s| (s.id.clone(), s.name.clone()))
+                    .collect();
+                let fields: Vec<(String, String)> = self.data.fields.values()
+                    .map(|f| (f.id.clone(), f.name.clone()))
+                    .collect();
+                egui::Grid::new("bulk_edit_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label("状态:");
+                        let selected_text = match &self.bulk_edit.status {
+                            Some(id) => self.data.status_name(id),
+                            None => "不修改".to_string(),
+                        };
+                        ComboBox::from_id_source("bulk_edit_status")
+                            .selected_text(selected_text)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut self.bulk_edit.status, None, "不修改");
+                                for (id, name) in &statuses {
+                                    ui.selectable_value(&mut self.bulk_edit.status, Some(id.clone()), name);
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("完成度:");
+                        ui.horizontal(|ui| {
+                            ui.radio_value(&mut self.bulk_edit.completion_mode, CompletionEdit::Keep, "不修改");
+                            ui.radio_value(&mut self.bulk_edit.completion_mode, CompletionEdit::Set, "设为");
+                            ui.radio_value(&mut self.bulk_edit.completion_mode, CompletionEdit::Adjust, "增减");
+                            let range = match self.bulk_edit.completion_mode {
+                                CompletionEdit::Adjust => -100.0..=100.0,
+                                _ => 0.0..=100.0,
+                            };
+                            ui.add_enabled(
+                                self.bulk_edit.completion_mode != CompletionEdit::Keep,
+                                DragValue::new(&mut self.bulk_edit.completion_value).clamp_range(range).suffix("%"),
+                            );
+                        });
+                        ui.end_row();
+
+                        ui.label("半径:");
+                        ui.horizontal(|ui| {
+                            let mut change_radius = self.bulk_edit.radius.is_some();
+                            if ui.checkbox(&mut change_radius, "修改").changed() {
+                                self.bulk_edit.radius = change_radius.then_some(20.0);
+                            }
+                            if let Some(radius) = &mut self.bulk_edit.radius {
+                                ui.add(Slider::new(radius, 8.0..=60.0));
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("领域:");
+                        ui.vertical(|ui| {
+                            for (id, name) in &fields {
+                                ui.horizontal(|ui| {
+                                    // 每个领域：不变 / 加入 / 移出
+                                    let mut state = if self.bulk_edit.add_field_ids.contains(id) {
+                                        1
+                                    } else if self.bulk_edit.remove_field_ids.contains(id) {
+                                        2
+                                    } else {
+                                        0
+                                    };
+                                    let before = state;
+                                    ui.add_sized([110.0, 18.0], Label::new(name.as_str()).truncate(true));
+                                    ui.radio_value(&mut state, 0, "不变");
+                                    ui.radio_value(&mut state, 1, "加入");
+                                    ui.radio_value(&mut state, 2, "移出");
+                                    if state != before {
+                                        self.bulk_edit.add_field_ids.retain(|f| f != id);
+                                        self.bulk_edit.remove_field_ids.retain(|f| f != id);
+                                        match state {
+                                            1 => self.bulk_edit.add_field_ids.push(id.clone()),
+                                            2 => self.bulk_edit.remove_field_ids.push(id.clone()),
+                                            _ => {}
+                                        }
+                                    }
+                                });
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("关系标签:");
+                        ui.vertical(|ui| {
+                            ui.add(
+                                TextEdit::singleline(&mut self.bulk_edit.relation_tag)
+                                    .hint_text("添加到关系的标签（留空不添加）")
+                                    .desired_width(240.0)
+                            );
+                            ui.checkbox(&mut self.bulk_edit.tag_all_relations, "包括与未选中项目之间的关系");
+                        });
+                        ui.end_row();
+                    });
+
+                ui.add_space(8.0);
+                ui.separator();
+                let project_count = self.bulk_edit_affected_projects().len();
+                let relation_count = self.bulk_edit_relation_indices().len();
+                ui.label(
+                    RichText::new(format!("将修改 {} 个项目、{} 条关系", project_count, relation_count))
+                        .size(11.0)
+                        .color(theme.info)
+                );
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(project_count + relation_count > 0, Button::new("应用")).clicked() {
+                        self.apply_bulk_edit();
+                        close = true;
+                    }
+                    if ui.button("取消").clicked() {
+                        close = true;
                     }
+                });
+            });
+
+        if !open || close || self.selected_project_ids.is_empty() {
+            self.show_bulk_edit_dialog = false;
+        }
+    }
+
+    /// 画布右键菜单：对选中的项目批量操作
+    fn selection_context_menu(&mut self, ui: &mut Ui) {
+        let count = self.selected_project_ids.len();
+        if count == 0 {
+            ui.label("未选中项目（Shift+点击或 Shift+拖拽框选）");
+            return;
+        }
+        ui.label(format!("已选中 {} 个项目", count));
+        ui.separator();
+
+        let statuses: Vec<(String, String)> = self.data.sorted_statuses().into_iter()
+            .map(|s| (s.id.clone(), s.name.clone()))
+            .collect();
+        ui.menu_button("设置状态", |ui| {
+            for (id, name) in &statuses {
+                if ui.button(name).clicked() {
+                    self.set_selected_status(id);
+                    ui.close_menu();
+                }
+            }
+        });
+
+        let fields: Vec<(String, String)> = self.data.fields.values()
+            .map(|f| (f.id.clone(), f.name.clone()))
+            .collect();
+        ui.menu_button("加入领域", |ui| {
+            for (id, name) in &fields {
+                if ui.button(name).clicked() {
+                    self.set_selected_field_membership(id, true);
+                    ui.close_menu();
+                }
+            }
+        });
+        ui.menu_button("移出领域", |ui| {
+            for (id, name) in &fields {
+                let in_use = self.selected_project_ids.iter()
+                    .any(|p| self.data.projects.get(p).is_some_and(|p| p.field_ids.contains(id)));
+                if ui.add_enabled(in_use, Button::new(name)).clicked() {
+                    self.set_selected_field_membership(id, false);
+                    ui.close_menu();
+                }
+            }
+        });
+
+        let any_unlocked = self.selected_project_ids.iter()
+            .any(|p| self.data.projects.get(p).is_some_and(|p| !p.locked));
+        if any_unlocked {
+            if ui.button("锁定位置").on_hover_text("自动布局不再移动这些项目，画布上也不能拖动").clicked() {
+                self.set_selected_locked(true);
+                ui.close_menu();
+            }
+        } else if ui.button("解锁位置").clicked() {
+            self.set_selected_locked(false);
+            ui.close_menu();
+        }
+
+        if ui.button("批量编辑…").clicked() {
+            self.open_bulk_edit_dialog();
+            ui.close_menu();
+        }
+
+        ui.separator();
+        if ui.button(format!("删除 {} 个项目", count)).clicked() {
+            self.delete_selected_projects();
+            ui.close_menu();
+        }
+    }
+
+    /// 表格视图：可排序、可筛选、可直接编辑单元格；支持 Ctrl/Cmd 点击多选和 Shift 范围选择
+    fn table_view(&mut self, ui: &mut Ui) {
+        use chrono::NaiveDate;
+        
+        let theme = self.theme();
+        
+        // 每个项目的关系数量（出, 入）
+        let mut relation_counts: HashMap<&str, (usize, usize)> = HashMap::new();
+        for relation in &self.data.relations {
+            relation_counts.entry(relation.from_id.as_str()).or_default().0 += 1;
+            relation_counts.entry(relation.to_id.as_str()).or_default().1 += 1;
+        }
+        let date_of = |project: &Project, column: TableColumn| -> Option<NaiveDate> {
+            match column {
+                TableColumn::StartDate => project.start_date,
+                TableColumn::TargetDate => project.target_date,
+                TableColumn::SubmissionDate => project.submission_date,
+                TableColumn::PublicationDate => project.publication_date,
+                _ => None,
+            }
+        };
+        let fields_text = |project: &Project| -> String {
+            project.field_ids.iter()
+                .filter_map(|f| self.data.fields.get(f).map(|f| f.name.clone()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        // 单元格的显示文本（用于筛选）
+        let cell_text = |idx: usize, project: &Project, column: TableColumn| -> String {
+            match column {
+                TableColumn::Number => (idx + 1).to_string(),
+                TableColumn::Name => project.name.clone(),
+                TableColumn::Status => self.data.status_name(&project.status),
+                TableColumn::Fields => fields_text(project),
+                TableColumn::Tags => project.tags.join(", "),
+                TableColumn::Completion => format!("{:.0}", project.completion_percentage),
+                TableColumn::Relations => {
+                    let (out, inc) = relation_counts.get(project.id.as_str()).copied().unwrap_or_default();
+                    format!("{}/{}", out, inc)
+                }
+                _ => date_of(project, column).map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+            }
+        };
+        // 单元格的数值（用于比较筛选，如 >50）
+        let cell_number = |idx: usize, project: &Project, column: TableColumn| -> Option<f64> {
+            match column {
+                TableColumn::Number => Some((idx + 1) as f64),
+                TableColumn::Completion => Some(project.completion_percentage as f64),
+                TableColumn::Relations => {
+                    let (out, inc) = relation_counts.get(project.id.as_str()).copied().unwrap_or_default();
+                    Some((out + inc) as f64)
                 }
-
-                // 处理点击（在绘制完成后）- 排除图例区域
-                if response.clicked() && !is_over_legend {
-                    if let Some(click_pos) = response.interact_pointer_pos() {
-                        let modifiers = ui.input(|i| i.modifiers);
-                        let cmd_pressed = modifiers.command; // macOS: Command, Windows/Linux: Ctrl
-                        let shift_pressed = modifiers.shift;
+                _ => None,
+            }
+        };
+        
+        // 筛选
+        let mut rows: Vec<(usize, &Project)> = self.data.projects.values().enumerate()
+            .filter(|(idx, project)| {
+                self.table_filters.iter().all(|(column, filter)| {
+                    table_filter_matches(
+                        filter,
+                        &cell_text(*idx, project, *column),
+                        cell_number(*idx, project, *column),
+                        date_of(project, *column),
+                    )
+                })
+            })
+            .collect();
+        
+        // 排序（日期为空的排在最后）
+        let (sort_column, ascending) = self.table_sort;
+        rows.sort_by(|(ia, a), (ib, b)| {
+            let ordering = match sort_column {
+                TableColumn::Number => ia.cmp(ib),
+                TableColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                TableColumn::Status => self.data.status_index(&a.status).cmp(&self.data.status_index(&b.status)),
+                TableColumn::Fields => fields_text(a).cmp(&fields_text(b)),
+                TableColumn::Tags => a.tags.join(", ").to_lowercase().cmp(&b.tags.join(", ").to_lowercase()),
+                TableColumn::Completion => a.completion_percentage.total_cmp(&b.completion_percentage),
+                TableColumn::Relations => cell_number(*ia, a, sort_column)
+                    .unwrap_or_default()
+                    .total_cmp(&cell_number(*ib, b, sort_column).unwrap_or_default()),
+                _ => match (date_of(a, sort_column), date_of(b, sort_column)) {
+                    (Some(da), Some(db)) => da.cmp(&db),
+                    (Some(_), None) => return std::cmp::Ordering::Less,
+                    (None, Some(_)) => return std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Equal,
+                },
+            };
+            if ascending { ordering } else { ordering.reverse() }
+        });
+        
+        let row_ids: Vec<String> = rows.iter().map(|(_, p)| p.id.clone()).collect();
+        let selected_rows: Vec<bool> = row_ids.iter().map(|id| self.selected_project_ids.contains(id)).collect();
+        let statuses: Vec<(String, String)> = self.data.sorted_statuses().iter().map(|s| (s.id.clone(), s.name.clone())).collect();
+        let all_fields: Vec<(String, String)> = self.data.fields.values().map(|f| (f.id.clone(), f.name.clone())).collect();
+        let modifiers = ui.input(|i| i.modifiers);
+        
+        let mut edits: Vec<TableEdit> = Vec::new();
+        let mut clicked_row: Option<String> = None;
+        let mut new_sort = self.table_sort;
+        let mut filters = self.table_filters.clone();
+        
+        ScrollArea::both()
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                let selection_color = theme.selection.gamma_multiply(0.35);
+                egui::Grid::new("project_table_grid")
+                    .num_columns(TableColumn::all().len())
+                    .spacing([12.0, 6.0])
+                    .striped(true)
+                    .with_row_color(move |row, _style| {
+                        // 第0行为表头，第1行为筛选
+                        (row >= 2 && selected_rows.get(row - 2).copied().unwrap_or(false)).then_some(selection_color)
+                    })
+                    .show(ui, |ui| {
+                        // 表头：点击排序
+                        for column in TableColumn::all() {
+                            let arrow = if new_sort.0 == column {
+                                if new_sort.1 { " ▲" } else { " ▼" }
+                            } else {
+                                ""
+                            };
+                            let header = ui.selectable_label(
+                                new_sort.0 == column,
+                                RichText::new(format!("{}{}", column.title(), arrow)).size(12.0).strong(),
+                            );
+                            if header.clicked() {
+                                new_sort = if new_sort.0 == column { (column, !new_sort.1) } else { (column, true) };
+                            }
+                        }
+                        ui.end_row();
                         
-                        for (id, center, radius, project) in &project_centers {
-                            let distance = (click_pos - *center).length();
-                            if distance <= *radius {
-                                // 如果按住 Command 且已有选中项目，则建立关系
-                                if cmd_pressed && self.selected_project.is_some() {
-                                    let from_id = self.selected_project.clone().unwrap();
-                                    let to_id = id.clone();
-                                    
-                                    // 不能建立到自己的关系
-                                    if from_id != to_id {
-                                        // 检查是否已存在相同的关系
-                                        let relation_exists = self.data.relations.iter().any(|r| {
-                                            r.from_id == from_id && r.to_id == to_id
-                                        });
-                                        
-                                        if !relation_exists {
-                                            self.save_to_history();
-                                            // Command+Shift: 虚线箭头，Command: 实线箭头
-                                            let relation_type = if shift_pressed {
-                                                RelationType::Indirect
-                                            } else {
-                                                RelationType::Direct
-                                            };
-                                            
-                                            self.data.relations.push(ProjectRelation {
-                                                from_id,
-                                                to_id,
-                                                relation_type,
-                                                tags: Vec::new(),
-                                                color: default_relation_color(),
-                                                width: default_relation_width(),
+                        // 筛选行：文本包含匹配，数字和日期列支持 >、<、>=、<=、= 比较
+                        for column in TableColumn::all() {
+                            let filter = filters.entry(column).or_default();
+                            ui.add(
+                                TextEdit::singleline(filter)
+                                    .hint_text("筛选")
+                                    .desired_width(if column == TableColumn::Name { 160.0 } else { 70.0 })
+                            ).on_hover_text("输入文字筛选；数字和日期列可用 >50、<=2024-06-30 等比较");
+                        }
+                        ui.end_row();
+                        
+                        for (idx, project) in &rows {
+                            let id = &project.id;
+                            let is_selected = self.selected_project_ids.contains(id);
+                            for column in TableColumn::all() {
+                                match column {
+                                    TableColumn::Number => {
+                                        if ui.selectable_label(is_selected, format!("{:02}", idx + 1)).clicked() {
+                                            clicked_row = Some(id.clone());
+                                        }
+                                    }
+                                    TableColumn::Name => {
+                                        let mut name = project.name.clone();
+                                        // 固定ID：按名称排序或筛选时行会随输入移动，焦点需跟随项目而不是行号
+                                        let edit = TextEdit::singleline(&mut name)
+                                            .id(Id::new(("table_name", id)))
+                                            .desired_width(160.0);
+                                        if ui.add(edit).changed() {
+                                            edits.push(TableEdit::Name(id.clone(), name));
+                                        }
+                                    }
+                                    TableColumn::Status => {
+                                        ComboBox::from_id_source(("table_status", id))
+                                            .selected_text(self.data.status_name(&project.status))
+                                            .width(90.0)
+                                            .show_ui(ui, |ui| {
+                                                for (status_id, status_name) in &statuses {
+                                                    if ui.selectable_label(&project.status == status_id, status_name).clicked()
+                                                        && &project.status != status_id
+                                                    {
+                                                        edits.push(TableEdit::Status(id.clone(), status_id.clone()));
+                                                    }
+                                                }
                                             });
+                                    }
+                                    TableColumn::Fields => {
+                                        let text = fields_text(project);
+                                        ui.menu_button(if text.is_empty() { "（无）".to_string() } else { text }, |ui| {
+                                            for (field_id, field_name) in &all_fields {
+                                                let mut checked = project.field_ids.contains(field_id);
+                                                if ui.checkbox(&mut checked, field_name).changed() {
+                                                    let mut field_ids = project.field_ids.clone();
+                                                    if checked {
+                                                        field_ids.push(field_id.clone());
+                                                    } else {
+                                                        field_ids.retain(|f| f != field_id);
+                                                    }
+                                                    edits.push(TableEdit::Fields(id.clone(), field_ids));
+                                                }
+                                            }
+                                        });
+                                    }
+                                    TableColumn::Completion => {
+                                        let mut percentage = project.completion_percentage;
+                                        if ui.add(
+                                            egui::DragValue::new(&mut percentage)
+                                                .clamp_range(0.0..=100.0)
+                                                .suffix("%")
+                                                .max_decimals(0)
+                                        ).changed() {
+                                            edits.push(TableEdit::Completion(id.clone(), percentage));
+                                        }
+                                    }
+                                    TableColumn::Relations | TableColumn::Tags => {
+                                        ui.label(cell_text(*idx, project, column));
+                                    }
+                                    _ => {
+                                        let mut date = date_of(project, column);
+                                        if date_edit(ui, ("table_date", id, column), &mut date, theme.error) {
+                                            edits.push(TableEdit::Date(id.clone(), column, date));
                                         }
                                     }
-                                } else {
-                                    // 普通点击：选中项目
-                                    self.selected_project = Some(id.clone());
-                                    self.editing_project = Some(project.clone());
                                 }
-                                break;
                             }
+                            ui.end_row();
                         }
-                    }
-                }
+                    });
                 
-                // 处理项目拖拽开始
-                if response.drag_started() && !is_over_legend {
-                    if let Some(drag_pos) = response.interact_pointer_pos() {
-                        for (id, center, radius, _) in &project_centers {
-                            let distance = (drag_pos - *center).length();
-                            if distance <= *radius {
-                                self.dragging_project = Some(id.clone());
-                                self.selected_project = Some(id.clone());
-                                break;
-                            }
+                if rows.len() < self.data.projects.len() {
+                    ui.add_space(8.0);
+                    ui.label(
+                        RichText::new(format!("显示 {} / {} 个项目", rows.len(), self.data.projects.len()))
+                            .size(11.0)
+                            .color(theme.text_secondary)
+                    );
+                }
+            });
+        
+        self.table_sort = new_sort;
+        filters.retain(|_, f| !f.trim().is_empty());
+        self.table_filters = filters;
+        
+        // 行选择：单击单选，Ctrl/Cmd 切换，Shift 选择范围（按当前表格顺序）
+        if let Some(id) = clicked_row {
+            if modifiers.shift {
+                let anchor = self.selection_anchor.clone().unwrap_or_else(|| id.clone());
+                let a = row_ids.iter().position(|r| r == &anchor).unwrap_or(0);
+                let b = row_ids.iter().position(|r| r == &id).unwrap_or(0);
+                let (lo, hi) = (a.min(b), a.max(b));
+                self.selected_project_ids = row_ids[lo..=hi].to_vec();
+                self.selected_project = Some(id.clone());
+            } else if modifiers.command {
+                if let Some(pos) = self.selected_project_ids.iter().position(|s| s == &id) {
+                    self.selected_project_ids.remove(pos);
+                    self.selected_project = self.selected_project_ids.last().cloned();
+                } else {
+                    self.selected_project_ids.push(id.clone());
+                    self.selected_project = Some(id.clone());
+                }
+                self.selection_anchor = Some(id);
+            } else {
+                self.selected_project_ids = vec![id.clone()];
+                self.selected_project = Some(id.clone());
+                self.selection_anchor = Some(id);
+            }
+            self.editing_project = self.selected_project.as_ref().and_then(|id| self.data.projects.get(id)).cloned();
+        }
+        
+        // 应用单元格编辑
+        if !edits.is_empty() {
+            for edit in edits {
+                match edit {
+                    TableEdit::Name(id, name) => {
+                        if let Some(p) = self.data.projects.get_mut(&id) {
+                            p.name = name;
                         }
                     }
-                }
-                
-                // 处理项目拖拽中
-                if response.dragged() && self.dragging_project.is_some() {
-                    let delta = response.drag_delta();
-                    // 转换屏幕坐标变化到数据坐标变化
-                    let scale = auto_scale * self.visualization_zoom;
-                    let data_delta_x = delta.x / scale;
-                    let data_delta_y = delta.y / scale;
-                    
-                    if let Some(project_id) = &self.dragging_project.clone() {
-                        if let Some(project) = self.data.projects.get_mut(project_id) {
-                            project.position.0 += data_delta_x;
-                            project.position.1 += data_delta_y;
+                    TableEdit::Status(id, status) => {
+                        if let Some(p) = self.data.projects.get_mut(&id) {
+                            p.status = status;
                         }
-                        // 同步更新编辑中的项目
-                        if let Some(editing) = &mut self.editing_project {
-                            if &editing.id == project_id {
-                                editing.position.0 += data_delta_x;
-                                editing.position.1 += data_delta_y;
-                            }
+                    }
+                    TableEdit::Completion(id, percentage) => {
+                        if let Some(p) = self.data.projects.get_mut(&id) {
+                            p.completion_percentage = percentage;
                         }
                     }
-                }
-                
-                // 处理项目拖拽结束
-                if response.drag_stopped() && self.dragging_project.is_some() {
-                    // 检查位置是否正确，不正确则移动到最近的正确位置
-                    if let Some(project_id) = &self.dragging_project.clone() {
-                        if let Some(project) = self.data.projects.get(project_id).cloned() {
-                            if !self.is_project_position_valid(&project) {
-                                let new_pos = self.find_nearest_valid_position(&project);
-                                if let Some(p) = self.data.projects.get_mut(project_id) {
-                                    p.position = new_pos;
-                                }
-                                // 同步更新编辑中的项目
-                                if let Some(editing) = &mut self.editing_project {
-                                    if &editing.id == project_id {
-                                        editing.position = new_pos;
-                                    }
-                                }
+                    TableEdit::Date(id, column, date) => {
+                        if let Some(p) = self.data.projects.get_mut(&id) {
+                            match column {
+                                TableColumn::StartDate => p.start_date = date,
+                                TableColumn::TargetDate => p.target_date = date,
+                                TableColumn::SubmissionDate => p.submission_date = date,
+                                TableColumn::PublicationDate => p.publication_date = date,
+                                _ => {}
                             }
                         }
                     }
-                    // 保存历史记录
-                    self.save_to_history();
-                    // 保存数据
-                    self.save_data();
-                    self.dragging_project = None;
-                }
-
-                // 处理拖拽和缩放 - 排除图例拖拽和项目拖拽
-                if response.dragged() && !self.legend_dragging && self.dragging_project.is_none() {
-                    // 计算内容的边界（考虑缩放）
-                    let scaled_content_width = content_width * auto_scale * self.visualization_zoom;
-                    let scaled_content_height = content_height * auto_scale * self.visualization_zoom;
-                    
-                    // 边距
-                    let margin = 20.0;
-                    
-                    // 计算新的偏移量
-                    let new_offset_x = self.visualization_offset.x + response.drag_delta().x;
-                    let new_offset_y = self.visualization_offset.y + response.drag_delta().y;
-                    
-                    // 限制X方向：确保内容边界在窗口内
-                    let final_offset_x = if scaled_content_width <= rect.width() {
-                        // 内容小于窗口，允许在窗口内移动，但限制中心在窗口内
-                        let max_offset_x = (rect.width() - scaled_content_width) / 2.0;
-                        new_offset_x.clamp(-max_offset_x, max_offset_x)
-                    } else {
-                        // 内容大于窗口，限制边界在窗口内
-                        let min_offset_x = rect.left() + margin - offset_x - scaled_content_width / 2.0;
-                        let max_offset_x = rect.right() - margin - offset_x + scaled_content_width / 2.0;
-                        new_offset_x.clamp(min_offset_x, max_offset_x)
-                    };
-                    
-                    // 限制Y方向：确保内容边界在窗口内
-                    let final_offset_y = if scaled_content_height <= rect.height() {
-                        // 内容小于窗口，允许在窗口内移动，但限制中心在窗口内
-                        let max_offset_y = (rect.height() - scaled_content_height) / 2.0;
-                        new_offset_y.clamp(-max_offset_y, max_offset_y)
-                    } else {
-                        // 内容大于窗口，限制边界在窗口内
-                        let min_offset_y = rect.top() + margin - offset_y - scaled_content_height / 2.0;
-                        let max_offset_y = rect.bottom() - margin - offset_y + scaled_content_height / 2.0;
-                        new_offset_y.clamp(min_offset_y, max_offset_y)
-                    };
-                    
-                    // 应用限制后的偏移量
-                    self.visualization_offset = Vec2::new(final_offset_x, final_offset_y);
-                }
-
-                // 鼠标滚轮缩放
-                if response.hovered() {
-                    let scroll_delta = ctx.input(|i| i.raw_scroll_delta.y);
-                    if scroll_delta != 0.0 {
-                        let zoom_factor = 1.0 + scroll_delta * 0.001;
-                        self.visualization_zoom *= zoom_factor;
-                        self.visualization_zoom = self.visualization_zoom.clamp(0.1, 3.0);
+                    TableEdit::Fields(id, field_ids) => {
+                        // 修改领域后重新计算位置并解决重叠
+                        if let Some(mut project) = self.data.projects.get(&id).cloned() {
+                            project.field_ids = field_ids;
+                            project.position = self.calculate_project_position(&SpatialIndex::new(&self.data), &project);
+                            self.data.projects.insert(id, project);
+                            self.validate_and_fix_project_positions();
+                        }
                     }
                 }
-            });
+            }
+            if let Some(id) = &self.selected_project {
+                self.editing_project = self.data.projects.get(id).cloned();
+            }
+            self.save_to_history();
         }
     }
 
@@ -2270,6 +4074,16 @@ impl VennCVApp {
             return;
         }
         
+        // 后台布局任务进行中：Enter 接受结果，Esc 放弃，其他快捷键暂停
+        if let Some(worker) = &self.layout_worker {
+            if worker.is_finished() && ctx.input(|i| i.key_pressed(Key::Enter)) {
+                self.accept_layout_task();
+            } else if ctx.input(|i| i.key_pressed(Key::Escape)) {
+                self.cancel_layout_task();
+            }
+            return;
+        }
+        
         // Command+S: 保存 (macOS)
         if ctx.input(|i| i.key_pressed(Key::S) && i.modifiers.mac_cmd) {
             self.save_data();
@@ -2292,10 +4106,36 @@ impl VennCVApp {
         
         // Tab键处理已移至update函数开头，确保在UI渲染前消耗
         
+        // 复制/粘贴/创建副本（文本框有焦点时交给文本框处理）
+        if !ctx.wants_keyboard_input() {
+            let (copy, paste) = ctx.input(|i| {
+                let copy = i.events.iter().any(|e| matches!(e, Event::Copy));
+                let paste = i.events.iter().find_map(|e| match e {
+                    Event::Paste(text) => Some(text.clone()),
+                    _ => None,
+                });
+                (copy, paste)
+            });
+            if copy {
+                self.copy_to_clipboard(ctx);
+            }
+            if let Some(text) = paste {
+                self.paste_projects(&text);
+            }
+            if ctx.input(|i| i.key_pressed(Key::D) && i.modifiers.command) {
+                self.duplicate_selected_projects();
+            }
+        }
+        
+        // Ctrl+F / Command+F: 搜索项目
+        if ctx.input(|i| i.key_pressed(Key::F) && (i.modifiers.ctrl || i.modifiers.mac_cmd)) {
+            self.show_project_table = true;
+            self.search_focus_requested = true;
+        }
+        
         // Ctrl+Plus 或 Ctrl+=: 放大
         if ctx.input(|i| {
-            (i.key_pressed(Key::Plus) && i.modifiers.ctrl) ||
-            (i.key_pressed(Key::Equals) && i.modifiers.ctrl)
+            (i.key_pressed(Key::Plus) || i.key_pressed(Key::Equals)) && i.modifiers.ctrl
         }) {
             self.zoom_in();
         }
@@ -2321,30 +4161,11 @@ impl VennCVApp {
             if let Some(idx) = self.toolbar_focus_index {
                 match idx {
                     0 => self.create_new_project_shortcut(),
-                    1 => {
-                        if let Some(id) = self.selected_project.clone() {
-                            self.data.projects.shift_remove(&id);
-                            self.data.relations.retain(|r| r.from_id != id && r.to_id != id);
-                            self.selected_project = None;
-                            self.editing_project = None;
-                            self.save_to_history();
-                        }
-                    }
+                    1 => self.delete_selected_projects(),
                     2 => self.save_data(),
                     3 => self.undo(),
                     4 => self.redo(),
-                    5 => {
-                        // 只修正位置不正确的项目
-                        self.validate_and_fix_project_positions();
-                        // 更新编辑中的项目位置
-                        if let Some(id) = &self.selected_project {
-                            if let Some(project) = self.data.projects.get(id) {
-                                if let Some(editing) = &mut self.editing_project {
-                                    editing.position = project.position;
-                                }
-                            }
-                        }
-                    }
+                    5 => self.start_layout_task(LayoutTask::FixPositions, self.data.clone()),
                     _ => {}
                 }
             }
@@ -2387,28 +4208,33 @@ impl VennCVApp {
         }
     }
     
-    /// 新建项目（快捷键）
-    fn create_new_project_shortcut(&mut self) {
-        // 找到最大的项目编号，确保新ID不会冲突
-        let max_num = self.data.projects.keys()
+    /// 下一个可用的项目编号（已有 project_N 中最大的 N + 1），确保新ID不会冲突
+    fn next_project_number(&self) -> usize {
+        self.data.projects.keys()
             .filter_map(|id| id.strip_prefix("project_").and_then(|n| n.parse::<usize>().ok()))
             .max()
-            .unwrap_or(0);
-        let new_id = format!("project_{}", max_num + 1);
+            .unwrap_or(0)
+            + 1
+    }
+
+    /// 新建项目（快捷键）
+    fn create_new_project_shortcut(&mut self) {
+        let new_id = format!("project_{}", self.next_project_number());
         let mut new_project = Project {
             id: new_id.clone(),
             name: "新项目".to_string(),
             description: String::new(),
-            status: ProjectStatus::ToBeStarted,
+            status: self.data.default_status_id(),
             field_ids: Vec::new(),
             position: (400.0, 400.0),
             radius: 20.0,
             completion_percentage: 0.0,
+            ..Default::default()
         };
         // 先调整领域布局
-        self.adjust_field_layout();
+        layout::adjust_field_layout(&mut self.data);
         // 自动计算位置，避免重叠
-        new_project.position = self.calculate_project_position(&new_project);
+        new_project.position = self.calculate_project_position(&SpatialIndex::new(&self.data), &new_project);
         
         self.save_to_history();
         self.data.projects.insert(new_id.clone(), new_project.clone());
@@ -2416,19 +4242,451 @@ impl VennCVApp {
         self.editing_project = Some(new_project);
     }
     
+    /// 将选中的项目及它们之间的关系序列化为剪贴板 JSON
+    fn copy_selected_projects(&self) -> Option<String> {
+        let ids = &self.selected_project_ids;
+        let projects: Vec<Project> = ids.iter()
+            .filter_map(|id| self.data.projects.get(id))
+            .cloned()
+            .collect();
+        if projects.is_empty() {
+            return None;
+        }
+        let relations: Vec<ProjectRelation> = self.data.relations.iter()
+            .filter(|r| ids.contains(&r.from_id) && ids.contains(&r.to_id))
+            .cloned()
+            .collect();
+        let fields = self.data.fields.values()
+            .filter(|f| projects.iter().any(|p| self.data.effective_field_ids(p).contains(&f.id)))
+            .cloned()
+            .collect();
+        let statuses = self.data.statuses.iter()
+            .filter(|s| projects.iter().any(|p| p.status == s.id))
+            .cloned()
+            .collect();
+        let relation_types = self.data.relation_types.iter()
+            .filter(|t| relations.iter().any(|r| r.relation_type == t.id))
+            .cloned()
+            .collect();
+        let relation_tag_colors = self.data.relation_tag_colors.iter()
+            .filter(|(tag, _)| relations.iter().any(|r| r.tags.contains(tag)))
+            .map(|(tag, color)| (tag.clone(), *color))
+            .collect();
+        let clipboard = ProjectClipboard {
+            format: CLIPBOARD_FORMAT.to_string(),
+            projects,
+            relations,
+            fields,
+            statuses,
+            relation_types,
+            relation_tag_colors,
+        };
+        match serde_json::to_string_pretty(&clipboard) {
+            Ok(json) => Some(json),
+            Err(e) => {
+                eprintln!("复制项目失败: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 复制选中的项目到系统剪贴板（同时保留一份供菜单粘贴使用）
+    fn copy_to_clipboard(&mut self, ctx: &Context) {
+        if let Some(json) = self.copy_selected_projects() {
+            ctx.output_mut(|o| o.copied_text = json.clone());
+            self.project_clipboard = Some(json);
+        }
+    }
+
+    /// 粘贴剪贴板中的项目：生成新的项目ID，重连它们之间的关系，并自动计算位置
+    /// 领域按名称匹配（同名时优先同ID的领域），没有同名领域时被忽略；缺少的状态和关系类型从剪贴板补全
+    /// 返回是否为项目剪贴板内容（不是时不做任何修改）
+    fn paste_projects(&mut self, json: &str) -> bool {
+        let clipboard = match serde_json::from_str::<ProjectClipboard>(json) {
+            Ok(clipboard) if clipboard.format == CLIPBOARD_FORMAT => clipboard,
+            _ => return false,
+        };
+        if clipboard.projects.is_empty() {
+            return true;
+        }
+        for status in &clipboard.statuses {
+            if self.data.status(&status.id).is_none() {
+                let order = self.data.statuses.iter().map(|s| s.order).max().map(|o| o + 1).unwrap_or(0);
+                self.data.statuses.push(ProjectStatus { order, ..status.clone() });
+            }
+        }
+        for relation_type in &clipboard.relation_types {
+            if self.data.relation_type(&relation_type.id).is_none() {
+                let order = self.data.relation_types.iter().map(|t| t.order).max().map(|o| o + 1).unwrap_or(0);
+                self.data.relation_types.push(RelationType { order, ..relation_type.clone() });
+            }
+        }
+        // 新建领域的ID都是 field_N，不同文件中同ID的领域往往无关，因此按名称匹配（同名时优先同ID的领域）
+        let field_map: HashMap<String, String> = clipboard.fields.iter()
+            .filter_map(|f| {
+                let target = self.data.fields.get(&f.id)
+                    .filter(|t| t.name == f.name)
+                    .or_else(|| self.data.fields.values().find(|t| t.name == f.name))?;
+                Some((f.id.clone(), target.id.clone()))
+            })
+            .collect();
+
+        let mut id_map: HashMap<String, String> = HashMap::new();
+        let mut new_ids = Vec::new();
+        for source in &clipboard.projects {
+            let new_id = format!("project_{}", self.next_project_number());
+            let name_taken = self.data.projects.values().any(|p| p.name == source.name);
+            let project = Project {
+                id: new_id.clone(),
+                name: if name_taken { format!("{} 副本", source.name) } else { source.name.clone() },
+                field_ids: source.field_ids.iter().filter_map(|f| field_map.get(f).cloned()).collect(),
+                locked: false,  // 粘贴的项目需要重新摆放
+                ..source.clone()
+            };
+            self.data.projects.insert(new_id.clone(), project);
+            id_map.insert(source.id.clone(), new_id.clone());
+            new_ids.push(new_id);
+        }
+        for relation in &clipboard.relations {
+            if let (Some(from_id), Some(to_id)) = (id_map.get(&relation.from_id), id_map.get(&relation.to_id)) {
+                self.data.relations.push(ProjectRelation {
+                    from_id: from_id.clone(),
+                    to_id: to_id.clone(),
+                    ..relation.clone()
+                });
+            }
+        }
+        for (tag, color) in &clipboard.relation_tag_colors {
+            self.data.relation_tag_colors.entry(tag.clone()).or_insert(*color);
+        }
+        // 按目标领域逐个摆放粘贴出的项目
+        layout::place_projects(&mut self.data, &new_ids);
+        self.data.migrate();
+        // 选中粘贴出的项目
+        self.selected_project = new_ids.first().cloned();
+        self.selected_project_ids = new_ids;
+        self.refresh_editing_project();
+        self.save_to_history();
+        true
+    }
+
+    /// 创建选中项目的副本（不经过系统剪贴板）
+    fn duplicate_selected_projects(&mut self) {
+        if let Some(json) = self.copy_selected_projects() {
+            self.paste_projects(&json);
+        }
+    }
+
+    /// 可用 Tab/方向键切换的项目：搜索时为搜索结果，否则为全部项目
+    fn navigable_project_ids(&self) -> Vec<String> {
+        match &self.search_results {
+            Some(results) => results.clone(),
+            None => self.data.projects.keys().cloned().collect(),
+        }
+    }
+
+    /// 解析搜索框中的查询，更新搜索结果
+    fn update_search(&mut self) {
+        match query::parse(&self.search_query) {
+            Ok(filter) if !filter.is_empty() => {
+                self.search_results = Some(filter.select_projects(&self.data));
+                self.search_filter = Some(filter);
+                self.search_error = None;
+            }
+            Ok(_) => {
+                self.search_results = None;
+                self.search_filter = None;
+                self.search_error = None;
+            }
+            Err(e) => {
+                // 语法错误时不筛选，只提示错误
+                self.search_results = None;
+                self.search_filter = None;
+                self.search_error = Some(e);
+            }
+        }
+    }
+
+    /// 视图筛选表达式解析后的查询（语法错误时视为空查询）
+    fn view_query(&self) -> Query {
+        query::parse(&self.view_filter).unwrap_or_default()
+    }
+
+    /// 领域在当前视图中是否隐藏：自身或任一上级领域被隐藏
+    fn field_hidden(&self, field_id: &str) -> bool {
+        self.hidden_field_ids.iter().any(|h| h == field_id)
+            || self.data.field_ancestors(field_id).iter().any(|a| self.hidden_field_ids.contains(a))
+    }
+
+    /// 项目在当前视图筛选下是否显示：状态未隐藏、匹配筛选表达式，
+    /// 且至少属于一个未隐藏的领域（没有领域的项目不受领域筛选影响）
+    fn project_visible(&self, project: &Project, filter: &Query) -> bool {
+        !self.hidden_status_ids.contains(&project.status)
+            && (project.field_ids.is_empty()
+                || project.field_ids.iter().any(|f| !self.field_hidden(f)))
+            && filter.matches(&self.data, project)
+    }
+
+    /// 当前视图显示的项目ID
+    fn visible_project_ids(&self, filter: &Query) -> Vec<String> {
+        self.data.projects.values()
+            .filter(|p| self.project_visible(p, filter))
+            .map(|p| p.id.clone())
+            .collect()
+    }
+
+    /// 当前视图显示的关系序号：两端项目都显示、标签未全部隐藏且匹配筛选表达式
+    fn visible_relation_indices(&self, visible_project_ids: &[String], filter: &Query) -> Vec<usize> {
+        self.data.relations.iter()
+            .enumerate()
+            .filter(|(_, r)| visible_project_ids.contains(&r.from_id) && visible_project_ids.contains(&r.to_id))
+            .filter(|(_, r)| r.tags.is_empty() || r.tags.iter().any(|t| !self.hidden_relation_tags.contains(t)))
+            .filter(|(_, r)| filter.matches_relation(&self.data, r))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// 当前视图显示的领域ID：自身和上级领域都未隐藏，且包含显示的项目（属于子领域的项目也算在上级领域内）
+    fn visible_field_ids(&self, visible_project_ids: &[String]) -> Vec<String> {
+        self.data.fields.keys()
+            .filter(|id| !self.field_hidden(id))
+            .filter(|id| {
+                visible_project_ids.iter().any(|p| {
+                    self.data.projects.get(p).is_some_and(|p| self.data.effective_field_ids(p).contains(id))
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// 是否有视图筛选生效
+    fn view_filtered(&self) -> bool {
+        !self.view_filter.trim().is_empty()
+            || !self.hidden_field_ids.is_empty()
+            || !self.hidden_status_ids.is_empty()
+            || !self.hidden_relation_tags.is_empty()
+    }
+
+    /// 清除视图筛选
+    fn clear_view_filter(&mut self) {
+        self.view_filter.clear();
+        self.hidden_field_ids.clear();
+        self.hidden_status_ids.clear();
+        self.hidden_relation_tags.clear();
+        self.active_view = None;
+    }
+
+    /// 应用保存的视图
+    fn apply_saved_view(&mut self, view: &SavedView) {
+        self.view_filter = view.filter.clone();
+        self.hidden_field_ids = view.hidden_field_ids.clone();
+        self.hidden_status_ids = view.hidden_status_ids.clone();
+        self.hidden_relation_tags = view.hidden_relation_tags.clone();
+        self.visualization_zoom = view.zoom.clamp(0.1, 3.0);
+        self.visualization_offset = vec2(view.offset.0, view.offset.1);
+        self.settings.show_legend = view.show_legend;
+        self.legend_position = vec2(view.legend_position.0, view.legend_position.1);
+        self.active_view = Some(view.name.clone());
+        self.central_view = CentralView::Venn;
+    }
+
+    /// 将当前筛选、缩放和图例状态保存为视图（同名视图会被覆盖）
+    fn save_current_view(&mut self) {
+        let name = self.new_view_name.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let view = SavedView {
+            name: name.clone(),
+            filter: self.view_filter.trim().to_string(),
+            hidden_field_ids: self.hidden_field_ids.clone(),
+            hidden_status_ids: self.hidden_status_ids.clone(),
+            hidden_relation_tags: self.hidden_relation_tags.clone(),
+            zoom: self.visualization_zoom,
+            offset: (self.visualization_offset.x, self.visualization_offset.y),
+            show_legend: self.settings.show_legend,
+            legend_position: (self.legend_position.x, self.legend_position.y),
+        };
+        match self.data.saved_views.iter_mut().find(|v| v.name == name) {
+            Some(existing) => *existing = view,
+            None => self.data.saved_views.push(view),
+        }
+        self.active_view = Some(name);
+        self.new_view_name.clear();
+        self.save_to_history();
+    }
+
+    /// 导出当前视图的维恩图为 SVG 图片
+    fn export_svg(&mut self) {
+        let file_name = format!("{}.svg", self.active_view.as_deref().unwrap_or("VennCV"));
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("SVG", &["svg"])
+            .set_title("导出图片")
+            .set_file_name(file_name)
+            .save_file()
+        {
+            let theme = self.theme();
+            let filter = self.view_query();
+            let visible_project_ids = self.visible_project_ids(&filter);
+            let options = crate::export::SvgOptions {
+                background: self.canvas_background(&theme),
+                visible_field_ids: self.visible_field_ids(&visible_project_ids),
+                visible_relation_indices: self.visible_relation_indices(&visible_project_ids, &filter),
+                visible_project_ids,
+                legend_status_ids: self.legend_statuses().into_iter().map(|(_, s)| s.id.clone()).collect(),
+                show_legend: self.settings.show_legend,
+            };
+            let svg = crate::export::venn_svg(&self.data, &theme, &self.settings, &options);
+            if let Err(e) = std::fs::write(&path, svg) {
+                eprintln!("导出图片失败: {}", e);
+            }
+        }
+    }
+
+    /// 视图菜单中的筛选和保存视图部分
+    fn saved_views_menu(&mut self, ui: &mut Ui) {
+        ui.menu_button("筛选", |ui| {
+            ui.label("筛选表达式（与搜索相同）:").on_hover_text(query::QUERY_HELP);
+            if ui.text_edit_singleline(&mut self.view_filter).changed() {
+                self.active_view = None;
+            }
+            if let Err(e) = query::parse(&self.view_filter) {
+                ui.label(RichText::new(e).size(10.0).color(self.theme().error));
+            }
+            if !self.data.project_tags.is_empty() {
+                ui.menu_button("按项目标签筛选", |ui| {
+                    for tag in self.data.project_tags.clone() {
+                        if ui.button(&tag).clicked() {
+                            // 在筛选表达式后追加条件（与已有条件同时满足）
+                            let condition = format!("keyword={}", query::quote(&tag));
+                            self.view_filter = match self.view_filter.trim() {
+                                "" => condition,
+                                existing => format!("{} {}", existing, condition),
+                            };
+                            self.active_view = None;
+                            ui.close_menu();
+                        }
+                    }
+                });
+            }
+            ui.separator();
+            ui.label("显示的领域:");
+            let fields: Vec<(String, String)> = self.data.fields.values()
+                .map(|f| (f.id.clone(), f.name.clone()))
+                .collect();
+            for (id, name) in fields {
+                let mut shown = !self.hidden_field_ids.contains(&id);
+                if ui.checkbox(&mut shown, name).changed() {
+                    if shown {
+                        self.hidden_field_ids.retain(|f| f != &id);
+                    } else {
+                        self.hidden_field_ids.push(id);
+                    }
+                    self.active_view = None;
+                }
+            }
+            ui.separator();
+            ui.label("显示的状态:");
+            let statuses: Vec<(String, String)> = self.data.sorted_statuses().into_iter()
+                .map(|s| (s.id.clone(), s.name.clone()))
+                .collect();
+            for (id, name) in statuses {
+                let mut shown = !self.hidden_status_ids.contains(&id);
+                if ui.checkbox(&mut shown, name).changed() {
+                    if shown {
+                        self.hidden_status_ids.retain(|s| s != &id);
+                    } else {
+                        self.hidden_status_ids.push(id);
+                    }
+                    self.active_view = None;
+                }
+            }
+            if !self.data.relation_tags.is_empty() {
+                ui.separator();
+                ui.label("显示的关系标签:");
+                for tag in self.data.relation_tags.clone() {
+                    let mut shown = !self.hidden_relation_tags.contains(&tag);
+                    if ui.checkbox(&mut shown, &tag).changed() {
+                        if shown {
+                            self.hidden_relation_tags.retain(|t| t != &tag);
+                        } else {
+                            self.hidden_relation_tags.push(tag);
+                        }
+                        self.active_view = None;
+                    }
+                }
+            }
+            ui.separator();
+            if ui.add_enabled(self.view_filtered(), Button::new("显示全部")).clicked() {
+                self.clear_view_filter();
+            }
+        });
+
+        ui.menu_button("保存的视图", |ui| {
+            if self.data.saved_views.is_empty() {
+                ui.label("（暂无保存的视图）");
+            }
+            let mut apply = None;
+            let mut export = None;
+            let mut delete = None;
+            for (index, view) in self.data.saved_views.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    let active = self.active_view.as_ref() == Some(&view.name);
+                    if ui.selectable_label(active, &view.name).clicked() {
+                        apply = Some(index);
+                    }
+                    if ui.small_button("导出").on_hover_text("应用此视图并导出为 SVG 图片").clicked() {
+                        export = Some(index);
+                    }
+                    if ui.small_button("删除").clicked() {
+                        delete = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = apply.or(export) {
+                let view = self.data.saved_views[index].clone();
+                self.apply_saved_view(&view);
+                if export.is_some() {
+                    self.export_svg();
+                }
+                ui.close_menu();
+            }
+            if let Some(index) = delete {
+                let removed = self.data.saved_views.remove(index);
+                if self.active_view.as_ref() == Some(&removed.name) {
+                    self.active_view = None;
+                }
+                self.save_to_history();
+            }
+            ui.separator();
+            ui.label("保存当前视图为:");
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut self.new_view_name);
+                if ui.add_enabled(!self.new_view_name.trim().is_empty(), Button::new("保存")).clicked() {
+                    self.save_current_view();
+                }
+            });
+        });
+
+        if ui.button("导出图片 (SVG)").clicked() {
+            self.export_svg();
+            ui.close_menu();
+        }
+    }
+
     /// 切换到下一个项目
     fn next_project(&mut self) {
-        let project_ids: Vec<String> = self.data.projects.keys().cloned().collect();
+        let project_ids = self.navigable_project_ids();
         if project_ids.is_empty() {
             return;
         }
         
-        let next_index = if let Some(ref current) = self.selected_project {
-            let current_index = project_ids.iter().position(|id| id == current).unwrap_or(0);
-            (current_index + 1) % project_ids.len()
-        } else {
-            // 没有选中时，从第一个项目开始
-            0
+        let current_index = self.selected_project.as_ref()
+            .and_then(|current| project_ids.iter().position(|id| id == current));
+        let next_index = match current_index {
+            Some(index) => (index + 1) % project_ids.len(),
+            // 没有选中（或选中的项目不在搜索结果中）时，从第一个项目开始
+            None => 0,
         };
         
         let next_id = project_ids[next_index].clone();
@@ -2441,7 +4699,7 @@ impl VennCVApp {
     
     /// 切换到上一个项目
     fn previous_project(&mut self) {
-        let project_ids: Vec<String> = self.data.projects.keys().cloned().collect();
+        let project_ids = self.navigable_project_ids();
         if project_ids.is_empty() {
             return;
         }
@@ -2486,6 +4744,7 @@ impl VennCVApp {
 
     fn new_file(&mut self) {
         self.data = AppData::default();
+        self.clear_view_filter();
         self.current_file_path = None;
         self.selected_project = None;
         self.editing_project = None;
@@ -2502,8 +4761,10 @@ impl VennCVApp {
         {
                             if let Ok(content) = std::fs::read_to_string(&path) {
                                 match serde_json::from_str::<AppData>(&content) {
-                                    Ok(data) => {
+                                    Ok(mut data) => {
+                                        data.migrate();
                                         self.data = data.clone();
+                                        self.clear_view_filter();
                                         self.current_file_path = Some(path.clone());
                                         self.selected_project = None;
                                         self.editing_project = None;
@@ -2581,6 +4842,18 @@ impl VennCVApp {
                 match serde_json::from_str::<AppData>(&content) {
                     Ok(imported_data) => {
                         // 合并导入的数据（保留当前数据，添加或更新导入的数据）
+                        // 合并状态目录（按ID，已有状态保持不变）
+                        for status in &imported_data.statuses {
+                            if self.data.status(&status.id).is_none() {
+                                self.data.statuses.push(status.clone());
+                            }
+                        }
+                        // 合并关系类型目录（按ID，已有类型保持不变）
+                        for relation_type in &imported_data.relation_types {
+                            if self.data.relation_type(&relation_type.id).is_none() {
+                                self.data.relation_types.push(relation_type.clone());
+                            }
+                        }
                         // 合并项目
                         for (id, project) in imported_data.projects {
                             self.data.projects.insert(id, project);
@@ -2597,6 +4870,27 @@ impl VennCVApp {
                                 self.data.relations.push(relation);
                             }
                         }
+                        // 合并关系标签库和标签颜色（已有标签的颜色保持不变）
+                        for tag in imported_data.relation_tags {
+                            if !self.data.relation_tags.contains(&tag) {
+                                self.data.relation_tags.push(tag);
+                            }
+                        }
+                        for (tag, color) in imported_data.relation_tag_colors {
+                            self.data.relation_tag_colors.entry(tag).or_insert(color);
+                        }
+                        for tag in imported_data.project_tags {
+                            if !self.data.project_tags.contains(&tag) {
+                                self.data.project_tags.push(tag);
+                            }
+                        }
+                        // 合并保存的视图（按名称，已有视图保持不变）
+                        for view in imported_data.saved_views {
+                            if !self.data.saved_views.iter().any(|v| v.name == view.name) {
+                                self.data.saved_views.push(view);
+                            }
+                        }
+                        self.data.migrate();
                         // 保存到历史
                         self.save_to_history();
                     }
@@ -2629,9 +4923,32 @@ impl VennCVApp {
         }
     }
 
+    /// 按搜索查询范围导出数据：匹配的项目、它们所属的领域和它们之间的关系
+    fn export_search_results(&mut self) {
+        let Some(results) = self.search_results.clone() else {
+            return;
+        };
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .set_title("导出搜索结果")
+            .save_file()
+        {
+            match serde_json::to_string_pretty(&self.data.subset(&results)) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&path, json) {
+                        eprintln!("导出数据失败: {}", e);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("序列化数据失败: {}", e);
+                }
+            }
+        }
+    }
+
     /// 验证用户登录，从config.yaml读取用户信息
     /// 返回 (数据存储路径, 最近编辑的文件路径)
-    fn verify_user(&self, username: &str, password: &str) -> Result<Option<(Option<String>, Option<String>)>, String> {
+    fn verify_user(&self, username: &str, password: &str) -> Result<Option<UserLoginInfo>, String> {
         let config_path = "config.yaml";
         
         // 如果config.yaml不存在，创建默认配置
@@ -2707,7 +5024,7 @@ impl VennCVApp {
                 .map_err(|e| format!("创建用户目录失败: {}", e))?;
         }
 
-        let user_dir = format!("{}/{}", users_dir, username);
+        let user_dir = default_user_dir(username);
         if !std::path::Path::new(&user_dir).exists() {
             std::fs::create_dir_all(&user_dir)
                 .map_err(|e| format!("创建用户目录失败: {}", e))?;
@@ -2727,6 +5044,200 @@ impl VennCVApp {
         Ok(())
     }
 
+    /// 用户管理中可见的用户：admin 可管理所有用户，其他用户只能管理自己
+    fn manageable_users(&self) -> Vec<String> {
+        match self.current_user.as_deref() {
+            Some("admin") => self.available_users.clone(),
+            Some(username) => vec![username.to_string()],
+            None => Vec::new(),
+        }
+    }
+
+    /// 打开用户管理对话框
+    fn open_user_admin_dialog(&mut self) {
+        self.load_available_users();
+        self.show_user_admin_dialog = true;
+        self.user_admin_selected = self.current_user.clone();
+        self.reset_user_admin_inputs();
+        self.user_admin_message.clear();
+        self.user_admin_error.clear();
+    }
+
+    /// 清空用户管理对话框中的输入
+    fn reset_user_admin_inputs(&mut self) {
+        self.user_admin_rename = self.user_admin_selected.clone().unwrap_or_default();
+        self.user_admin_old_password.clear();
+        self.user_admin_new_password.clear();
+        self.user_admin_confirm_password.clear();
+        self.user_admin_pending_delete = None;
+        self.user_admin_delete_files = false;
+    }
+
+    /// 修改用户密码
+    /// admin 可以直接重置其他用户的密码，其他情况需要验证当前密码
+    fn change_user_password(&mut self, username: &str, old_password: &str, new_password: &str) -> Result<(), String> {
+        if new_password.is_empty() {
+            return Err("新密码不能为空".to_string());
+        }
+        let mut config = self.load_config()?;
+        let is_admin_reset = self.current_user.as_deref() == Some("admin") && username != "admin";
+        let user = config.users.iter_mut()
+            .find(|u| u.username == username)
+            .ok_or_else(|| "用户不存在".to_string())?;
+        if !is_admin_reset && user.password_hash != old_password {
+            return Err("当前密码错误".to_string());
+        }
+        user.password_hash = new_password.to_string();  // 简单实现，实际应使用加密
+        self.save_config(&config)
+    }
+
+    /// 重命名用户
+    /// 如果用户使用默认目录（users/用户名），目录会随之移动，并同步更新最近编辑的文件路径
+    /// 管理员权限由用户名 admin 决定，因此 admin 不能改名，其他用户也不能改名为 admin
+    fn rename_user(&mut self, old_name: &str, new_name: &str) -> Result<(), String> {
+        let new_name = new_name.trim();
+        validate_username(new_name)?;
+        if old_name == new_name {
+            return Ok(());
+        }
+        if old_name == "admin" {
+            return Err("admin 用户不能重命名".to_string());
+        }
+        if new_name == "admin" {
+            return Err("admin 为保留用户名".to_string());
+        }
+
+        let mut config = self.load_config()?;
+        if config.users.iter().any(|u| u.username == new_name) {
+            return Err("用户名已存在".to_string());
+        }
+        let user_idx = config.users.iter()
+            .position(|u| u.username == old_name)
+            .ok_or_else(|| "用户不存在".to_string())?;
+
+        // 只有默认目录跟随用户名移动，自定义目录保持不变
+        let old_storage = config.users[user_idx].data_storage_path.clone();
+        // 移动前记录旧目录的绝对路径，用于改写以绝对路径保存的文件
+        let old_storage_abs = old_storage.as_deref().map(absolute_dir);
+        let uses_default_dir = old_storage.as_deref() == Some(default_user_dir(old_name).as_str());
+        let new_storage = if uses_default_dir {
+            let new_dir = default_user_dir(new_name);
+            move_directory(std::path::Path::new(&default_user_dir(old_name)), std::path::Path::new(&new_dir))?;
+            Some(new_dir)
+        } else {
+            old_storage.clone()
+        };
+
+        {
+            let user = &mut config.users[user_idx];
+            user.username = new_name.to_string();
+            if let (Some(old_dir), Some(old_abs), Some(new_dir)) = (&old_storage, &old_storage_abs, &new_storage) {
+                user.last_edited_file = user.last_edited_file.as_ref()
+                    .map(|f| rebase_path(f, old_dir, old_abs, new_dir));
+            }
+            user.data_storage_path = new_storage.clone();
+        }
+
+        if let Err(e) = self.save_config(&config) {
+            // 保存失败时尽量把目录移回原处
+            if uses_default_dir {
+                let _ = move_directory(std::path::Path::new(&default_user_dir(new_name)), std::path::Path::new(&default_user_dir(old_name)));
+            }
+            return Err(e);
+        }
+
+        // 同步当前会话状态
+        if self.current_user.as_deref() == Some(old_name) {
+            self.current_user = Some(new_name.to_string());
+            self.user_data_storage_path = new_storage.clone();
+            if let (Some(old_dir), Some(old_abs), Some(new_dir), Some(path)) = (&old_storage, &old_storage_abs, &new_storage, &self.current_file_path) {
+                let rebased = rebase_path(&path.to_string_lossy(), old_dir, old_abs, new_dir);
+                self.current_file_path = Some(std::path::PathBuf::from(rebased));
+            }
+        }
+        if self.login_username == old_name {
+            self.login_username = new_name.to_string();
+        }
+        self.load_available_users();
+        Ok(())
+    }
+
+    /// 删除用户
+    /// 只会删除默认目录（users/用户名），自定义存储目录需要用户自行处理
+    fn delete_user(&mut self, username: &str, delete_files: bool) -> Result<(), String> {
+        if self.current_user.as_deref() == Some(username) {
+            return Err("不能删除当前登录的用户".to_string());
+        }
+        if username == "admin" {
+            return Err("admin 用户不能删除".to_string());
+        }
+        let mut config = self.load_config()?;
+        let user_idx = config.users.iter()
+            .position(|u| u.username == username)
+            .ok_or_else(|| "用户不存在".to_string())?;
+        let removed = config.users.remove(user_idx);
+        self.save_config(&config)?;
+
+        if delete_files {
+            let default_dir = default_user_dir(username);
+            if removed.data_storage_path.as_deref() == Some(default_dir.as_str()) {
+                if std::path::Path::new(&default_dir).exists() {
+                    std::fs::remove_dir_all(&default_dir)
+                        .map_err(|e| format!("用户已删除，但删除数据目录失败: {}", e))?;
+                }
+            } else if let Some(path) = removed.data_storage_path {
+                return Err(format!("用户已删除，自定义数据目录未删除: {}", path));
+            }
+        }
+
+        if self.login_username == username {
+            self.login_username.clear();
+        }
+        self.load_available_users();
+        Ok(())
+    }
+
+    /// 移动用户数据目录到新位置，并更新 data_storage_path 和 last_edited_file
+    /// new_dir 为 None 时恢复到默认目录（users/用户名）
+    fn relocate_user_storage(&mut self, username: &str, new_dir: Option<std::path::PathBuf>) -> Result<(), String> {
+        let mut config = self.load_config()?;
+        let user_idx = config.users.iter()
+            .position(|u| u.username == username)
+            .ok_or_else(|| "用户不存在".to_string())?;
+
+        let old_dir = config.users[user_idx].data_storage_path.clone()
+            .unwrap_or_else(|| default_user_dir(username));
+        let new_dir = new_dir
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| default_user_dir(username));
+        if std::path::Path::new(&old_dir) == std::path::Path::new(&new_dir) {
+            return Ok(());
+        }
+
+        let old_dir_abs = absolute_dir(&old_dir);
+        move_directory(std::path::Path::new(&old_dir), std::path::Path::new(&new_dir))?;
+
+        {
+            let user = &mut config.users[user_idx];
+            user.last_edited_file = user.last_edited_file.as_ref()
+                .map(|f| rebase_path(f, &old_dir, &old_dir_abs, &new_dir));
+            user.data_storage_path = Some(new_dir.clone());
+        }
+        if let Err(e) = self.save_config(&config) {
+            let _ = move_directory(std::path::Path::new(&new_dir), std::path::Path::new(&old_dir));
+            return Err(e);
+        }
+
+        if self.current_user.as_deref() == Some(username) {
+            self.user_data_storage_path = Some(new_dir.clone());
+            if let Some(path) = &self.current_file_path {
+                let rebased = rebase_path(&path.to_string_lossy(), &old_dir, &old_dir_abs, &new_dir);
+                self.current_file_path = Some(std::path::PathBuf::from(rebased));
+            }
+        }
+        Ok(())
+    }
+
     /// 更新最近编辑的文件路径
     /// 只更新属于当前用户数据存储路径的文件，避免用户文件路径混乱
     fn update_last_edited_file(&mut self, file_path: &std::path::Path) {
@@ -2818,8 +5329,10 @@ impl VennCVApp {
             {
                                 if let Ok(content) = std::fs::read_to_string(&path) {
                                     match serde_json::from_str::<crate::models::AppData>(&content) {
-                                        Ok(data) => {
+                                        Ok(mut data) => {
+                                            data.migrate();
                                             self.data = data.clone();
+                                            self.clear_view_filter();
                                             self.current_file_path = Some(path.clone());
                                             self.selected_project = None;
                                             self.editing_project = None;
@@ -2842,1063 +5355,279 @@ impl VennCVApp {
         }
     }
 
-    /// 检查项目位置是否正确（整个项目圆圈在所有目标领域内，且整个圆圈不在非目标领域内）
-    fn is_project_position_valid(&self, project: &Project) -> bool {
-        let (x, y) = project.position;
-        let project_radius = project.radius;
-        
-        if project.field_ids.is_empty() {
-            // 无领域的项目必须在所有领域之外
-            for field in self.data.fields.values() {
-                let dx = x - field.position.0;
-                let dy = y - field.position.1;
-                let distance = (dx * dx + dy * dy).sqrt();
-                // 项目圆圈最近点必须在领域外
-                if distance - project_radius < field.radius {
-                    return false;  // 项目圆圈部分在某个领域内
-                }
-            }
-        } else {
-            // 检查整个项目圆圈是否完全在所有目标领域内
-            for field_id in &project.field_ids {
-                if let Some(field) = self.data.fields.get(field_id) {
-                    let dx = x - field.position.0;
-                    let dy = y - field.position.1;
-                    let distance = (dx * dx + dy * dy).sqrt();
-                    // 项目圆圈最远点到领域中心的距离 = distance + project_radius
-                    // 必须 <= field.radius（留5像素边距）
-                    if distance + project_radius > field.radius - 5.0 {
-                        return false;  // 项目圆圈部分在目标领域外
-                    }
-                }
-            }
-            
-            // 检查整个项目圆圈是否完全在非目标领域外
-            for field in self.data.fields.values() {
-                if project.field_ids.contains(&field.id) {
-                    continue;
-                }
-                let dx = x - field.position.0;
-                let dy = y - field.position.1;
-                let distance = (dx * dx + dy * dy).sqrt();
-                // 项目圆圈最近点到领域中心的距离 = distance - project_radius
-                // 必须 >= field.radius（项目圆圈完全在非目标领域外）
-                if distance - project_radius < field.radius {
-                    return false;  // 项目圆圈部分在非目标领域内
-                }
-            }
-        }
-        
-        // 检查与其他项目是否重叠
-        for other in self.data.projects.values() {
-            if other.id == project.id {
-                continue;
-            }
-            let dx = x - other.position.0;
-            let dy = y - other.position.1;
-            let distance = (dx * dx + dy * dy).sqrt();
-            // 两个圆圈不能重叠：距离必须 > 两个半径之和
-            let min_dist = project_radius + other.radius + 15.0;  // 至少15像素间距
-            if distance < min_dist {
-                return false;  // 与其他项目重叠
-            }
-        }
-        
-        true
-    }
-    
-    /// 找到离当前位置最近的正确位置
-    fn find_nearest_valid_position(&self, project: &Project) -> (f32, f32) {
-        let (current_x, current_y) = project.position;
-        let project_radius = project.radius;
-        
-        // 无领域项目：找到所有领域之外的位置
-        if project.field_ids.is_empty() {
-            let all_fields: Vec<&ResearchField> = self.data.fields.values().collect();
-            
-            let mut best_pos = project.position;
-            let mut best_dist = f32::MAX;
-            
-            for attempt in 0..500 {
-                let angle = (attempt as f32) * 0.618 * std::f32::consts::PI * 2.0;
-                let radius = (attempt as f32).sqrt() * 5.0;
-                
-                let x = current_x + radius * angle.cos();
-                let y = current_y + radius * angle.sin();
-                
-                // 检查是否在所有领域之外
-                let mut outside_all = true;
-                for field in &all_fields {
-                    let dx = x - field.position.0;
-                    let dy = y - field.position.1;
-                    let distance = (dx * dx + dy * dy).sqrt();
-                    if distance - project_radius < field.radius {
-                        outside_all = false;
-                        break;
-                    }
-                }
-                if !outside_all {
-                    continue;
-                }
-                
-                // 检查与其他项目的距离
-                let mut overlaps = false;
-                for existing in self.data.projects.values() {
-                    if existing.id == project.id {
-                        continue;
-                    }
-                    let dx = x - existing.position.0;
-                    let dy = y - existing.position.1;
-                    let dist = (dx * dx + dy * dy).sqrt();
-                    if dist < project_radius + existing.radius + 15.0 {
-                        overlaps = true;
-                        break;
-                    }
-                }
-                if overlaps {
-                    continue;
-                }
-                
-                let dist = ((x - current_x).powi(2) + (y - current_y).powi(2)).sqrt();
-                if dist < best_dist {
-                    best_dist = dist;
-                    best_pos = (x, y);
-                    if dist < 1.0 {
-                        break;
-                    }
-                }
-            }
-            
-            return best_pos;
-        }
-        
-        // 收集目标领域
-        let target_fields: Vec<&ResearchField> = project.field_ids.iter()
-            .filter_map(|id| self.data.fields.get(id))
-            .collect();
-        
-        if target_fields.is_empty() {
-            return project.position;
-        }
-        
-        // 收集非目标领域
-        let non_target_fields: Vec<&ResearchField> = self.data.fields.values()
-            .filter(|f| !project.field_ids.contains(&f.id))
-            .collect();
-        
-        let mut best_pos = project.position;
-        let mut best_dist = f32::MAX;
-        
-        // 计算目标领域的中心
-        let mut target_center_x = 0.0;
-        let mut target_center_y = 0.0;
-        for field in &target_fields {
-            target_center_x += field.position.0;
-            target_center_y += field.position.1;
-        }
-        target_center_x /= target_fields.len() as f32;
-        target_center_y /= target_fields.len() as f32;
-        
-        // 计算目标领域的最小半径（用于从边缘搜索）
-        let min_target_radius = target_fields.iter()
-            .map(|f| f.radius)
-            .min_by(|a, b| a.partial_cmp(b).unwrap())
-            .unwrap_or(100.0);
-        
-        // 从当前位置和目标领域边缘两个起点搜索
-        // 边缘起点：远离中心的方向
-        let edge_angle = (current_y - target_center_y).atan2(current_x - target_center_x);
-        let edge_x = target_center_x + (min_target_radius * 0.6) * edge_angle.cos();
-        let edge_y = target_center_y + (min_target_radius * 0.6) * edge_angle.sin();
-        
-        let search_starts = [(current_x, current_y), (edge_x, edge_y)];
-        
-        for (start_x, start_y) in search_starts {
-        // 螺旋搜索找最近的有效位置
-        for attempt in 0..500 {
-            let angle = (attempt as f32) * 0.618 * std::f32::consts::PI * 2.0;
-            let radius = (attempt as f32).sqrt() * 8.0;  // 增大搜索步长
-            
-            let x = start_x + radius * angle.cos();
-            let y = start_y + radius * angle.sin();
-            
-            // 检查整个项目圆圈是否完全在所有目标领域内
-            let mut in_all_targets = true;
-            for field in &target_fields {
-                let dx = x - field.position.0;
-                let dy = y - field.position.1;
-                let distance = (dx * dx + dy * dy).sqrt();
-                // 项目圆圈最远点必须在领域内
-                if distance + project_radius > field.radius - 5.0 {
-                    in_all_targets = false;
-                    break;
-                }
-            }
-            if !in_all_targets {
-                continue;
-            }
-            
-            // 检查整个项目圆圈是否完全在非目标领域外
-            let mut in_non_target = false;
-            for field in &non_target_fields {
-                let dx = x - field.position.0;
-                let dy = y - field.position.1;
-                let distance = (dx * dx + dy * dy).sqrt();
-                // 项目圆圈最近点必须在非目标领域外
-                if distance - project_radius < field.radius {
-                    in_non_target = true;
-                    break;
-                }
-            }
-            if in_non_target {
-                continue;
-            }
-            
-            // 检查与其他项目的距离（避免重叠，保持间距）
-            let mut overlaps_project = false;
-            for existing in self.data.projects.values() {
-                if existing.id == project.id {
-                    continue;
-                }
-                let dx = x - existing.position.0;
-                let dy = y - existing.position.1;
-                let dist = (dx * dx + dy * dy).sqrt();
-                // 两个圆圈不能重叠：距离必须 > 两个半径之和 + 间距
-                let min_dist = project_radius + existing.radius + 15.0;  // 保持15像素间距
-                if dist < min_dist {
-                    overlaps_project = true;
-                    break;
-                }
-            }
-            if overlaps_project {
-                continue;
-            }
-            
-            // 计算到原位置的距离
-            let dist = ((x - current_x).powi(2) + (y - current_y).powi(2)).sqrt();
-            if dist < best_dist {
-                best_dist = dist;
-                best_pos = (x, y);
-                if dist < 1.0 {
-                    break;  // 足够近了
-                }
-            }
-        }
-        }  // 结束 search_starts 循环
-        
-        best_pos
-    }
-    
-    /// 检查点是否在线段附近（用于检测箭头穿过）
-    fn point_near_line_segment(&self, px: f32, py: f32, x1: f32, y1: f32, x2: f32, y2: f32, threshold: f32) -> bool {
-        let line_len_sq = (x2 - x1).powi(2) + (y2 - y1).powi(2);
-        if line_len_sq < 1.0 {
-            return false;  // 线段太短
-        }
-        
-        // 计算点到线段的最近点参数 t
-        let t = ((px - x1) * (x2 - x1) + (py - y1) * (y2 - y1)) / line_len_sq;
-        let t = t.clamp(0.0, 1.0);
-        
-        // 最近点坐标
-        let nearest_x = x1 + t * (x2 - x1);
-        let nearest_y = y1 + t * (y2 - y1);
-        
-        // 计算距离
-        let dist = ((px - nearest_x).powi(2) + (py - nearest_y).powi(2)).sqrt();
-        dist < threshold
-    }
-    
-    /// 重新计算所有项目位置（用于领域位置变化后）
-    fn recalculate_all_project_positions(&mut self) {
-        let project_ids: Vec<String> = self.data.projects.keys().cloned().collect();
-        for id in project_ids {
-            if let Some(project) = self.data.projects.get(&id).cloned() {
-                let new_pos = self.calculate_project_position(&project);
-                if let Some(p) = self.data.projects.get_mut(&id) {
-                    p.position = new_pos;
-                }
-            }
-        }
-    }
-    
-    /// 重新计算所有项目位置（带随机抖动，用于领域顺序变化后）
-    fn recalculate_all_project_positions_with_jitter(&mut self) {
-        use std::time::{SystemTime, UNIX_EPOCH};
-        let seed = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_nanos() as u64;
-        
-        let project_ids: Vec<String> = self.data.projects.keys().cloned().collect();
-        for (idx, id) in project_ids.iter().enumerate() {
-            if let Some(project) = self.data.projects.get(id).cloned() {
-                // 先给位置加入随机抖动，避免完全重叠
-                let jitter_seed = seed.wrapping_add(idx as u64);
-                let jitter_x = ((jitter_seed % 100) as f32 - 50.0) * 0.5;
-                let jitter_y = (((jitter_seed / 100) % 100) as f32 - 50.0) * 0.5;
-                
-                if let Some(p) = self.data.projects.get_mut(id) {
-                    p.position.0 += jitter_x;
-                    p.position.1 += jitter_y;
-                }
-                
-                // 重新获取带抖动的项目
-                if let Some(project) = self.data.projects.get(id).cloned() {
-                    let new_pos = self.calculate_project_position(&project);
-                    if let Some(p) = self.data.projects.get_mut(id) {
-                        p.position = new_pos;
-                    }
-                }
-            }
-        }
+    /// 检查项目位置是否正确（整个项目圆圈在所有目标领域内，且整个圆圈不在非目标领域内，与其他项目不重叠）
+    /// index 由调用方按当前数据构建，同一轮检查多个项目时共用
+    fn is_project_position_valid(&self, index: &SpatialIndex, project: &Project) -> bool {
+        layout::is_position_valid(&self.data, index, project)
     }
-    
-    /// 验证并修正所有项目位置（使用物理引擎思想）
-    fn validate_and_fix_project_positions(&mut self) {
-        let mut changed = false;
-        
-        // 第一步：先确保所有项目在正确的区域内
-        let project_ids: Vec<String> = self.data.projects.keys().cloned().collect();
-        for id in &project_ids {
-            if let Some(project) = self.data.projects.get(id).cloned() {
-                if !self.is_project_in_correct_region(&project) {
-                    let new_pos = self.find_nearest_valid_position(&project);
-                    if let Some(p) = self.data.projects.get_mut(id) {
-                        p.position = new_pos;
-                        changed = true;
-                    }
-                }
-            }
-        }
-        
-        // 第二步：物理引擎迭代，解决重叠问题
-        let physics_iterations = 50;
-        let damping = 0.8;  // 阻尼系数
-        let min_spacing = 15.0;  // 最小间距
-        let arrow_spacing = 25.0;  // 箭头避让距离
-        
-        for _ in 0..physics_iterations {
-            let mut forces: std::collections::HashMap<String, (f32, f32)> = std::collections::HashMap::new();
-            
-            // 初始化力为零
-            for id in &project_ids {
-                forces.insert(id.clone(), (0.0, 0.0));
-            }
-            
-            // 计算项目之间的斥力
-            for i in 0..project_ids.len() {
-                for j in (i + 1)..project_ids.len() {
-                    let id1 = &project_ids[i];
-                    let id2 = &project_ids[j];
-                    
-                    if let (Some(p1), Some(p2)) = (
-                        self.data.projects.get(id1),
-                        self.data.projects.get(id2)
-                    ) {
-                        let dx = p2.position.0 - p1.position.0;
-                        let dy = p2.position.1 - p1.position.1;
-                        let distance = (dx * dx + dy * dy).sqrt().max(1.0);
-                        let min_dist = p1.radius + p2.radius + min_spacing;
-                        
-                        if distance < min_dist {
-                            // 计算斥力（重叠越多，力越大）
-                            let overlap = min_dist - distance;
-                            let force_magnitude = overlap * 0.5;
-                            let fx = (dx / distance) * force_magnitude;
-                            let fy = (dy / distance) * force_magnitude;
-                            
-                            // p1 受到反方向的力
-                            if let Some(f) = forces.get_mut(id1) {
-                                f.0 -= fx;
-                                f.1 -= fy;
-                            }
-                            // p2 受到正方向的力
-                            if let Some(f) = forces.get_mut(id2) {
-                                f.0 += fx;
-                                f.1 += fy;
-                            }
-                        }
-                    }
-                }
-            }
-            
-            // 计算箭头对项目的斥力
-            let relations: Vec<(String, String)> = self.data.relations.iter()
-                .map(|r| (r.from_id.clone(), r.to_id.clone()))
-                .collect();
-            
-            for (from_id, to_id) in &relations {
-                if let (Some(from_proj), Some(to_proj)) = (
-                    self.data.projects.get(from_id),
-                    self.data.projects.get(to_id)
-                ) {
-                    let (x1, y1) = from_proj.position;
-                    let (x2, y2) = to_proj.position;
-                    
-                    // 对每个不是箭头端点的项目计算斥力
-                    for id in &project_ids {
-                        if id == from_id || id == to_id {
-                            continue;
-                        }
-                        
-                        if let Some(project) = self.data.projects.get(id) {
-                            let (px, py) = project.position;
-                            let threshold = project.radius + arrow_spacing;
-                            
-                            // 计算点到线段的最近点
-                            let line_len_sq = (x2 - x1).powi(2) + (y2 - y1).powi(2);
-                            if line_len_sq > 1.0 {
-                                let t = (((px - x1) * (x2 - x1) + (py - y1) * (y2 - y1)) / line_len_sq).clamp(0.0, 1.0);
-                                let nearest_x = x1 + t * (x2 - x1);
-                                let nearest_y = y1 + t * (y2 - y1);
-                                
-                                let dx = px - nearest_x;
-                                let dy = py - nearest_y;
-                                let dist = (dx * dx + dy * dy).sqrt().max(1.0);
-                                
-                                if dist < threshold {
-                                    let overlap = threshold - dist;
-                                    let force_magnitude = overlap * 0.3;
-                                    let fx = (dx / dist) * force_magnitude;
-                                    let fy = (dy / dist) * force_magnitude;
-                                    
-                                    if let Some(f) = forces.get_mut(id) {
-                                        f.0 += fx;
-                                        f.1 += fy;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            
-            // 应用力并检查边界约束
-            let mut any_moved = false;
-            for id in &project_ids {
-                if let Some((fx, fy)) = forces.get(id).cloned() {
-                    if fx.abs() > 0.1 || fy.abs() > 0.1 {
-                        if let Some(project) = self.data.projects.get(id).cloned() {
-                            let mut new_x = project.position.0 + fx * damping;
-                            let mut new_y = project.position.1 + fy * damping;
-                            
-                            // 边界约束：确保项目仍在正确区域内
-                            let mut temp_project = project.clone();
-                            temp_project.position = (new_x, new_y);
-                            
-                            if !self.is_project_in_correct_region(&temp_project) {
-                                // 如果移动后不在正确区域，尝试只移动一个方向
-                                temp_project.position = (new_x, project.position.1);
-                                if self.is_project_in_correct_region(&temp_project) {
-                                    new_y = project.position.1;
-                                } else {
-                                    temp_project.position = (project.position.0, new_y);
-                                    if self.is_project_in_correct_region(&temp_project) {
-                                        new_x = project.position.0;
-                                    } else {
-                                        // 两个方向都不行，不移动
-                                        continue;
-                                    }
-                                }
-                            }
-                            
-                            if let Some(p) = self.data.projects.get_mut(id) {
-                                p.position = (new_x, new_y);
-                                any_moved = true;
-                                changed = true;
-                            }
-                        }
-                    }
-                }
-            }
-            
-            if !any_moved {
-                break;  // 已达到平衡
-            }
-        }
-        
-        // 第三步：检查是否仍有重叠，如果有则扩展领域
-        let still_overlapping = self.check_any_project_overlap(min_spacing);
-        if still_overlapping {
-            self.expand_fields_for_projects();
-            changed = true;
-            // 扩展后重新验证位置
-            for id in &project_ids {
-                if let Some(project) = self.data.projects.get(id).cloned() {
-                    if !self.is_project_in_correct_region(&project) {
-                        let new_pos = self.find_nearest_valid_position(&project);
-                        if let Some(p) = self.data.projects.get_mut(id) {
-                            p.position = new_pos;
-                        }
-                    }
-                }
-            }
-        }
-        
-        if changed {
+    
+    /// 找到离当前位置最近的正确位置
+    fn find_nearest_valid_position(&self, index: &SpatialIndex, project: &Project) -> (f32, f32) {
+        layout::find_nearest_valid_position(&self.data, index, project)
+    }
+    
+    /// 验证并修正所有项目位置（使用物理引擎思想），有改动时记入历史
+    fn validate_and_fix_project_positions(&mut self) {
+        if layout::fix_positions(&mut self.data) {
             self.save_to_history();
         }
     }
-    
-    /// 检查是否有任何项目重叠
-    fn check_any_project_overlap(&self, min_spacing: f32) -> bool {
-        let project_ids: Vec<String> = self.data.projects.keys().cloned().collect();
-        for i in 0..project_ids.len() {
-            for j in (i + 1)..project_ids.len() {
-                if let (Some(p1), Some(p2)) = (
-                    self.data.projects.get(&project_ids[i]),
-                    self.data.projects.get(&project_ids[j])
-                ) {
-                    let dx = p1.position.0 - p2.position.0;
-                    let dy = p1.position.1 - p2.position.1;
-                    let distance = (dx * dx + dy * dy).sqrt();
-                    let min_dist = p1.radius + p2.radius + min_spacing;
-                    if distance < min_dist {
-                        return true;
-                    }
-                }
-            }
+
+    /// 在后台启动布局任务；任务期间画布显示中间结果，编辑操作暂停
+    /// original 为放弃时恢复的数据（一般为当前数据，领域重排序时为排序前的数据）
+    fn start_layout_task(&mut self, task: LayoutTask, original: AppData) {
+        if let Some(worker) = self.layout_worker.take() {
+            self.data = worker.original.clone();
         }
-        false
+        self.dragging_project = None;
+        self.drag_group.clear();
+        self.box_select_start = None;
+        self.layout_worker = Some(LayoutWorker::start(task, self.data.clone(), original));
     }
-    
-    /// 扩展领域大小以容纳更多项目
-    fn expand_fields_for_projects(&mut self) {
-        let expand_amount = 30.0;  // 每次扩展30像素
-        
-        // 统计每个领域内的项目数量和所需空间
-        let mut field_project_count: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
-        let mut field_required_area: std::collections::HashMap<String, f32> = std::collections::HashMap::new();
-        
-        for project in self.data.projects.values() {
-            for field_id in &project.field_ids {
-                *field_project_count.entry(field_id.clone()).or_insert(0) += 1;
-                let area = std::f32::consts::PI * (project.radius + 7.5).powi(2);  // 项目面积 + 间距
-                *field_required_area.entry(field_id.clone()).or_insert(0.0) += area;
-            }
+
+    /// 接收后台布局任务的进度，把中间结果的位置显示到画布上
+    fn poll_layout_task(&mut self, ctx: &Context) {
+        let Some(worker) = &mut self.layout_worker else {
+            return;
+        };
+        let frame = worker.poll();
+        if !worker.is_finished() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(30));
         }
-        
-        // 检查并扩展需要更多空间的领域
-        for (field_id, required_area) in &field_required_area {
-            if let Some(field) = self.data.fields.get_mut(field_id) {
-                let current_area = std::f32::consts::PI * field.radius.powi(2);
-                // 如果所需面积超过当前面积的40%（考虑交叉区域），则扩展
-                if *required_area > current_area * 0.4 {
-                    field.radius += expand_amount;
-                }
-            }
+        if let Some(frame) = frame {
+            layout::copy_geometry(&mut self.data, &frame);
+            self.refresh_editing_project();
         }
     }
-    
-    /// 检查项目是否在正确的区域内（不检查与其他项目的重叠）
-    fn is_project_in_correct_region(&self, project: &Project) -> bool {
-        let (x, y) = project.position;
-        let project_radius = project.radius;
-        
-        if project.field_ids.is_empty() {
-            // 无领域的项目必须在所有领域之外
-            for field in self.data.fields.values() {
-                let dx = x - field.position.0;
-                let dy = y - field.position.1;
-                let distance = (dx * dx + dy * dy).sqrt();
-                if distance - project_radius < field.radius {
-                    return false;
-                }
-            }
-        } else {
-            // 检查整个项目圆圈是否完全在所有目标领域内
-            for field_id in &project.field_ids {
-                if let Some(field) = self.data.fields.get(field_id) {
-                    let dx = x - field.position.0;
-                    let dy = y - field.position.1;
-                    let distance = (dx * dx + dy * dy).sqrt();
-                    if distance + project_radius > field.radius - 5.0 {
-                        return false;
-                    }
-                }
-            }
-            
-            // 检查整个项目圆圈是否完全在非目标领域外
-            for field in self.data.fields.values() {
-                if project.field_ids.contains(&field.id) {
-                    continue;
-                }
-                let dx = x - field.position.0;
-                let dy = y - field.position.1;
-                let distance = (dx * dx + dy * dy).sqrt();
-                if distance - project_radius < field.radius {
-                    return false;
-                }
+
+    /// 接受布局结果并记入历史
+    fn accept_layout_task(&mut self) {
+        let Some(worker) = self.layout_worker.take() else {
+            return;
+        };
+        if let Some((result, changed)) = &worker.result {
+            layout::copy_geometry(&mut self.data, result);
+            if *changed {
+                self.save_to_history();
             }
         }
-        
-        true
+        self.refresh_editing_project();
     }
 
-    /// 计算新项目的位置，根据所属领域自动布局，并避免与已有项目重叠
-    /// 单领域项目会放在远离其他领域的方向，避免被误认为在交叉区域
-    fn calculate_project_position(&self, project: &Project) -> (f32, f32) {
-        let project_radius = project.radius;
-        
-        // 如果项目没有指定领域，放在所有领域之外
-        if project.field_ids.is_empty() {
-            // 计算所有领域的边界
-            let mut max_x = 0.0_f32;
-            let mut max_y = 0.0_f32;
-            for field in self.data.fields.values() {
-                max_x = max_x.max(field.position.0 + field.radius);
-                max_y = max_y.max(field.position.1 + field.radius);
+    /// 取消或放弃布局任务，恢复到任务开始前的数据
+    fn cancel_layout_task(&mut self) {
+        if let Some(worker) = self.layout_worker.take() {
+            self.data = worker.original.clone();
+            self.refresh_editing_project();
+        }
+    }
+
+    /// 工具栏中的布局任务进度：计算中显示进度条和取消按钮，完成后显示接受和放弃按钮
+    fn layout_task_controls(&mut self, ui: &mut Ui) {
+        let Some(worker) = &self.layout_worker else {
+            return;
+        };
+        let theme = self.theme();
+        let task_name = worker.task.name();
+        if worker.is_finished() {
+            ui.label(RichText::new(format!("{}完成", task_name)).size(12.0).color(theme.success));
+            if ui.button("接受").on_hover_text("保留新布局并记入历史 (Enter)").clicked() {
+                self.accept_layout_task();
             }
-            
-            // 在右下角外侧找位置
-            let start_x = max_x + 50.0;
-            let start_y = max_y + 50.0;
-            
-            // 螺旋搜索找不重叠的位置
-            for attempt in 0..200 {
-                let angle = (attempt as f32) * 0.618 * std::f32::consts::PI * 2.0;
-                let radius = (attempt as f32).sqrt() * 10.0;
-                
-                let x = start_x + radius * angle.cos();
-                let y = start_y + radius * angle.sin();
-                
-                // 检查是否在所有领域之外
-                let mut outside_all = true;
-                for field in self.data.fields.values() {
-                    let dx = x - field.position.0;
-                    let dy = y - field.position.1;
-                    let distance = (dx * dx + dy * dy).sqrt();
-                    if distance - project_radius < field.radius {
-                        outside_all = false;
-                        break;
-                    }
-                }
-                if !outside_all {
-                    continue;
-                }
-                
-                // 检查与其他项目的距离
-                let mut overlaps = false;
-                for existing in self.data.projects.values() {
-                    if existing.id == project.id {
-                        continue;
-                    }
-                    let dx = x - existing.position.0;
-                    let dy = y - existing.position.1;
-                    let dist = (dx * dx + dy * dy).sqrt();
-                    if dist < project_radius + existing.radius + 15.0 {
-                        overlaps = true;
-                        break;
-                    }
-                }
-                if !overlaps {
-                    return (x, y);
-                }
+            if ui.button("放弃").on_hover_text("恢复布局前的位置 (Esc)").clicked() {
+                self.cancel_layout_task();
             }
-            
-            return (start_x, start_y);
-        }
-
-        // 计算所属领域的中心位置和半径
-        let mut center_x = 0.0;
-        let mut center_y = 0.0;
-        let mut min_radius = f32::MAX;
-        let mut field_count = 0;
-        let mut target_fields: Vec<&ResearchField> = Vec::new();
-
-        for field_id in &project.field_ids {
-            if let Some(field) = self.data.fields.get(field_id) {
-                center_x += field.position.0;
-                center_y += field.position.1;
-                min_radius = min_radius.min(field.radius);
-                field_count += 1;
-                target_fields.push(field);
+        } else {
+            ui.label(RichText::new(format!("{}中", task_name)).size(12.0).color(theme.text_secondary));
+            ui.add(ProgressBar::new(worker.progress).desired_width(120.0).show_percentage());
+            if ui.button("取消").on_hover_text("停止计算并恢复布局前的位置 (Esc)").clicked() {
+                self.cancel_layout_task();
             }
         }
+        ui.separator();
+    }
 
-        if field_count == 0 {
-            return (400.0, 400.0);
-        }
-
-        // 计算多个领域的平均中心位置
-        center_x /= field_count as f32;
-        center_y /= field_count as f32;
-
-        let project_radius = project.radius;
+    /// 布局诊断窗口：质量分、各类问题数量和问题列表，点击问题选中相关项目并在维恩图中定位
+    fn layout_diagnostics_window(&mut self, ctx: &Context) {
+        let theme = self.theme();
+        let mut open = true;
+        let Some(report) = self.layout_report.clone() else {
+            return;
+        };
+        let mut clicked: Option<LayoutIssue> = None;
 
-        // 单领域：计算远离其他领域的方向
-        if field_count == 1 {
-            let target_field = target_fields[0];
-            
-            // 收集其他领域的位置
-            let other_fields: Vec<&ResearchField> = self.data.fields.values()
-                .filter(|f| f.id != target_field.id)
-                .collect();
-            
-            // 计算远离其他领域的方向
-            let (preferred_angle, use_opposite) = if !other_fields.is_empty() {
-                // 计算从目标领域中心指向其他领域中心的平均方向
-                let mut avg_dx = 0.0;
-                let mut avg_dy = 0.0;
-                for other in &other_fields {
-                    avg_dx += other.position.0 - target_field.position.0;
-                    avg_dy += other.position.1 - target_field.position.1;
-                }
-                avg_dx /= other_fields.len() as f32;
-                avg_dy /= other_fields.len() as f32;
-                
-                // 使用相反方向（远离其他领域）
-                let opposite_angle = (-avg_dy).atan2(-avg_dx);
-                (opposite_angle, true)
-            } else {
-                (0.0, false)
-            };
-            
-            // 安全边距
-            let safe_margin = project_radius + 15.0;
-            let max_safe_radius = target_field.radius - safe_margin;
-            
-            // 收集该领域内已有项目的位置（用于找最空白的位置）
-            let existing_projects: Vec<(f32, f32, f32)> = self.data.projects.values()
-                .filter(|p| p.id != project.id && p.field_ids.contains(&target_field.id))
-                .map(|p| (p.position.0, p.position.1, p.radius))
-                .collect();
-            
-            // 生成候选位置并评分（离已有项目越远越好）
-            let golden_angle = 2.399963229728653;  // 黄金角 ≈ 137.5°
-            let mut best_pos: Option<(f32, f32)> = None;
-            let mut best_min_dist = 0.0_f32;
-            
-            // 遍历多个候选位置
-            let max_attempts = 200;
-            for attempt in 0..max_attempts {
-                // 黄金角螺旋覆盖整个领域
-                let angle = (attempt as f32) * golden_angle;
-                
-                // 半径从边缘向内，覆盖整个领域
-                let radius_factor = 0.8 - (attempt as f32 / max_attempts as f32) * 0.6;
-                let radius = (max_safe_radius * radius_factor).max(project_radius + 10.0);
-                
-                let x = target_field.position.0 + radius * angle.cos();
-                let y = target_field.position.1 + radius * angle.sin();
-                
-                // 检查是否在领域内
-                let dx = x - target_field.position.0;
-                let dy = y - target_field.position.1;
-                let distance_from_center = (dx * dx + dy * dy).sqrt();
-                if distance_from_center > max_safe_radius {
-                    continue;
-                }
-                
-                // 检查整个项目圆圈是否与其他领域交叉（项目圆圈不能进入非目标领域）
-                let mut in_other_field = false;
-                for other in &other_fields {
-                    let odx = x - other.position.0;
-                    let ody = y - other.position.1;
-                    let dist_to_other = (odx * odx + ody * ody).sqrt();
-                    // 项目圆圈最近点到领域中心的距离 = dist_to_other - project_radius
-                    // 必须 >= other.radius（项目圆圈完全在非目标领域外）
-                    if dist_to_other - project_radius < other.radius {
-                        in_other_field = true;
-                        break;
-                    }
-                }
-                if in_other_field {
-                    continue;
-                }
-                
-                // 检查是否与已有项目重叠（保持间距）
-                let mut has_overlap = false;
-                let mut min_dist_to_existing = f32::MAX;
-                for existing_project in self.data.projects.values() {
-                    if existing_project.id == project.id {
-                        continue;
-                    }
-                    let edx = x - existing_project.position.0;
-                    let edy = y - existing_project.position.1;
-                    let distance = (edx * edx + edy * edy).sqrt();
-                    // 两个圆圈不能重叠：距离必须 > 两个半径之和 + 间距
-                    let min_dist = project_radius + existing_project.radius + 15.0;  // 保持15像素间距
-                    if distance < min_dist {
-                        has_overlap = true;
-                        break;
+        egui::Window::new("布局诊断")
+            .default_width(360.0)
+            .default_height(320.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let score_color = if report.score >= 90.0 {
+                    theme.success
+                } else if report.score >= 60.0 {
+                    theme.warning
+                } else {
+                    theme.error
+                };
+                ui.horizontal(|ui| {
+                    ui.label("布局质量:");
+                    ui.label(RichText::new(format!("{:.0} / 100", report.score)).strong().color(score_color));
+                });
+                ui.horizontal_wrapped(|ui| {
+                    for kind in ["区域错误", "项目重叠", "压住箭头", "名称重叠"] {
+                        ui.label(
+                            RichText::new(format!("{} {}", kind, report.count(kind)))
+                                .size(11.0)
+                                .color(theme.text_secondary)
+                        );
                     }
-                    // 记录到最近项目的距离
-                    min_dist_to_existing = min_dist_to_existing.min(distance);
-                }
-                if has_overlap {
-                    continue;
+                });
+                ui.label(
+                    RichText::new("区域错误和重叠各扣 1 分权重，压住箭头和名称重叠各扣 0.5，按项目和领域总数折算")
+                        .size(10.0)
+                        .color(theme.text_disabled)
+                );
+                ui.separator();
+
+                if report.issues.is_empty() {
+                    ui.label(RichText::new("没有发现布局问题").color(theme.success));
+                    return;
                 }
-                
-                // 检查是否被箭头穿过
-                let mut crossed_by_arrow = false;
-                for relation in &self.data.relations {
-                    // 跳过与当前项目相关的箭头
-                    if relation.from_id == project.id || relation.to_id == project.id {
-                        continue;
-                    }
-                    if let (Some(from), Some(to)) = (
-                        self.data.projects.get(&relation.from_id),
-                        self.data.projects.get(&relation.to_id),
-                    ) {
-                        if self.point_near_line_segment(
-                            x, y,
-                            from.position.0, from.position.1,
-                            to.position.0, to.position.1,
-                            project_radius + 25.0,  // 箭头避让距离：项目半径 + 25像素间距
-                        ) {
-                            crossed_by_arrow = true;
-                            break;
+                let row_height = ui.text_style_height(&egui::TextStyle::Body) + ui.spacing().item_spacing.y;
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show_rows(ui, row_height, report.issues.len(), |ui, range| {
+                        for issue in &report.issues[range] {
+                            let kind_color = match issue {
+                                LayoutIssue::WrongRegion(_) | LayoutIssue::Overlap(_, _) => theme.error,
+                                LayoutIssue::OnArrow(_, _) | LayoutIssue::LabelOverlap(_, _) => theme.warning,
+                            };
+                            ui.horizontal(|ui| {
+                                ui.label(RichText::new(issue.kind_name()).size(11.0).color(kind_color));
+                                let selected = !issue.project_ids().is_empty()
+                                    && issue.project_ids().iter().all(|id| self.selected_project_ids.contains(id));
+                                if ui.selectable_label(selected, issue.describe(&self.data))
+                                    .on_hover_text("选中并定位")
+                                    .clicked()
+                                {
+                                    clicked = Some(issue.clone());
+                                }
+                            });
                         }
-                    }
-                }
-                if crossed_by_arrow {
-                    continue;
-                }
-                
-                // 这是一个有效位置，记录离已有项目最远的位置
-                if min_dist_to_existing > best_min_dist {
-                    best_min_dist = min_dist_to_existing;
-                    best_pos = Some((x, y));
-                }
-            }
-            
-            // 返回最空白的位置
-            if let Some(pos) = best_pos {
-                return pos;
-            }
-            
-            // 回退：扩大搜索范围，放宽重叠和箭头限制，但仍严格检查领域边界
-            for attempt in 0..200 {
-                let angle = (attempt as f32) * 0.618 * std::f32::consts::PI * 2.0;
-                let radius = (attempt as f32).sqrt() * 10.0;
-                
-                let x = target_field.position.0 + radius * angle.cos();
-                let y = target_field.position.1 + radius * angle.sin();
-                
-                // 检查是否在目标领域内
-                let dx = x - target_field.position.0;
-                let dy = y - target_field.position.1;
-                let distance_from_center = (dx * dx + dy * dy).sqrt();
-                if distance_from_center + project_radius > target_field.radius - 5.0 {
-                    continue;
-                }
-                
-                // 严格检查：整个项目圆圈不能进入非目标领域
-                let mut in_other_field = false;
-                for other in &other_fields {
-                    let odx = x - other.position.0;
-                    let ody = y - other.position.1;
-                    let dist_to_other = (odx * odx + ody * ody).sqrt();
-                    if dist_to_other - project_radius < other.radius {
-                        in_other_field = true;
-                        break;
-                    }
-                }
-                if in_other_field {
-                    continue;
-                }
-                
-                return (x, y);
-            }
-            
-            // 最终回退：领域中心（不理想但至少在目标领域内）
-            return (target_field.position.0, target_field.position.1);
+                    });
+            });
+
+        if let Some(issue) = clicked {
+            self.focus_layout_issue(&issue);
         }
-        
-        // 多领域：找到真正的交集区域中心，同时远离非目标领域
-        // 收集非目标领域
-        let non_target_fields: Vec<&ResearchField> = self.data.fields.values()
-            .filter(|f| !project.field_ids.contains(&f.id))
-            .collect();
-        
-        // 计算远离非目标领域的方向
-        let mut away_dir_x = 0.0;
-        let mut away_dir_y = 0.0;
-        for non_target in &non_target_fields {
-            // 从非目标领域中心指向目标领域平均中心的方向
-            away_dir_x += center_x - non_target.position.0;
-            away_dir_y += center_y - non_target.position.1;
-        }
-        let away_len = (away_dir_x * away_dir_x + away_dir_y * away_dir_y).sqrt();
-        if away_len > 0.0 {
-            away_dir_x /= away_len;
-            away_dir_y /= away_len;
+        if !open {
+            self.show_layout_diagnostics = false;
+            self.layout_report = None;
         }
-        
-        // 使用迭代方法找到所有领域的交集中心，同时尽量远离非目标领域
-        let mut intersection_center_x = center_x;
-        let mut intersection_center_y = center_y;
-        
-        // 迭代优化：将中心点移向所有目标领域都能覆盖且远离非目标领域的位置
-        for _ in 0..100 {
-            let mut move_x = 0.0;
-            let mut move_y = 0.0;
-            let mut need_move = false;
-            
-            // 确保在所有目标领域内
-            for field in &target_fields {
-                let dx = intersection_center_x - field.position.0;
-                let dy = intersection_center_y - field.position.1;
-                let dist = (dx * dx + dy * dy).sqrt();
-                let max_dist = field.radius - project_radius - 15.0;
-                
-                if dist > max_dist && dist > 0.0 {
-                    let ratio = (dist - max_dist) / dist;
-                    move_x -= dx * ratio * 0.5;
-                    move_y -= dy * ratio * 0.5;
-                    need_move = true;
-                }
-            }
-            
-            // 远离非目标领域
-            for non_target in &non_target_fields {
-                let dx = intersection_center_x - non_target.position.0;
-                let dy = intersection_center_y - non_target.position.1;
-                let dist = (dx * dx + dy * dy).sqrt();
-                // 如果太靠近非目标领域，向外推
-                if dist < non_target.radius + project_radius + 20.0 && dist > 0.0 {
-                    let push_strength = (non_target.radius + project_radius + 20.0 - dist) / dist;
-                    move_x += dx * push_strength * 0.3;
-                    move_y += dy * push_strength * 0.3;
-                    need_move = true;
-                }
-            }
-            
-            if !need_move {
-                break;
-            }
-            
-            intersection_center_x += move_x;
-            intersection_center_y += move_y;
+    }
+
+    /// 定位到布局问题：切换到维恩图并把问题所在处移到视图中心，同时选中涉及的项目
+    /// （布局任务运行期间只定位，不改变选择）
+    fn focus_layout_issue(&mut self, issue: &LayoutIssue) {
+        self.show_visualization = true;
+        self.central_view = CentralView::Venn;
+        self.focus_request = issue.focus(&self.data);
+        let project_ids = issue.project_ids();
+        if self.layout_worker.is_some() || project_ids.is_empty() {
+            return;
         }
-        
-        // 从交集中心开始搜索
-        let base_radius = 0.0;  // 从中心开始
-        
-        let max_attempts = 100;
-        for attempt in 0..max_attempts {
-            let angle = (attempt as f32) * 0.618 * std::f32::consts::PI * 2.0;  // 黄金角
-            let radius = base_radius + (attempt as f32).sqrt() * 8.0;  // 螺旋扩展
-
-            let x = intersection_center_x + radius * angle.cos();
-            let y = intersection_center_y + radius * angle.sin();
-
-            // 检查整个项目圆圈是否完全在所有目标领域内
-            let mut in_all_fields = true;
-            for field in &target_fields {
-                let dx = x - field.position.0;
-                let dy = y - field.position.1;
-                let distance = (dx * dx + dy * dy).sqrt();
-                // 项目圆圈最远点必须在领域内（留10像素边距）
-                if distance + project_radius > field.radius - 10.0 {
-                    in_all_fields = false;
-                    break;
-                }
-            }
-            if !in_all_fields {
-                continue;
-            }
-            
-            // 检查整个项目圆圈是否完全在非目标领域外
-            let mut in_non_target_field = false;
-            for field in self.data.fields.values() {
-                // 跳过目标领域
-                if project.field_ids.contains(&field.id) {
-                    continue;
-                }
-                let dx = x - field.position.0;
-                let dy = y - field.position.1;
-                let distance = (dx * dx + dy * dy).sqrt();
-                // 整个项目圆圈必须在非目标领域外
-                // 项目圆圈最近点到领域中心距离 = distance - project_radius
-                if distance - project_radius < field.radius {
-                    in_non_target_field = true;
-                    break;
-                }
-            }
-            if in_non_target_field {
-                continue;
-            }
+        self.selected_project = project_ids.first().cloned();
+        self.selected_project_ids = project_ids;
+        self.editing_project = self.selected_project.as_ref()
+            .and_then(|id| self.data.projects.get(id))
+            .cloned();
+    }
 
-            // 检查是否与已有项目重叠（保持间距）
-            let mut has_overlap = false;
-            for existing_project in self.data.projects.values() {
-                if existing_project.id == project.id {
-                    continue;
-                }
-                let dx = x - existing_project.position.0;
-                let dy = y - existing_project.position.1;
-                let distance = (dx * dx + dy * dy).sqrt();
-                // 两个圆圈不能重叠：距离必须 > 两个半径之和 + 间距
-                let min_dist = project_radius + existing_project.radius + 15.0;  // 保持15像素间距
-                if distance < min_dist {
-                    has_overlap = true;
-                    break;
-                }
+    /// 在维恩图上标记布局问题：区域错误和重叠为红色，压住箭头和名称重叠为橙色
+    /// 只标记显示中的项目和领域；origin 和 scale 为数据坐标到屏幕坐标的变换
+    fn paint_layout_issues(
+        &self,
+        painter: &egui::Painter,
+        report: &LayoutReport,
+        visible_project_ids: &[String],
+        visible_field_ids: &[String],
+        origin: Vec2,
+        scale: f32,
+    ) {
+        let theme = self.theme();
+        let to_screen = |(x, y): (f32, f32)| pos2(origin.x + x * scale, origin.y + y * scale);
+        let visible = |id: &String| visible_project_ids.contains(id);
+        let ring = |id: &str, color: Color32| {
+            if let Some(project) = self.data.projects.get(id) {
+                painter.circle_stroke(to_screen(project.position), project.radius * scale + 6.0, Stroke::new(2.0, color));
             }
-            if has_overlap {
+        };
+
+        for issue in &report.issues {
+            if !issue.project_ids().iter().all(visible) {
                 continue;
             }
-            
-            // 检查是否被箭头穿过
-            let mut crossed_by_arrow = false;
-            for relation in &self.data.relations {
-                // 跳过与当前项目相关的箭头
-                if relation.from_id == project.id || relation.to_id == project.id {
-                    continue;
+            match issue {
+                LayoutIssue::WrongRegion(id) => ring(id, theme.error),
+                LayoutIssue::Overlap(a, b) => {
+                    ring(a, theme.error);
+                    ring(b, theme.error);
+                    if let (Some(a), Some(b)) = (self.data.projects.get(a), self.data.projects.get(b)) {
+                        painter.line_segment([to_screen(a.position), to_screen(b.position)], Stroke::new(2.0, theme.error));
+                    }
                 }
-                if let (Some(from), Some(to)) = (
-                    self.data.projects.get(&relation.from_id),
-                    self.data.projects.get(&relation.to_id),
-                ) {
-                    if self.point_near_line_segment(
-                        x, y,
-                        from.position.0, from.position.1,
-                        to.position.0, to.position.1,
-                        project_radius + 25.0,  // 箭头避让距离：项目半径 + 25像素间距
-                    ) {
-                        crossed_by_arrow = true;
-                        break;
+                LayoutIssue::OnArrow(id, relation_index) => {
+                    ring(id, theme.warning);
+                    let relation = self.data.relations.get(*relation_index);
+                    let ends = relation.and_then(|r| Some((self.data.projects.get(&r.from_id)?, self.data.projects.get(&r.to_id)?)));
+                    if let Some((from, to)) = ends.filter(|(from, to)| visible(&from.id) && visible(&to.id)) {
+                        painter.line_segment(
+                            [to_screen(from.position), to_screen(to.position)],
+                            Stroke::new(4.0, theme.warning.gamma_multiply(0.5)),
+                        );
+                    }
+                }
+                LayoutIssue::LabelOverlap(field_id, obstacle) => {
+                    let Some(field) = self.data.fields.get(field_id).filter(|f| visible_field_ids.contains(&f.id)) else {
+                        continue;
+                    };
+                    // 名称在屏幕上的位置和尺寸与画布绘制一致：距圆心 半径 + 25 像素，字号固定
+                    let angle = layout::field_label_angle(
+                        field,
+                        self.data.fields.values().filter(|f| visible_field_ids.contains(&f.id)),
+                    );
+                    let distance = field.radius * scale + 25.0;
+                    let center = to_screen(field.position) + vec2(angle.cos(), angle.sin()) * distance;
+                    let size = vec2(field.name.chars().count() as f32 * 10.0, 20.0);
+                    painter.rect_stroke(Rect::from_center_size(center, size), 2.0, Stroke::new(1.5, theme.warning));
+                    if let LabelObstacle::Field(other_id) = obstacle {
+                        if let Some(other) = self.data.fields.get(other_id).filter(|f| visible_field_ids.contains(&f.id)) {
+                            painter.circle_stroke(to_screen(other.position), other.radius * scale, Stroke::new(1.5, theme.warning));
+                        }
                     }
                 }
             }
-            if crossed_by_arrow {
-                continue;
+            if let LayoutIssue::LabelOverlap(_, LabelObstacle::Project(id)) = issue {
+                ring(id, theme.warning);
             }
-
-            return (x, y);
         }
+    }
 
-        // 最后的回退：返回交集中心位置
-        (intersection_center_x, intersection_center_y)
+    /// 计算新项目的位置，根据所属领域自动布局，并避免与已有项目重叠
+    fn calculate_project_position(&self, index: &SpatialIndex, project: &Project) -> (f32, f32) {
+        layout::calculate_project_position(&self.data, index, project)
     }
 
-    /// 根据完成度百分比计算边界颜色
-    /// 0% = 红色, 50% = 黄色, 100% = 绿色
-    fn completion_percentage_to_color(&self, percentage: f32) -> Color32 {
-        let p = percentage.clamp(0.0, 100.0) / 100.0;
-        
-        if p <= 0.5 {
-            // 0% - 50%: 红色到黄色
-            let ratio = p * 2.0; // 0.0 到 1.0
-            let r = 255.0;
-            let g = (255.0 * ratio) as u8;
-            let b = 0;
-            Color32::from_rgb(r as u8, g, b)
-        } else {
-            // 50% - 100%: 黄色到绿色
-            let ratio = (p - 0.5) * 2.0; // 0.0 到 1.0
-            let r = (255.0 * (1.0 - ratio)) as u8;
-            let g = 255;
-            let b = 0;
-            Color32::from_rgb(r, g, b)
-        }
+    /// 图例中显示的状态（序号, 状态），隐藏的状态不列出
+    fn legend_statuses(&self) -> Vec<(usize, &ProjectStatus)> {
+        self.data.sorted_statuses()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, s)| !self.hidden_status_ids.contains(&s.id))
+            .collect()
     }
 
-    /// 计算图例的矩形区域
-    fn get_legend_rect(&self, rect: Rect) -> Rect {
+    /// 计算图例的矩形区域（visible_relations 决定关系类型部分的行数）
+    fn get_legend_rect(&self, rect: Rect, visible_field_ids: &[String], visible_relations: &[usize]) -> Rect {
         // 图例尺寸参数
         let padding = 12.0;
         let legend_width = 140.0;
@@ -3908,20 +5637,36 @@ impl VennCVApp {
         let section_title_height = 16.0;
         let section_spacing = 8.0;
         
-        // 项目状态部分：5个状态 + 1个标题
-        let status_count = 5;
+        // 研究领域部分：显示的领域 + 1个标题（没有领域时不显示）
+        let field_count = visible_field_ids.len();
+        let fields_height = if field_count == 0 {
+            0.0
+        } else {
+            section_title_height + (field_count as f32) * (item_height + spacing) + section_spacing
+        };
+        // 项目状态部分：显示的状态 + 1个标题
+        let status_count = self.legend_statuses().len();
         // 完成度部分：3个示例 + 1个标题
         let completion_count = 3;
+        // 关系类型部分：显示的关系用到的类型 + 1个标题（没有关系时不显示）
+        let relation_type_count = self.data.relation_types_used(visible_relations).len();
+        let relation_types_height = if relation_type_count == 0 {
+            0.0
+        } else {
+            section_spacing + section_title_height + (relation_type_count as f32) * (item_height + spacing)
+        };
         
         // 精确计算高度
         let legend_height = padding  // 顶部内边距
             + title_height  // 标题
             + spacing  // 标题后间距
+            + fields_height  // 研究领域
             + section_title_height  // "项目状态:"
             + (status_count as f32) * (item_height + spacing)  // 状态项
             + section_spacing  // 分组间距
             + section_title_height  // "完成度:"
             + (completion_count as f32) * (item_height + spacing)  // 完成度项
+            + relation_types_height  // 关系类型
             + padding;  // 底部内边距
         
         // 默认位置：右上角
@@ -3942,16 +5687,14 @@ impl VennCVApp {
         )
     }
     
-    /// 绘制图例，显示项目状态和完成度的颜色含义
-    fn draw_legend(&self, painter: &egui::Painter, rect: Rect, _theme: &Theme) {
-        use crate::models::ProjectStatus;
-        
+    /// 绘制图例，显示领域配色、项目状态和完成度的颜色含义，以及显示的关系用到的关系类型
+    fn draw_legend(&self, painter: &egui::Painter, rect: Rect, theme: &Theme, visible_field_ids: &[String], visible_relations: &[usize]) {
         if !self.settings.show_legend {
             return;
         }
         
         // 获取图例矩形
-        let legend_rect = self.get_legend_rect(rect);
+        let legend_rect = self.get_legend_rect(rect, visible_field_ids, visible_relations);
         let legend_x = legend_rect.left();
         let legend_y = legend_rect.top();
         let legend_width = legend_rect.width();
@@ -3965,9 +5708,8 @@ impl VennCVApp {
         let section_spacing = 8.0;
         
         // 绘制半透明背景
-        let bg_color = Color32::from_rgba_unmultiplied(255, 255, 255, 240);
-        painter.rect_filled(legend_rect, 4.0, bg_color);
-        painter.rect_stroke(legend_rect, 4.0, (1.0, Color32::from_rgb(180, 180, 180)));
+        painter.rect_filled(legend_rect, 4.0, theme.legend_background);
+        painter.rect_stroke(legend_rect, 4.0, (1.0, theme.legend_border));
         
         // 绘制标题
         let mut current_y = legend_y + padding + title_height / 2.0;
@@ -3976,10 +5718,47 @@ impl VennCVApp {
             Align2::CENTER_CENTER,
             "图例",
             FontId::proportional(12.0),
-            Color32::BLACK,
+            theme.canvas_text,
         );
         current_y += title_height / 2.0 + spacing;
         
+        // 绘制研究领域图例（边框和填充颜色，子领域缩进）
+        let legend_fields: Vec<(&ResearchField, usize)> = self.data.field_tree().into_iter()
+            .filter(|(f, _)| visible_field_ids.contains(&f.id))
+            .collect();
+        if !legend_fields.is_empty() {
+            current_y += section_title_height / 2.0;
+            painter.text(
+                pos2(legend_x + padding, current_y),
+                Align2::LEFT_CENTER,
+                "研究领域:",
+                FontId::proportional(10.0),
+                theme.canvas_text_secondary,
+            );
+            current_y += section_title_height / 2.0;
+            
+            for (field, depth) in legend_fields {
+                current_y += item_height / 2.0;
+                
+                let circle_radius = 5.0;
+                let circle_x = legend_x + padding + circle_radius + depth.min(3) as f32 * 8.0;
+                let (stroke_color, fill_color) = field_colors(field);
+                painter.circle_filled(pos2(circle_x, current_y), circle_radius, fill_color);
+                painter.circle_stroke(pos2(circle_x, current_y), circle_radius, (1.5, stroke_color));
+                
+                painter.text(
+                    pos2(circle_x + circle_radius + 6.0, current_y),
+                    Align2::LEFT_CENTER,
+                    &field.name,
+                    FontId::proportional(9.0),
+                    theme.canvas_text,
+                );
+                
+                current_y += item_height / 2.0 + spacing;
+            }
+            current_y += section_spacing;
+        }
+        
         // 绘制项目状态图例
         current_y += section_title_height / 2.0;
         painter.text(
@@ -3987,40 +5766,37 @@ impl VennCVApp {
             Align2::LEFT_CENTER,
             "项目状态:",
             FontId::proportional(10.0),
-            Color32::DARK_GRAY,
+            theme.canvas_text_secondary,
         );
         current_y += section_title_height / 2.0;
         
-        let statuses = [
-            ProjectStatus::Published,
-            ProjectStatus::Submitted,
-            ProjectStatus::HighPriority,
-            ProjectStatus::SteadyProgress,
-            ProjectStatus::ToBeStarted,
-        ];
-        
-        for status in &statuses {
-            let color = status.color();
-            let name = status.name();
+        for (index, status) in self.legend_statuses() {
+            let color = theme.status_color(status, index);
+            let name = status.name.as_str();
             
             current_y += item_height / 2.0;
             
-            // 绘制颜色圆圈
-            let circle_radius = 5.0;
+            // 绘制颜色圆圈（启用图案/符号时放大以便看清）
+            let circle_radius = if self.settings.status_marker == StatusMarker::None { 5.0 } else { 7.0 };
             let circle_x = legend_x + padding + circle_radius;
             painter.circle_filled(
                 pos2(circle_x, current_y),
                 circle_radius,
                 color,
             );
-            // 如果是白色，添加边框以便看清
-            if color == Color32::from_rgb(255, 255, 255) {
+            // 如果是浅色，添加边框以便看清
+            if contrast_text_color(color) == Color32::BLACK {
                 painter.circle_stroke(
                     pos2(circle_x, current_y),
                     circle_radius,
                     (1.0, Color32::GRAY),
                 );
             }
+            match self.settings.status_marker {
+                StatusMarker::None => {}
+                StatusMarker::Pattern => paint_status_pattern(painter, pos2(circle_x, current_y), circle_radius, index, color),
+                StatusMarker::Glyph => paint_status_glyph(painter, pos2(circle_x, current_y), circle_radius, index, theme.canvas_text, theme.legend_background),
+            }
             
             // 绘制文本
             painter.text(
@@ -4028,7 +5804,7 @@ impl VennCVApp {
                 Align2::LEFT_CENTER,
                 name,
                 FontId::proportional(9.0),
-                Color32::BLACK,
+                theme.canvas_text,
             );
             
             current_y += item_height / 2.0 + spacing;
@@ -4042,13 +5818,13 @@ impl VennCVApp {
             Align2::LEFT_CENTER,
             "完成度:",
             FontId::proportional(10.0),
-            Color32::DARK_GRAY,
+            theme.canvas_text_secondary,
         );
         current_y += section_title_height / 2.0;
         
         let completion_examples = [0.0, 50.0, 100.0];
         for percentage in &completion_examples {
-            let color = self.completion_percentage_to_color(*percentage);
+            let color = theme.completion_percentage_to_color(*percentage);
             let label = format!("{}%", *percentage as i32);
             
             current_y += item_height / 2.0;
@@ -4068,6 +5844,9 @@ impl VennCVApp {
                 circle_radius,
                 (2.0, color),
             );
+            if self.settings.show_completion_ring {
+                paint_completion_ring(painter, pos2(circle_x, current_y), circle_radius + 3.0, *percentage, theme.canvas_text);
+            }
             
             // 绘制文本
             painter.text(
@@ -4075,130 +5854,50 @@ impl VennCVApp {
                 Align2::LEFT_CENTER,
                 &label,
                 FontId::proportional(9.0),
-                Color32::BLACK,
+                theme.canvas_text,
             );
             
             current_y += item_height / 2.0 + spacing;
         }
-    }
-
-    /// 根据项目分布动态调整三个研究领域圆圈的位置和大小
-    /// 确保三个圆圈有合适的交集区域
-    fn adjust_field_layout(&mut self) {
-        // 统计每个领域和交集区域的项目数量
-        let field_ids: Vec<String> = self.data.fields.keys().cloned().collect();
-        if field_ids.len() != 3 {
-            return; // 只处理3个领域的情况
-        }
-
-        let field1_id = &field_ids[0];
-        let field2_id = &field_ids[1];
-        let field3_id = &field_ids[2];
-
-        // 统计各区域的项目数量
-        let mut count_field1_only = 0;
-        let mut count_field2_only = 0;
-        let mut count_field3_only = 0;
-        let mut count_field12 = 0; // field1 和 field2 的交集
-        let mut count_field13 = 0; // field1 和 field3 的交集
-        let mut count_field23 = 0; // field2 和 field3 的交集
-        let mut count_all = 0; // 三个领域的交集
-
-        for project in self.data.projects.values() {
-            let has1 = project.field_ids.contains(field1_id);
-            let has2 = project.field_ids.contains(field2_id);
-            let has3 = project.field_ids.contains(field3_id);
-
-            match (has1, has2, has3) {
-                (true, false, false) => count_field1_only += 1,
-                (false, true, false) => count_field2_only += 1,
-                (false, false, true) => count_field3_only += 1,
-                (true, true, false) => count_field12 += 1,
-                (true, false, true) => count_field13 += 1,
-                (false, true, true) => count_field23 += 1,
-                (true, true, true) => count_all += 1,
-                _ => {}
-            }
-        }
-
-        // 计算总项目数
-        let total_projects = self.data.projects.len() as f32;
-        if total_projects == 0.0 {
-            return; // 没有项目，使用默认布局
-        }
-
-        // 计算交集区域需要的空间（基于项目数量）
-        // 三个领域交集的项目数量影响交集区域大小
-        let intersection_weight = (count_all as f32 / total_projects.max(1.0)).min(1.0);
-        
-        // 计算每个领域的项目密度
-        let density1 = (count_field1_only + count_field12 + count_field13 + count_all) as f32 / total_projects;
-        let density2 = (count_field2_only + count_field12 + count_field23 + count_all) as f32 / total_projects;
-        let density3 = (count_field3_only + count_field13 + count_field23 + count_all) as f32 / total_projects;
-
-        // 基础半径（根据项目密度调整，但确保最小值）
-        let base_radius: f32 = 180.0;
-        let radius1 = base_radius.max(150.0) + density1 * 80.0;
-        let radius2 = base_radius.max(150.0) + density2 * 80.0;
-        let radius3 = base_radius.max(150.0) + density3 * 80.0;
-
-        // 计算三个圆圈的中心位置，确保有交集
-        // 使用等边三角形布局，中心在 (400, 400)
-        let center_x = 400.0;
-        let center_y = 400.0;
-        
-        // 根据交集项目数量调整三角形大小
-        // 如果有三个领域的交集项目，需要缩小三角形使圆圈更靠近
-        let base_triangle_size = 120.0;
-        // 交集项目越多，三角形越小（圆圈越靠近）
-        let triangle_size = base_triangle_size * (1.0 - intersection_weight * 0.4);
-        
-        // 确保三角形大小使得三个圆圈有交集
-        // 等边三角形的边长 = triangle_size * 2 * sin(60度) ≈ triangle_size * 1.732
-        let triangle_side = triangle_size * 1.732;
-        let min_radius_sum = (radius1 + radius2).min(radius2 + radius3).min(radius1 + radius3);
-        
-        // 如果三角形太大，调整它以确保有交集
-        let final_triangle_size = if triangle_side > min_radius_sum * 0.8 {
-            let adjusted_size = min_radius_sum * 0.46; // 0.8 / 1.732 ≈ 0.46
-            adjusted_size.max(80.0) // 最小80像素
-        } else {
-            triangle_size
-        };
-        
-        // 第一个圆圈（顶部）
-        let angle1 = -std::f32::consts::PI / 2.0; // -90度
-        let pos1_x = center_x + final_triangle_size * angle1.cos();
-        let pos1_y = center_y + final_triangle_size * angle1.sin();
         
-        // 第二个圆圈（左下）
-        let angle2 = -std::f32::consts::PI / 2.0 + 2.0 * std::f32::consts::PI / 3.0; // -90 + 120度
-        let pos2_x = center_x + final_triangle_size * angle2.cos();
-        let pos2_y = center_y + final_triangle_size * angle2.sin();
-        
-        // 第三个圆圈（右下）
-        let angle3 = -std::f32::consts::PI / 2.0 + 4.0 * std::f32::consts::PI / 3.0; // -90 + 240度
-        let pos3_x = center_x + final_triangle_size * angle3.cos();
-        let pos3_y = center_y + final_triangle_size * angle3.sin();
-
-        // 更新领域位置和半径
-        if let Some(field1) = self.data.fields.get_mut(field1_id) {
-            field1.position = (pos1_x, pos1_y);
-            field1.radius = radius1;
-        }
-        if let Some(field2) = self.data.fields.get_mut(field2_id) {
-            field2.position = (pos2_x, pos2_y);
-            field2.radius = radius2;
+        // 绘制关系类型图例（线型和箭头示例）
+        let relation_types = self.data.relation_types_used(visible_relations);
+        if relation_types.is_empty() {
+            return;
         }
-        if let Some(field3) = self.data.fields.get_mut(field3_id) {
-            field3.position = (pos3_x, pos3_y);
-            field3.radius = radius3;
+        current_y += section_spacing;
+        current_y += section_title_height / 2.0;
+        painter.text(
+            pos2(legend_x + padding, current_y),
+            Align2::LEFT_CENTER,
+            "关系类型:",
+            FontId::proportional(10.0),
+            theme.canvas_text_secondary,
+        );
+        current_y += section_title_height / 2.0;
+        
+        for relation_type in relation_types {
+            current_y += item_height / 2.0;
+            
+            let sample = [pos2(legend_x + padding, current_y), pos2(legend_x + padding + 22.0, current_y)];
+            let stroke = Stroke::new(relation_type.width.min(3.0), relation_type_display_color(theme, relation_type));
+            paint_relation_line(painter, &sample, relation_type.line_style, relation_type.arrow_head, stroke, 6.0);
+            
+            painter.text(
+                pos2(legend_x + padding + 28.0, current_y),
+                Align2::LEFT_CENTER,
+                &relation_type.name,
+                FontId::proportional(9.0),
+                theme.canvas_text,
+            );
+            
+            current_y += item_height / 2.0 + spacing;
         }
     }
-    
+
     /// 设置对话框 - VSCode风格
     fn settings_dialog(&mut self, ctx: &Context) {
-        let theme = Theme::light();
+        let theme = self.theme();
         
         egui::Window::new("设置")
             .collapsible(false)
@@ -4217,6 +5916,75 @@ impl VennCVApp {
                     ui.separator();
                     ui.add_space(12.0);
                     
+                    // 外观设置
+                    ui.vertical(|ui| {
+                        ui.label(
+                            RichText::new("外观")
+                                .size(12.0)
+                                .color(theme.text_secondary)
+                        );
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            for mode in [ThemeMode::Light, ThemeMode::Dark, ThemeMode::System] {
+                                ui.radio_value(&mut self.settings.theme_mode, mode, mode.name());
+                            }
+                        });
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new("自定义主题").size(11.0).color(theme.text_secondary));
+                            let selected_text = self.settings.custom_theme.clone().unwrap_or_else(|| "无".to_string());
+                            egui::ComboBox::from_id_source("custom_theme_combo")
+                                .selected_text(selected_text)
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut self.settings.custom_theme, None, "无");
+                                    for custom in &self.custom_themes {
+                                        ui.selectable_value(&mut self.settings.custom_theme, Some(custom.name.clone()), &custom.name);
+                                    }
+                                });
+                        });
+                        if let Some(name) = &self.settings.custom_theme {
+                            if !self.custom_themes.iter().any(|t| &t.name == name) {
+                                ui.label(
+                                    RichText::new(format!("未找到主题 {}，当前使用{}主题", name, self.settings.theme_mode.name()))
+                                        .size(11.0)
+                                        .color(theme.warning)
+                                );
+                            }
+                        }
+                        ui.add_space(4.0);
+                        ui.horizontal(|ui| {
+                            if ui.button("重新加载主题").clicked() {
+                                self.reload_custom_themes();
+                                self.theme_message = format!("已加载 {} 个自定义主题", self.custom_themes.len());
+                            }
+                            if ui.button("导出当前主题为模板").clicked() {
+                                // 模板使用新名称，避免与内置主题重名
+                                let mut template = theme.clone();
+                                template.name = format!("{} 自定义", theme.name);
+                                let file_name = format!("{}_template", theme.name.replace(['/', '\\', ' ', '+'], "_"));
+                                match save_theme_template(&template, &self.data.sorted_statuses(), &file_name) {
+                                    Ok(path) => self.theme_message = format!("已导出到 {}", path.display()),
+                                    Err(e) => self.theme_message = e,
+                                }
+                            }
+                        });
+                        ui.label(
+                            RichText::new(format!("主题文件目录: {}/（YAML 格式）", THEMES_DIR))
+                                .size(11.0)
+                                .color(theme.text_secondary)
+                        );
+                        if !self.theme_message.is_empty() {
+                            ui.label(RichText::new(&self.theme_message).size(11.0).color(theme.text_secondary));
+                        }
+                        for error in &self.theme_load_errors {
+                            ui.label(RichText::new(error).size(11.0).color(theme.error));
+                        }
+                    });
+                    
+                    ui.add_space(16.0);
+                    ui.separator();
+                    ui.add_space(12.0);
+                    
                     // 自动保存设置
                     ui.vertical(|ui| {
                         ui.label(
@@ -4262,6 +6030,7 @@ impl VennCVApp {
                         ui.add_space(8.0);
                         ui.checkbox(&mut self.settings.show_project_numbers, "显示项目编号");
                         ui.checkbox(&mut self.settings.show_project_names, "显示项目名称");
+                        ui.checkbox(&mut self.settings.show_project_tags, "显示项目标签徽章");
                         ui.add_space(8.0);
                         ui.vertical(|ui| {
                             ui.label(
@@ -4286,13 +6055,16 @@ impl VennCVApp {
                             RichText::new("背景颜色").size(11.0).color(theme.text_secondary)
                         );
                         ui.add_space(4.0);
+                        ui.checkbox(&mut self.settings.canvas_bg_follow_theme, "跟随主题");
                         let mut bg_color_rgba = [
                             self.settings.visualization_bg_color[0] as f32 / 255.0,
                             self.settings.visualization_bg_color[1] as f32 / 255.0,
                             self.settings.visualization_bg_color[2] as f32 / 255.0,
                             self.settings.visualization_bg_color[3] as f32 / 255.0,
                         ];
-                        if ui.color_edit_button_rgba_unmultiplied(&mut bg_color_rgba).changed() {
+                        if !self.settings.canvas_bg_follow_theme
+                            && ui.color_edit_button_rgba_unmultiplied(&mut bg_color_rgba).changed()
+                        {
                             self.settings.visualization_bg_color = [
                                 (bg_color_rgba[0] * 255.0) as u8,
                                 (bg_color_rgba[1] * 255.0) as u8,
@@ -4301,6 +6073,40 @@ impl VennCVApp {
                             ];
                         }
                     });
+                    ui.add_space(8.0);
+                    ui.vertical(|ui| {
+                        ui.label(
+                            RichText::new("色盲友好编码").size(11.0).color(theme.text_secondary)
+                        );
+                        ui.add_space(4.0);
+                        ui.horizontal(|ui| {
+                            ui.label("状态配色");
+                            egui::ComboBox::from_id_source("status_palette_combo")
+                                .selected_text(self.settings.status_palette.name())
+                                .show_ui(ui, |ui| {
+                                    for palette in [StatusPalette::Theme, StatusPalette::OkabeIto] {
+                                        ui.selectable_value(&mut self.settings.status_palette, palette, palette.name());
+                                    }
+                                });
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("完成度配色");
+                            egui::ComboBox::from_id_source("completion_palette_combo")
+                                .selected_text(self.settings.completion_palette.name())
+                                .show_ui(ui, |ui| {
+                                    for palette in [CompletionPalette::Theme, CompletionPalette::Viridis] {
+                                        ui.selectable_value(&mut self.settings.completion_palette, palette, palette.name());
+                                    }
+                                });
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("状态标记");
+                            for marker in [StatusMarker::None, StatusMarker::Pattern, StatusMarker::Glyph] {
+                                ui.radio_value(&mut self.settings.status_marker, marker, marker.name());
+                            }
+                        });
+                        ui.checkbox(&mut self.settings.show_completion_ring, "显示完成度弧线（弧长表示完成度）");
+                    });
                 });
                     
                     ui.add_space(16.0);
@@ -4447,82 +6253,914 @@ impl VennCVApp {
                 });
             });
     }
-    
-    /// 图例设置对话框
-    fn legend_settings_dialog(&mut self, ctx: &Context) {
-        let theme = Theme::light();
+    
+    /// 图例设置对话框
+    fn legend_settings_dialog(&mut self, ctx: &Context) {
+        let theme = self.theme();
+        
+        egui::Window::new("图例设置")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .fixed_size([280.0, 200.0])
+            .frame(Frame::window(&ctx.style()).fill(theme.background))
+            .show(ctx, |ui| {
+                ui.vertical(|ui| {
+                    ui.add_space(8.0);
+                    
+                    // 显示/隐藏图例
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new("显示图例")
+                                .size(12.0)
+                                .color(theme.text_primary)
+                        );
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            ui.checkbox(&mut self.settings.show_legend, "");
+                        });
+                    });
+                    
+                    ui.add_space(8.0);
+                    ui.separator();
+                    ui.add_space(8.0);
+                    
+                    // 重置位置按钮
+                    if ui.add_sized(
+                        [ui.available_width(), 28.0],
+                        Button::new(
+                            RichText::new("重置图例位置")
+                                .size(12.0)
+                                .color(theme.text_primary)
+                        )
+                        .fill(theme.surface)
+                    ).clicked() {
+                        self.legend_position = Vec2::ZERO;
+                    }
+                    
+                    ui.add_space(16.0);
+                    
+                    // 提示信息
+                    ui.label(
+                        RichText::new("提示：可以拖拽图例调整位置")
+                            .size(10.0)
+                            .color(theme.text_secondary)
+                    );
+                    
+                    ui.add_space(16.0);
+                    ui.separator();
+                    ui.add_space(8.0);
+                    
+                    // 关闭按钮
+                    ui.horizontal(|ui| {
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            if ui.add_sized(
+                                [80.0, 28.0],
+                                Button::new(
+                                    RichText::new("关闭")
+                                        .size(12.0)
+                                        .color(Color32::WHITE)
+                                )
+                                .fill(theme.primary)
+                            ).clicked() {
+                                self.save_settings();
+                                self.show_legend_settings = false;
+                            }
+                        });
+                    });
+                });
+            });
+    }
+    
+    /// 状态管理对话框：编辑当前文档的项目状态目录
+    fn status_dialog(&mut self, ctx: &Context) {
+        let theme = self.theme();
+        let mut open = true;
+        // 对话框中的操作：(状态ID, 操作)
+        enum StatusAction {
+            MoveUp,
+            MoveDown,
+            Delete,
+        }
+        let mut action: Option<(String, StatusAction)> = None;
+        let mut changed = false;
+        
+        egui::Window::new("状态管理")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .default_size([460.0, 360.0])
+            .show(ctx, |ui| {
+                ui.set_width(460.0);
+                ui.label(
+                    RichText::new("状态按顺序显示在图例和下拉列表中；\"计为完成\"的状态用于统计已完成项目")
+                        .size(11.0)
+                        .color(theme.text_secondary)
+                );
+                ui.add_space(8.0);
+                
+                let ids: Vec<String> = self.data.sorted_statuses().iter().map(|s| s.id.clone()).collect();
+                let count = ids.len();
+                egui::Grid::new("status_grid")
+                    .num_columns(5)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("颜色").size(11.0).color(theme.text_secondary));
+                        ui.label(RichText::new("名称").size(11.0).color(theme.text_secondary));
+                        ui.label(RichText::new("计为完成").size(11.0).color(theme.text_secondary));
+                        ui.label(RichText::new("项目数").size(11.0).color(theme.text_secondary));
+                        ui.label("");
+                        ui.end_row();
+                        
+                        for (index, id) in ids.iter().enumerate() {
+                            let usage = self.data.projects.values().filter(|p| &p.status == id).count();
+                            let Some(status) = self.data.statuses.iter_mut().find(|s| &s.id == id) else {
+                                continue;
+                            };
+                            let mut rgba = [
+                                status.color[0] as f32 / 255.0,
+                                status.color[1] as f32 / 255.0,
+                                status.color[2] as f32 / 255.0,
+                                status.color[3] as f32 / 255.0,
+                            ];
+                            if ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed() {
+                                status.color = [
+                                    (rgba[0] * 255.0) as u8,
+                                    (rgba[1] * 255.0) as u8,
+                                    (rgba[2] * 255.0) as u8,
+                                    (rgba[3] * 255.0) as u8,
+                                ];
+                                changed = true;
+                            }
+                            if ui.add(TextEdit::singleline(&mut status.name).desired_width(140.0)).changed() {
+                                changed = true;
+                            }
+                            if ui.checkbox(&mut status.counts_as_done, "").changed() {
+                                changed = true;
+                            }
+                            ui.label(usage.to_string());
+                            ui.horizontal(|ui| {
+                                if ui.add_enabled(index > 0, Button::new("↑").small()).clicked() {
+                                    action = Some((id.clone(), StatusAction::MoveUp));
+                                }
+                                if ui.add_enabled(index + 1 < count, Button::new("↓").small()).clicked() {
+                                    action = Some((id.clone(), StatusAction::MoveDown));
+                                }
+                                let can_delete = usage == 0 && count > 1;
+                                let delete_btn = ui.add_enabled(can_delete, Button::new("✕").small());
+                                if delete_btn.clicked() {
+                                    action = Some((id.clone(), StatusAction::Delete));
+                                }
+                                if usage > 0 {
+                                    delete_btn.on_disabled_hover_text(format!("有 {} 个项目使用此状态，无法删除", usage));
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                
+                ui.add_space(8.0);
+                ui.separator();
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.add(
+                        TextEdit::singleline(&mut self.new_status_name)
+                            .hint_text("新状态名称")
+                            .desired_width(180.0)
+                    );
+                    let name = self.new_status_name.trim().to_string();
+                    if ui.add_enabled(!name.is_empty(), Button::new("添加状态")).clicked() {
+                        let max_num = self.data.statuses.iter()
+                            .filter_map(|s| s.id.strip_prefix("status_").and_then(|n| n.parse::<usize>().ok()))
+                            .max()
+                            .unwrap_or(0);
+                        let order = self.data.statuses.iter().map(|s| s.order).max().map(|o| o + 1).unwrap_or(0);
+                        self.data.statuses.push(ProjectStatus::new(
+                            &format!("status_{}", max_num + 1),
+                            &name,
+                            [158, 158, 158, 255],
+                            order,
+                            false,
+                        ));
+                        self.new_status_name.clear();
+                        changed = true;
+                    }
+                });
+            });
+        
+        if let Some((id, action)) = action {
+            // 先按当前顺序重新编号，再交换相邻状态
+            let ids: Vec<String> = self.data.sorted_statuses().iter().map(|s| s.id.clone()).collect();
+            for status in self.data.statuses.iter_mut() {
+                status.order = ids.iter().position(|i| i == &status.id).unwrap_or(0) as i32;
+            }
+            let index = ids.iter().position(|i| i == &id).unwrap_or(0);
+            let swap_with = match action {
+                StatusAction::MoveUp => index.checked_sub(1),
+                StatusAction::MoveDown => Some(index + 1).filter(|i| *i < ids.len()),
+                StatusAction::Delete => {
+                    self.data.statuses.retain(|s| s.id != id);
+                    None
+                }
+            };
+            if let Some(other) = swap_with {
+                for status in self.data.statuses.iter_mut() {
+                    if status.id == ids[index] {
+                        status.order = other as i32;
+                    } else if status.id == ids[other] {
+                        status.order = index as i32;
+                    }
+                }
+            }
+            changed = true;
+        }
+        
+        if changed {
+            self.save_to_history();
+        }
+        if !open {
+            self.show_status_dialog = false;
+            self.new_status_name.clear();
+        }
+    }
+
+    /// 领域配色对话框：设置每个领域的边框颜色、半透明填充颜色和名称样式
+    fn field_style_dialog(&mut self, ctx: &Context) {
+        let theme = self.theme();
+        let mut open = true;
+        let mut changed = false;
+        
+        egui::Window::new("领域配色")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .default_size([480.0, 300.0])
+            .show(ctx, |ui| {
+                ui.set_width(480.0);
+                ui.label(
+                    RichText::new("填充为半透明颜色，多个领域相交处颜色叠加；填充透明度为 0 时不填充")
+                        .size(11.0)
+                        .color(theme.text_secondary)
+                );
+                ui.add_space(8.0);
+                
+                let tree: Vec<(String, usize)> = self.data.field_tree().iter().map(|(f, depth)| (f.id.clone(), *depth)).collect();
+                Grid::new("field_style_grid").num_columns(5).spacing([12.0, 6.0]).show(ui, |ui| {
+                    ui.label(RichText::new("领域").strong());
+                    ui.label(RichText::new("边框").strong());
+                    ui.label(RichText::new("填充").strong());
+                    ui.label(RichText::new("名称样式").strong());
+                    ui.label("");
+                    ui.end_row();
+                    
+                    for (id, depth) in &tree {
+                        let Some(field) = self.data.fields.get_mut(id) else {
+                            continue;
+                        };
+                        ui.label(format!("{}{}", "  ".repeat(*depth), field.name));
+                        for color in [&mut field.stroke_color, &mut field.fill_color] {
+                            let mut rgba = [
+                                color[0] as f32 / 255.0,
+                                color[1] as f32 / 255.0,
+                                color[2] as f32 / 255.0,
+                                color[3] as f32 / 255.0,
+                            ];
+                            if ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed() {
+                                *color = [
+                                    (rgba[0] * 255.0) as u8,
+                                    (rgba[1] * 255.0) as u8,
+                                    (rgba[2] * 255.0) as u8,
+                                    (rgba[3] * 255.0) as u8,
+                                ];
+                                changed = true;
+                            }
+                        }
+                        egui::ComboBox::from_id_source(format!("field_label_style_{}", id))
+                            .selected_text(field.label_style.name())
+                            .width(90.0)
+                            .show_ui(ui, |ui| {
+                                for label_style in FieldLabelStyle::all() {
+                                    if ui.selectable_value(&mut field.label_style, label_style, label_style.name()).changed() {
+                                        changed = true;
+                                    }
+                                }
+                            });
+                        if ui.small_button("默认").on_hover_text("恢复灰色边框、不填充、普通名称").clicked() {
+                            field.stroke_color = default_field_stroke_color();
+                            field.fill_color = default_field_fill_color();
+                            field.label_style = default_field_label_style();
+                            changed = true;
+                        }
+                        ui.end_row();
+                    }
+                });
+                
+                ui.add_space(8.0);
+                ui.separator();
+                if ui.button("自动配色").on_hover_text("按领域顺序为每个领域分配不同颜色的边框和半透明填充").clicked() {
+                    for (index, field) in self.data.fields.values_mut().enumerate() {
+                        let [r, g, b, _] = field_palette_color(index);
+                        field.stroke_color = [r, g, b, 255];
+                        field.fill_color = [r, g, b, 40];
+                    }
+                    changed = true;
+                }
+            });
+        
+        if changed {
+            self.save_to_history();
+        }
+        if !open {
+            self.show_field_style_dialog = false;
+        }
+    }
+
+    /// 关系类型管理对话框：编辑当前文档的关系类型目录（线型、箭头、默认颜色和线宽）
+    fn relation_type_dialog(&mut self, ctx: &Context) {
+        let theme = self.theme();
+        let mut open = true;
+        // 对话框中的操作：(关系类型ID, 操作)
+        enum RelationTypeAction {
+            MoveUp,
+            MoveDown,
+            Delete,
+            Apply,
+        }
+        let mut action: Option<(String, RelationTypeAction)> = None;
+        let mut changed = false;
+        
+        egui::Window::new("关系类型管理")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .default_size([600.0, 360.0])
+            .show(ctx, |ui| {
+                ui.set_width(600.0);
+                ui.label(
+                    RichText::new("颜色和线宽是新建关系时的默认值，\"应用\"把它们同步到该类型的已有关系；线型和箭头对所有该类型的关系生效")
+                        .size(11.0)
+                        .color(theme.text_secondary)
+                );
+                ui.add_space(8.0);
+                
+                let ids: Vec<String> = self.data.sorted_relation_types().iter().map(|t| t.id.clone()).collect();
+                let count = ids.len();
+                egui::Grid::new("relation_type_grid")
+                    .num_columns(7)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("颜色").size(11.0).color(theme.text_secondary));
+                        ui.label(RichText::new("名称").size(11.0).color(theme.text_secondary));
+                        ui.label(RichText::new("线型").size(11.0).color(theme.text_secondary));
+                        ui.label(RichText::new("箭头").size(11.0).color(theme.text_secondary));
+                        ui.label(RichText::new("线宽").size(11.0).color(theme.text_secondary));
+                        ui.label(RichText::new("关系数").size(11.0).color(theme.text_secondary));
+                        ui.label("");
+                        ui.end_row();
+                        
+                        for (index, id) in ids.iter().enumerate() {
+                            let usage = self.data.relations.iter().filter(|r| &r.relation_type == id).count();
+                            let Some(relation_type) = self.data.relation_types.iter_mut().find(|t| &t.id == id) else {
+                                continue;
+                            };
+                            let mut rgba = [
+                                relation_type.color[0] as f32 / 255.0,
+                                relation_type.color[1] as f32 / 255.0,
+                                relation_type.color[2] as f32 / 255.0,
+                                relation_type.color[3] as f32 / 255.0,
+                            ];
+                            if ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed() {
+                                relation_type.color = [
+                                    (rgba[0] * 255.0) as u8,
+                                    (rgba[1] * 255.0) as u8,
+                                    (rgba[2] * 255.0) as u8,
+                                    (rgba[3] * 255.0) as u8,
+                                ];
+                                changed = true;
+                            }
+                            if ui.add(TextEdit::singleline(&mut relation_type.name).desired_width(120.0)).changed() {
+                                changed = true;
+                            }
+                            egui::ComboBox::from_id_source(format!("relation_type_line_{}", id))
+                                .selected_text(relation_type.line_style.name())
+                                .width(60.0)
+                                .show_ui(ui, |ui| {
+                                    for line_style in LineStyle::all() {
+                                        if ui.selectable_value(&mut relation_type.line_style, line_style, line_style.name()).changed() {
+                                            changed = true;
+                                        }
+                                    }
+                                });
+                            egui::ComboBox::from_id_source(format!("relation_type_arrow_{}", id))
+                                .selected_text(relation_type.arrow_head.name())
+                                .width(60.0)
+                                .show_ui(ui, |ui| {
+                                    for arrow_head in ArrowHead::all() {
+                                        if ui.selectable_value(&mut relation_type.arrow_head, arrow_head, arrow_head.name()).changed() {
+                                            changed = true;
+                                        }
+                                    }
+                                });
+                            if ui.add(DragValue::new(&mut relation_type.width).speed(0.1).clamp_range(0.5..=10.0)).changed() {
+                                changed = true;
+                            }
+                            ui.label(usage.to_string());
+                            ui.horizontal(|ui| {
+                                if ui.add_enabled(index > 0, Button::new("↑").small()).clicked() {
+                                    action = Some((id.clone(), RelationTypeAction::MoveUp));
+                                }
+                                if ui.add_enabled(index + 1 < count, Button::new("↓").small()).clicked() {
+                                    action = Some((id.clone(), RelationTypeAction::MoveDown));
+                                }
+                                let can_delete = usage == 0 && count > 1;
+                                let delete_btn = ui.add_enabled(can_delete, Button::new("✕").small());
+                                if delete_btn.clicked() {
+                                    action = Some((id.clone(), RelationTypeAction::Delete));
+                                }
+                                if usage > 0 {
+                                    delete_btn.on_disabled_hover_text(format!("有 {} 条关系使用此类型，无法删除", usage));
+                                }
+                                if ui.add_enabled(usage > 0, Button::new("应用").small())
+                                    .on_hover_text("把颜色和线宽应用到该类型的已有关系")
+                                    .clicked()
+                                {
+                                    action = Some((id.clone(), RelationTypeAction::Apply));
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                
+                ui.add_space(8.0);
+                ui.separator();
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.add(
+                        TextEdit::singleline(&mut self.new_relation_type_name)
+                            .hint_text("新关系类型名称")
+                            .desired_width(180.0)
+                    );
+                    let name = self.new_relation_type_name.trim().to_string();
+                    if ui.add_enabled(!name.is_empty(), Button::new("添加类型")).clicked() {
+                        let max_num = self.data.relation_types.iter()
+                            .filter_map(|t| t.id.strip_prefix("relation_type_").and_then(|n| n.parse::<usize>().ok()))
+                            .max()
+                            .unwrap_or(0);
+                        let order = self.data.relation_types.iter().map(|t| t.order).max().map(|o| o + 1).unwrap_or(0);
+                        self.data.relation_types.push(RelationType::new(
+                            &format!("relation_type_{}", max_num + 1),
+                            &name,
+                            LineStyle::Solid,
+                            ArrowHead::Open,
+                            order,
+                        ));
+                        self.new_relation_type_name.clear();
+                        changed = true;
+                    }
+                });
+            });
+        
+        if let Some((id, action)) = action {
+            // 先按当前顺序重新编号，再交换相邻类型
+            let ids: Vec<String> = self.data.sorted_relation_types().iter().map(|t| t.id.clone()).collect();
+            for relation_type in self.data.relation_types.iter_mut() {
+                relation_type.order = ids.iter().position(|i| i == &relation_type.id).unwrap_or(0) as i32;
+            }
+            let index = ids.iter().position(|i| i == &id).unwrap_or(0);
+            let swap_with = match action {
+                RelationTypeAction::MoveUp => index.checked_sub(1),
+                RelationTypeAction::MoveDown => Some(index + 1).filter(|i| *i < ids.len()),
+                RelationTypeAction::Delete => {
+                    self.data.relation_types.retain(|t| t.id != id);
+                    None
+                }
+                RelationTypeAction::Apply => {
+                    if let Some((color, width)) = self.data.relation_type(&id).map(|t| (t.color, t.width)) {
+                        for relation in self.data.relations.iter_mut().filter(|r| r.relation_type == id) {
+                            relation.color = color;
+                            relation.width = width;
+                        }
+                    }
+                    None
+                }
+            };
+            if let Some(other) = swap_with {
+                for relation_type in self.data.relation_types.iter_mut() {
+                    if relation_type.id == ids[index] {
+                        relation_type.order = other as i32;
+                    } else if relation_type.id == ids[other] {
+                        relation_type.order = index as i32;
+                    }
+                }
+            }
+            changed = true;
+        }
+        
+        if changed {
+            self.save_to_history();
+        }
+        if !open {
+            self.show_relation_type_dialog = false;
+            self.new_relation_type_name.clear();
+        }
+    }
+
+    /// 关系标签管理对话框：查看标签使用次数，重命名/合并、删除标签，设置标签颜色和是否在维恩图中显示
+    fn relation_tag_dialog(&mut self, ctx: &Context) {
+        let theme = self.theme();
+        let mut open = true;
+        // 对话框中的操作：(标签, 操作)
+        enum TagAction {
+            MoveUp,
+            MoveDown,
+            Delete,
+            Rename(String),
+        }
+        let mut action: Option<(String, TagAction)> = None;
+        let mut changed = false;
+        
+        egui::Window::new("关系标签管理")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .default_size([500.0, 400.0])
+            .show(ctx, |ui| {
+                ui.set_width(500.0);
+                ui.label(
+                    RichText::new("重命名为已有标签即合并两个标签；删除标签会同时从所有关系中移除；取消\"显示\"的标签对应的关系在维恩图中隐藏")
+                        .size(11.0)
+                        .color(theme.text_secondary)
+                );
+                ui.add_space(8.0);
+                
+                let tags = self.data.relation_tags.clone();
+                let count = tags.len();
+                if tags.is_empty() {
+                    ui.label(RichText::new("暂无标签").size(11.0).color(theme.text_secondary));
+                }
+                ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    egui::Grid::new("relation_tag_grid")
+                        .num_columns(5)
+                        .spacing([8.0, 6.0])
+                        .show(ui, |ui| {
+                            if !tags.is_empty() {
+                                ui.label(RichText::new("颜色").size(11.0).color(theme.text_secondary));
+                                ui.label(RichText::new("名称").size(11.0).color(theme.text_secondary));
+                                ui.label(RichText::new("关系数").size(11.0).color(theme.text_secondary));
+                                ui.label(RichText::new("显示").size(11.0).color(theme.text_secondary));
+                                ui.label("");
+                                ui.end_row();
+                            }
+                            
+                            for (index, tag) in tags.iter().enumerate() {
+                                let usage = self.data.relation_tag_usage(tag);
+                                ui.horizontal(|ui| {
+                                    let color = tag_chip_color(self.data.relation_tag_colors.get(tag));
+                                    let mut rgba = color.to_srgba_unmultiplied().map(|c| c as f32 / 255.0);
+                                    if ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed() {
+                                        self.data.relation_tag_colors.insert(tag.clone(), rgba.map(|c| (c * 255.0) as u8));
+                                        changed = true;
+                                    }
+                                    if self.data.relation_tag_colors.contains_key(tag)
+                                        && ui.small_button("默认").on_hover_text("清除标签颜色").clicked()
+                                    {
+                                        self.data.relation_tag_colors.shift_remove(tag);
+                                        changed = true;
+                                    }
+                                });
+                                match &mut self.relation_tag_rename {
+                                    Some((original, new_name)) if original == tag => {
+                                        ui.horizontal(|ui| {
+                                            ui.add(TextEdit::singleline(new_name).desired_width(120.0));
+                                            let target = new_name.trim().to_string();
+                                            let merge = target != *tag && tags.contains(&target);
+                                            let label = if merge { "合并" } else { "确定" };
+                                            if ui.add_enabled(!target.is_empty(), Button::new(label).small()).clicked() {
+                                                action = Some((tag.clone(), TagAction::Rename(target)));
+                                            }
+                                        });
+                                    }
+                                    _ => {
+                                        ui.label(
+                                            RichText::new(tag)
+                                                .color(contrast_text_color(tag_chip_color(self.data.relation_tag_colors.get(tag))))
+                                                .background_color(tag_chip_color(self.data.relation_tag_colors.get(tag)))
+                                        );
+                                    }
+                                }
+                                ui.label(usage.to_string());
+                                let mut shown = !self.hidden_relation_tags.contains(tag);
+                                if ui.checkbox(&mut shown, "").changed() {
+                                    if shown {
+                                        self.hidden_relation_tags.retain(|t| t != tag);
+                                    } else {
+                                        self.hidden_relation_tags.push(tag.clone());
+                                    }
+                                    self.active_view = None;
+                                }
+                                ui.horizontal(|ui| {
+                                    if ui.add_enabled(index > 0, Button::new("↑").small()).clicked() {
+                                        action = Some((tag.clone(), TagAction::MoveUp));
+                                    }
+                                    if ui.add_enabled(index + 1 < count, Button::new("↓").small()).clicked() {
+                                        action = Some((tag.clone(), TagAction::MoveDown));
+                                    }
+                                    if ui.small_button("重命名").clicked() {
+                                        self.relation_tag_rename = Some((tag.clone(), tag.clone()));
+                                    }
+                                    let delete_btn = ui.small_button("✕");
+                                    if delete_btn.clicked() {
+                                        action = Some((tag.clone(), TagAction::Delete));
+                                    }
+                                    if usage > 0 {
+                                        delete_btn.on_hover_text(format!("同时从 {} 条关系中移除此标签", usage));
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                });
+                
+                ui.add_space(8.0);
+                ui.separator();
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.add(
+                        TextEdit::singleline(&mut self.new_relation_tag_name)
+                            .hint_text("新标签名称")
+                            .desired_width(180.0)
+                    );
+                    let name = self.new_relation_tag_name.trim().to_string();
+                    let can_add = !name.is_empty() && !self.data.relation_tags.contains(&name);
+                    if ui.add_enabled(can_add, Button::new("添加标签")).clicked() {
+                        self.data.relation_tags.push(name);
+                        self.new_relation_tag_name.clear();
+                        changed = true;
+                    }
+                });
+            });
+        
+        if let Some((tag, action)) = action {
+            let index = self.data.relation_tags.iter().position(|t| t == &tag).unwrap_or(0);
+            match action {
+                TagAction::MoveUp => {
+                    if index > 0 {
+                        self.data.relation_tags.swap(index, index - 1);
+                    }
+                }
+                TagAction::MoveDown => {
+                    if index + 1 < self.data.relation_tags.len() {
+                        self.data.relation_tags.swap(index, index + 1);
+                    }
+                }
+                TagAction::Delete => {
+                    self.data.delete_relation_tag(&tag);
+                    self.hidden_relation_tags.retain(|t| t != &tag);
+                }
+                TagAction::Rename(new_name) => {
+                    self.relation_tag_rename = None;
+                    self.data.rename_relation_tag(&tag, &new_name);
+                    // 当前视图的隐藏标签同样改名（合并时去重）
+                    let position = self.hidden_relation_tags.iter().position(|t| t == &tag).filter(|_| new_name != tag);
+                    if let Some(index) = position {
+                        if self.hidden_relation_tags.contains(&new_name) {
+                            self.hidden_relation_tags.remove(index);
+                        } else {
+                            self.hidden_relation_tags[index] = new_name;
+                        }
+                    }
+                }
+            }
+            changed = true;
+        }
+        
+        if changed {
+            self.save_to_history();
+        }
+        if !open {
+            self.show_relation_tag_dialog = false;
+            self.relation_tag_rename = None;
+            self.new_relation_tag_name.clear();
+        }
+    }
+
+    /// 用户管理对话框：重命名、修改密码、移动存储目录、删除用户
+    fn user_admin_dialog(&mut self, ctx: &Context) {
+        let theme = self.theme();
+        let users = self.manageable_users();
+        let mut open = true;
         
-        egui::Window::new("图例设置")
+        egui::Window::new("用户管理")
             .collapsible(false)
             .resizable(false)
-            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
-            .fixed_size([280.0, 200.0])
-            .frame(Frame::window(&ctx.style()).fill(theme.background))
+            .open(&mut open)
+            .default_size([520.0, 420.0])
             .show(ctx, |ui| {
-                ui.vertical(|ui| {
-                    ui.add_space(8.0);
-                    
-                    // 显示/隐藏图例
-                    ui.horizontal(|ui| {
+                ui.set_width(520.0);
+                ui.horizontal_top(|ui| {
+                    // 左侧：用户列表
+                    ui.vertical(|ui| {
+                        ui.set_width(140.0);
                         ui.label(
-                            RichText::new("显示图例")
+                            RichText::new("用户")
                                 .size(12.0)
-                                .color(theme.text_primary)
+                                .color(theme.text_secondary)
                         );
-                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                            ui.checkbox(&mut self.settings.show_legend, "");
-                        });
+                        ui.add_space(4.0);
+                        for username in &users {
+                            let is_selected = self.user_admin_selected.as_ref() == Some(username);
+                            let label = if self.current_user.as_ref() == Some(username) {
+                                format!("{}（当前）", username)
+                            } else {
+                                username.clone()
+                            };
+                            if ui.selectable_label(is_selected, label).clicked() && !is_selected {
+                                self.user_admin_selected = Some(username.clone());
+                                self.reset_user_admin_inputs();
+                                self.user_admin_message.clear();
+                                self.user_admin_error.clear();
+                            }
+                        }
                     });
                     
-                    ui.add_space(8.0);
                     ui.separator();
-                    ui.add_space(8.0);
                     
-                    // 重置位置按钮
-                    if ui.add_sized(
-                        [ui.available_width(), 28.0],
-                        Button::new(
-                            RichText::new("重置图例位置")
-                                .size(12.0)
+                    // 右侧：选中用户的操作
+                    ui.vertical(|ui| {
+                        let Some(username) = self.user_admin_selected.clone() else {
+                            ui.label(
+                                RichText::new("请选择一个用户").size(12.0).color(theme.text_secondary)
+                            );
+                            return;
+                        };
+                        let storage_path = self.load_config().ok()
+                            .and_then(|c| c.users.into_iter().find(|u| u.username == username))
+                            .and_then(|u| u.data_storage_path);
+                        
+                        // 重命名
+                        ui.label(RichText::new("用户名").size(11.0).color(theme.text_secondary));
+                        ui.add_space(4.0);
+                        ui.horizontal(|ui| {
+                            let is_admin = username == "admin";
+                            ui.add_enabled(!is_admin, TextEdit::singleline(&mut self.user_admin_rename).desired_width(180.0));
+                            if ui.add_enabled(!is_admin, Button::new("重命名"))
+                                .on_disabled_hover_text("admin 用户不能重命名")
+                                .clicked()
+                            {
+                                let new_name = self.user_admin_rename.trim().to_string();
+                                match self.rename_user(&username, &new_name) {
+                                    Ok(_) => {
+                                        self.user_admin_selected = Some(new_name.clone());
+                                        self.user_admin_message = format!("已重命名为 {}", new_name);
+                                        self.user_admin_error.clear();
+                                    }
+                                    Err(e) => {
+                                        self.user_admin_error = format!("重命名失败: {}", e);
+                                        self.user_admin_message.clear();
+                                    }
+                                }
+                            }
+                        });
+                        
+                        ui.add_space(12.0);
+                        
+                        // 修改密码
+                        ui.label(RichText::new("修改密码").size(11.0).color(theme.text_secondary));
+                        ui.add_space(4.0);
+                        let is_admin_reset = self.current_user.as_deref() == Some("admin") && username != "admin";
+                        Grid::new("user_admin_password").num_columns(2).spacing([8.0, 4.0]).show(ui, |ui| {
+                            if !is_admin_reset {
+                                ui.label("当前密码");
+                                ui.add(TextEdit::singleline(&mut self.user_admin_old_password).password(true).desired_width(180.0));
+                                ui.end_row();
+                            }
+                            ui.label("新密码");
+                            ui.add(TextEdit::singleline(&mut self.user_admin_new_password).password(true).desired_width(180.0));
+                            ui.end_row();
+                            ui.label("确认新密码");
+                            ui.add(TextEdit::singleline(&mut self.user_admin_confirm_password).password(true).desired_width(180.0));
+                            ui.end_row();
+                        });
+                        if ui.button("修改密码").clicked() {
+                            if self.user_admin_new_password != self.user_admin_confirm_password {
+                                self.user_admin_error = "两次输入的新密码不一致".to_string();
+                                self.user_admin_message.clear();
+                            } else {
+                                let old_password = self.user_admin_old_password.clone();
+                                let new_password = self.user_admin_new_password.clone();
+                                match self.change_user_password(&username, &old_password, &new_password) {
+                                    Ok(_) => {
+                                        self.user_admin_old_password.clear();
+                                        self.user_admin_new_password.clear();
+                                        self.user_admin_confirm_password.clear();
+                                        self.user_admin_message = "密码已修改".to_string();
+                                        self.user_admin_error.clear();
+                                    }
+                                    Err(e) => {
+                                        self.user_admin_error = format!("修改密码失败: {}", e);
+                                        self.user_admin_message.clear();
+                                    }
+                                }
+                            }
+                        }
+                        
+                        ui.add_space(12.0);
+                        
+                        // 存储目录
+                        ui.label(RichText::new("数据存储路径").size(11.0).color(theme.text_secondary));
+                        ui.add_space(4.0);
+                        ui.label(
+                            RichText::new(storage_path.clone().unwrap_or_else(|| "未设置".to_string()))
+                                .size(11.0)
                                 .color(theme.text_primary)
-                        )
-                        .fill(theme.surface)
-                    ).clicked() {
-                        self.legend_position = Vec2::ZERO;
-                    }
-                    
-                    ui.add_space(16.0);
-                    
-                    // 提示信息
-                    ui.label(
-                        RichText::new("提示：可以拖拽图例调整位置")
-                            .size(10.0)
-                            .color(theme.text_secondary)
-                    );
-                    
-                    ui.add_space(16.0);
-                    ui.separator();
-                    ui.add_space(8.0);
-                    
-                    // 关闭按钮
-                    ui.horizontal(|ui| {
-                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                            if ui.add_sized(
-                                [80.0, 28.0],
-                                Button::new(
-                                    RichText::new("关闭")
-                                        .size(12.0)
-                                        .color(Color32::WHITE)
-                                )
-                                .fill(theme.primary)
-                            ).clicked() {
-                                self.save_settings();
-                                self.show_legend_settings = false;
+                        );
+                        ui.horizontal(|ui| {
+                            if ui.button("移动目录...").clicked() {
+                                if let Some(folder) = rfd::FileDialog::new()
+                                    .set_title("选择新的数据存储路径")
+                                    .pick_folder()
+                                {
+                                    match self.relocate_user_storage(&username, Some(folder)) {
+                                        Ok(_) => {
+                                            self.user_admin_message = "数据目录已移动".to_string();
+                                            self.user_admin_error.clear();
+                                        }
+                                        Err(e) => {
+                                            self.user_admin_error = format!("移动目录失败: {}", e);
+                                            self.user_admin_message.clear();
+                                        }
+                                    }
+                                }
+                            }
+                            let is_default = storage_path.as_deref() == Some(default_user_dir(&username).as_str());
+                            if ui.add_enabled(!is_default, Button::new("恢复默认目录")).clicked() {
+                                match self.relocate_user_storage(&username, None) {
+                                    Ok(_) => {
+                                        self.user_admin_message = format!("数据目录已恢复为 {}", default_user_dir(&username));
+                                        self.user_admin_error.clear();
+                                    }
+                                    Err(e) => {
+                                        self.user_admin_error = format!("恢复默认目录失败: {}", e);
+                                        self.user_admin_message.clear();
+                                    }
+                                }
                             }
                         });
+                        
+                        ui.add_space(12.0);
+                        
+                        // 删除用户（需要二次确认）
+                        let is_current = self.current_user.as_ref() == Some(&username);
+                        if self.user_admin_pending_delete.as_ref() == Some(&username) {
+                            ui.label(
+                                RichText::new(format!("确定要删除用户 {} 吗？此操作无法撤销。", username))
+                                    .size(12.0)
+                                    .color(theme.error)
+                            );
+                            ui.checkbox(&mut self.user_admin_delete_files, "同时删除默认数据目录");
+                            ui.horizontal(|ui| {
+                                if ui.add(
+                                    Button::new(RichText::new("确认删除").color(Color32::WHITE)).fill(theme.error)
+                                ).clicked() {
+                                    match self.delete_user(&username, self.user_admin_delete_files) {
+                                        Ok(_) => {
+                                            self.user_admin_message = format!("已删除用户 {}", username);
+                                            self.user_admin_error.clear();
+                                        }
+                                        Err(e) => {
+                                            self.user_admin_error = e;
+                                            self.user_admin_message.clear();
+                                        }
+                                    }
+                                    // 用户列表已变化，回到当前用户
+                                    self.user_admin_selected = self.current_user.clone();
+                                    self.reset_user_admin_inputs();
+                                }
+                                if ui.button("取消").clicked() {
+                                    self.user_admin_pending_delete = None;
+                                }
+                            });
+                        } else if ui.add_enabled(!is_current, Button::new("删除用户"))
+                            .on_disabled_hover_text("不能删除当前登录的用户")
+                            .clicked()
+                        {
+                            self.user_admin_pending_delete = Some(username.clone());
+                            self.user_admin_delete_files = false;
+                        }
                     });
                 });
+                
+                if !self.user_admin_error.is_empty() {
+                    ui.add_space(8.0);
+                    ui.label(RichText::new(&self.user_admin_error).size(11.0).color(theme.error));
+                } else if !self.user_admin_message.is_empty() {
+                    ui.add_space(8.0);
+                    ui.label(RichText::new(&self.user_admin_message).size(11.0).color(theme.success));
+                }
             });
+        
+        if !open {
+            self.show_user_admin_dialog = false;
+        }
     }
     
     /// 加载应用设置
@@ -4555,7 +7193,7 @@ impl VennCVApp {
         let mut fields = indexmap::IndexMap::new();
         let mut projects = indexmap::IndexMap::new();
         let mut relations = Vec::new();
-        let mut relation_tags = vec![
+        let relation_tags = vec![
             "依赖".to_string(),
             "扩展".to_string(),
             "参考".to_string(),
@@ -4570,6 +7208,11 @@ impl VennCVApp {
             description: "RNA结构研究".to_string(),
             position: (400.0, 200.0),
             radius: 200.0,
+            locked: false,
+            parent_id: None,
+            stroke_color: default_field_stroke_color(),
+            fill_color: default_field_fill_color(),
+            label_style: default_field_label_style(),
         });
 
         fields.insert("microbial_ecology".to_string(), ResearchField {
@@ -4578,6 +7221,11 @@ impl VennCVApp {
             description: "微生物生态研究".to_string(),
             position: (200.0, 500.0),
             radius: 200.0,
+            locked: false,
+            parent_id: None,
+            stroke_color: default_field_stroke_color(),
+            fill_color: default_field_fill_color(),
+            label_style: default_field_label_style(),
         });
 
         fields.insert("bioinfo_tools".to_string(), ResearchField {
@@ -4586,6 +7234,11 @@ impl VennCVApp {
             description: "生物信息学工具开发".to_string(),
             position: (600.0, 500.0),
             radius: 200.0,
+            locked: false,
+            parent_id: None,
+            stroke_color: default_field_stroke_color(),
+            fill_color: default_field_fill_color(),
+            label_style: default_field_label_style(),
         });
 
         // 添加多个项目，展示不同状态和领域
@@ -4593,103 +7246,113 @@ impl VennCVApp {
             id: "project_1".to_string(),
             name: "itol.toolkit".to_string(),
             description: "iTOL工具包，用于可视化系统发育树".to_string(),
-            status: ProjectStatus::Published,
+            status: STATUS_PUBLISHED.to_string(),
             field_ids: vec!["bioinfo_tools".to_string()],
             position: (550.0, 450.0),
             radius: 20.0,
             completion_percentage: 100.0,
+            ..Default::default()
         });
 
         projects.insert("project_2".to_string(), Project {
             id: "project_2".to_string(),
             name: "VnFinder".to_string(),
             description: "VnFinder工具，用于病毒发现".to_string(),
-            status: ProjectStatus::Published,
+            status: STATUS_PUBLISHED.to_string(),
             field_ids: vec!["microbial_ecology".to_string()],
             position: (250.0, 450.0),
             radius: 20.0,
             completion_percentage: 100.0,
+            ..Default::default()
         });
 
         projects.insert("project_3".to_string(), Project {
             id: "project_3".to_string(),
             name: "RNA结构预测工具".to_string(),
             description: "基于深度学习的RNA二级结构预测".to_string(),
-            status: ProjectStatus::Submitted,
+            status: STATUS_SUBMITTED.to_string(),
             field_ids: vec!["rna_structure".to_string(), "bioinfo_tools".to_string()],
             position: (450.0, 300.0),
             radius: 20.0,
             completion_percentage: 90.0,
+            ..Default::default()
         });
 
         projects.insert("project_4".to_string(), Project {
             id: "project_4".to_string(),
             name: "微生物群落分析".to_string(),
             description: "16S rRNA测序数据分析流程".to_string(),
-            status: ProjectStatus::HighPriority,
+            status: STATUS_HIGH_PRIORITY.to_string(),
             field_ids: vec!["microbial_ecology".to_string()],
             position: (200.0, 450.0),
             radius: 20.0,
             completion_percentage: 60.0,
+            ..Default::default()
         });
 
         projects.insert("project_5".to_string(), Project {
             id: "project_5".to_string(),
             name: "RNA-RNA相互作用预测".to_string(),
             description: "预测RNA分子间的相互作用".to_string(),
-            status: ProjectStatus::SteadyProgress,
+            status: STATUS_STEADY_PROGRESS.to_string(),
             field_ids: vec!["rna_structure".to_string()],
             position: (400.0, 250.0),
             radius: 20.0,
             completion_percentage: 40.0,
+            ..Default::default()
         });
 
         projects.insert("project_6".to_string(), Project {
             id: "project_6".to_string(),
             name: "新工具开发计划".to_string(),
             description: "计划开发的新生物信息学工具".to_string(),
-            status: ProjectStatus::ToBeStarted,
+            status: STATUS_TO_BE_STARTED.to_string(),
             field_ids: vec!["bioinfo_tools".to_string()],
             position: (600.0, 450.0),
             radius: 20.0,
             completion_percentage: 0.0,
+            ..Default::default()
         });
 
         // 添加多个关系，展示不同类型的连接
         relations.push(ProjectRelation {
             from_id: "project_1".to_string(),
             to_id: "project_2".to_string(),
-            relation_type: RelationType::Direct,
+            relation_type: RELATION_DIRECT.to_string(),
             tags: vec!["依赖".to_string()],
             color: [0, 0, 255, 255],
             width: 2.0,
+            route: default_relation_route(),
         });
 
         relations.push(ProjectRelation {
             from_id: "project_3".to_string(),
             to_id: "project_1".to_string(),
-            relation_type: RelationType::Indirect,
+            relation_type: RELATION_INDIRECT.to_string(),
             tags: vec!["参考".to_string()],
             color: [128, 128, 128, 255],
             width: 1.5,
+            route: default_relation_route(),
         });
 
         relations.push(ProjectRelation {
             from_id: "project_4".to_string(),
             to_id: "project_2".to_string(),
-            relation_type: RelationType::Direct,
+            relation_type: RELATION_DIRECT.to_string(),
             tags: vec!["扩展".to_string()],
             color: [0, 128, 0, 255],
             width: 2.5,
+            route: default_relation_route(),
         });
 
         relations.push(ProjectRelation {
             from_id: "project_5".to_string(),
             to_id: "project_3".to_string(),
-            relation_type: RelationType::Indirect,
+            relation_type: RELATION_INDIRECT.to_string(),
             tags: vec!["补充".to_string()],
             color: [255, 165, 0, 255],
             width: 1.8,
+            route: default_relation_route(),
         });
 
         AppData {
@@ -4697,23 +7360,208 @@ impl VennCVApp {
             projects,
             relations,
             relation_tags,
+            relation_tag_colors: indexmap::IndexMap::new(),
+            project_tags: Vec::new(),
+            statuses: default_status_catalog(),
+            relation_types: default_relation_type_catalog(),
+            saved_views: Vec::new(),
         }
     }
     
 }
 
+/// 表格筛选：以 >、<、>=、<=、= 开头时按数值或日期比较，否则为不区分大小写的包含匹配
+fn table_filter_matches(filter: &str, text: &str, number: Option<f64>, date: Option<chrono::NaiveDate>) -> bool {
+    let filter = filter.trim();
+    if filter.is_empty() {
+        return true;
+    }
+    for op in [">=", "<=", ">", "<", "="] {
+        let Some(operand) = filter.strip_prefix(op) else { continue };
+        let operand = operand.trim();
+        let ordering = if let (Some(value), Ok(target)) = (number, operand.parse::<f64>()) {
+            value.partial_cmp(&target)
+        } else if let Ok(target) = chrono::NaiveDate::parse_from_str(operand, "%Y-%m-%d") {
+            date.map(|d| d.cmp(&target))
+        } else {
+            break;  // 无法比较时按文本匹配
+        };
+        let Some(ordering) = ordering else { return false };
+        return match op {
+            ">=" => ordering.is_ge(),
+            "<=" => ordering.is_le(),
+            ">" => ordering.is_gt(),
+            "<" => ordering.is_lt(),
+            _ => ordering.is_eq(),
+        };
+    }
+    text.to_lowercase().contains(&filter.to_lowercase())
+}
+
+/// 日期输入框（YYYY-MM-DD，留空表示未设置），返回日期是否被修改
+/// 编辑过程中的文本暂存在 egui 内存中，格式错误时不修改日期并显示提示
+fn date_edit(ui: &mut Ui, id_source: impl std::hash::Hash, value: &mut Option<chrono::NaiveDate>, error_color: Color32) -> bool {
+    let id = ui.make_persistent_id(id_source);
+    let buffer_id = id.with("buffer");
+    let formatted = value.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default();
+    // 未聚焦时始终显示实际值（撤销、切换项目后保持同步）
+    let mut text = if ui.memory(|m| m.has_focus(id)) {
+        ui.data_mut(|d| d.get_temp::<String>(buffer_id)).unwrap_or(formatted)
+    } else {
+        formatted
+    };
+
+    let response = ui.add(
+        TextEdit::singleline(&mut text)
+            .id(id)
+            .hint_text("YYYY-MM-DD")
+            .desired_width(100.0)
+    );
+
+    let mut changed = false;
+    let trimmed = text.trim();
+    let parsed = chrono::NaiveDate::parse_from_str(trimmed, "%Y-%m-%d");
+    if response.changed() {
+        if trimmed.is_empty() {
+            changed = value.take().is_some();
+        } else if let Ok(date) = parsed {
+            if *value != Some(date) {
+                *value = Some(date);
+                changed = true;
+            }
+        }
+    }
+    if !trimmed.is_empty() && parsed.is_err() {
+        ui.label(RichText::new("格式应为 YYYY-MM-DD").size(10.0).color(error_color));
+    }
+    ui.data_mut(|d| d.insert_temp(buffer_id, text));
+    changed
+}
+
+/// 用户的默认数据目录
+fn default_user_dir(username: &str) -> String {
+    format!("users/{}", username)
+}
+
+/// 检查用户名是否可以用作目录名
+fn validate_username(username: &str) -> Result<(), String> {
+    if username.is_empty() {
+        return Err("用户名不能为空".to_string());
+    }
+    if username == "." || username == ".." || username.contains(['/', '\\', ':']) {
+        return Err("用户名不能包含路径字符".to_string());
+    }
+    Ok(())
+}
+
+/// 获取目录的绝对路径（目录不存在时基于当前工作目录拼接）
+fn absolute_dir(dir: &str) -> std::path::PathBuf {
+    let path = std::path::Path::new(dir);
+    path.canonicalize().unwrap_or_else(|_| {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    })
+}
+
+/// 将位于 old_dir 下的文件路径改写到 new_dir 下，不在 old_dir 下的路径保持不变
+/// 同时匹配配置中保存的原始写法和绝对路径写法
+fn rebase_path(path: &str, old_dir: &str, old_dir_abs: &std::path::Path, new_dir: &str) -> String {
+    let file_path = std::path::Path::new(path);
+    if let Ok(relative) = file_path.strip_prefix(old_dir) {
+        return std::path::Path::new(new_dir).join(relative).to_string_lossy().to_string();
+    }
+    if let Ok(relative) = file_path.strip_prefix(old_dir_abs) {
+        return absolute_dir(new_dir).join(relative).to_string_lossy().to_string();
+    }
+    path.to_string()
+}
+
+/// 移动目录
+/// 目标不存在时直接重命名（跨磁盘时回退为复制后删除），
+/// 目标已存在时把源目录中的条目逐个移入，遇到同名条目则中止
+fn move_directory(from: &std::path::Path, to: &std::path::Path) -> Result<(), String> {
+    if !from.exists() {
+        std::fs::create_dir_all(to).map_err(|e| format!("创建目录失败: {}", e))?;
+        return Ok(());
+    }
+    let from_abs = from.canonicalize().unwrap_or_else(|_| from.to_path_buf());
+    let to_abs = absolute_dir(&to.to_string_lossy());
+    if to_abs.starts_with(&from_abs) {
+        return Err("不能把目录移动到它自己的子目录中".to_string());
+    }
+
+    if !to.exists() {
+        if let Some(parent) = to.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+            }
+        }
+        if std::fs::rename(from, to).is_ok() {
+            return Ok(());
+        }
+        copy_directory(from, to)?;
+        return std::fs::remove_dir_all(from).map_err(|e| format!("删除旧目录失败: {}", e));
+    }
+
+    let entries: Vec<std::fs::DirEntry> = std::fs::read_dir(from)
+        .map_err(|e| format!("读取目录失败: {}", e))?
+        .filter_map(|e| e.ok())
+        .collect();
+    if let Some(conflict) = entries.iter().find(|e| to.join(e.file_name()).exists()) {
+        return Err(format!("目标目录中已存在同名文件: {}", conflict.file_name().to_string_lossy()));
+    }
+    for entry in entries {
+        let target = to.join(entry.file_name());
+        if std::fs::rename(entry.path(), &target).is_err() {
+            if entry.path().is_dir() {
+                copy_directory(&entry.path(), &target)?;
+                std::fs::remove_dir_all(entry.path()).map_err(|e| format!("删除旧文件失败: {}", e))?;
+            } else {
+                std::fs::copy(entry.path(), &target).map_err(|e| format!("复制文件失败: {}", e))?;
+                std::fs::remove_file(entry.path()).map_err(|e| format!("删除旧文件失败: {}", e))?;
+            }
+        }
+    }
+    std::fs::remove_dir(from).map_err(|e| format!("删除旧目录失败: {}", e))
+}
+
+/// 递归复制目录
+fn copy_directory(from: &std::path::Path, to: &std::path::Path) -> Result<(), String> {
+    std::fs::create_dir_all(to).map_err(|e| format!("创建目录失败: {}", e))?;
+    for entry in std::fs::read_dir(from).map_err(|e| format!("读取目录失败: {}", e))? {
+        let entry = entry.map_err(|e| format!("读取目录失败: {}", e))?;
+        let target = to.join(entry.file_name());
+        if entry.path().is_dir() {
+            copy_directory(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target).map_err(|e| format!("复制文件失败: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
 impl eframe::App for VennCVApp {
-    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
+        // 跟踪系统主题并应用当前主题
+        if let Some(system_theme) = frame.info().system_theme {
+            self.system_dark_mode = system_theme == eframe::Theme::Dark;
+        }
+        self.apply_theme(ctx);
+        
         // 在所有UI渲染之前消耗Tab键并处理，防止egui默认焦点切换
         if self.is_logged_in {
-            // 清除egui的焦点，防止Tab键导航到UI元素
-            ctx.memory_mut(|m| m.request_focus(egui::Id::NULL));
-            
             // 消耗Tab键事件并获取状态
             let tab_pressed = ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, Key::Tab));
             let shift_tab_pressed = ctx.input_mut(|i| i.consume_key(egui::Modifiers::SHIFT, Key::Tab));
             let cmd_tab_pressed = ctx.input_mut(|i| i.consume_key(egui::Modifiers::MAC_CMD, Key::Tab));
             
+            // 按下Tab时清除egui的焦点，防止Tab键导航到UI元素
+            // （只在按下Tab时清除，否则文本框无法保持焦点）
+            if tab_pressed || shift_tab_pressed || cmd_tab_pressed {
+                ctx.memory_mut(|m| m.request_focus(egui::Id::NULL));
+            }
+            
             // 处理Tab键逻辑
             if cmd_tab_pressed {
                 if self.toolbar_focus_index.is_some() {
@@ -4755,6 +7603,34 @@ impl eframe::App for VennCVApp {
             if self.show_legend_settings {
                 self.legend_settings_dialog(ctx);
             }
+            // 显示用户管理对话框
+            if self.show_user_admin_dialog {
+                self.user_admin_dialog(ctx);
+            }
+            // 显示状态管理对话框
+            if self.show_status_dialog {
+                self.status_dialog(ctx);
+            }
+            // 显示领域配色对话框
+            if self.show_field_style_dialog {
+                self.field_style_dialog(ctx);
+            }
+            // 显示关系类型管理对话框
+            if self.show_relation_type_dialog {
+                self.relation_type_dialog(ctx);
+            }
+            // 显示关系标签管理对话框
+            if self.show_relation_tag_dialog {
+                self.relation_tag_dialog(ctx);
+            }
+            // 显示批量编辑对话框
+            if self.show_bulk_edit_dialog {
+                self.bulk_edit_dialog(ctx);
+            }
+            // 显示布局诊断窗口
+            if self.show_layout_diagnostics {
+                self.layout_diagnostics_window(ctx);
+            }
             self.main_ui(ctx);
         }
     }
@@ -0,0 +1,103 @@
+use crate::models::AppData;
+use crate::query;
+
+const USAGE: &str = "\
+用法: venncv --query <查询> <数据文件.json> [--relations] [--output <导出文件.json>]
+
+  --query, -q    按查询语言筛选项目，每行输出一个匹配的项目（ID、名称、状态、完成度）
+  --relations    同时输出匹配的关系（起点 -> 终点、类型、标签）
+  --output, -o   将匹配的项目及其领域和关系导出为新的数据文件
+  --help, -h     显示帮助
+
+不带参数启动时打开图形界面。";
+
+/// 是否以命令行模式运行（第一个参数为 --query/-q 或 --help/-h）
+pub fn is_cli(args: &[String]) -> bool {
+    matches!(args.first().map(|a| a.as_str()), Some("--query" | "-q" | "--help" | "-h"))
+}
+
+/// 运行命令行查询，返回进程退出码（0 成功，1 文件错误，2 参数或查询错误）
+pub fn run(args: &[String]) -> i32 {
+    let mut query_text = None;
+    let mut data_path = None;
+    let mut output_path = None;
+    let mut show_relations = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                println!("{}\n\n查询语法:\n{}", USAGE, query::QUERY_HELP);
+                return 0;
+            }
+            "--query" | "-q" => query_text = iter.next().cloned(),
+            "--output" | "-o" => output_path = iter.next().cloned(),
+            "--relations" => show_relations = true,
+            _ if data_path.is_none() && !arg.starts_with('-') => data_path = Some(arg.clone()),
+            _ => {
+                eprintln!("未知参数: {}\n\n{}", arg, USAGE);
+                return 2;
+            }
+        }
+    }
+    let (Some(query_text), Some(data_path)) = (query_text, data_path) else {
+        eprintln!("{}", USAGE);
+        return 2;
+    };
+
+    let query = match query::parse(&query_text) {
+        Ok(query) => query,
+        Err(e) => {
+            eprintln!("查询语法错误: {}", e);
+            return 2;
+        }
+    };
+    let data = match std::fs::read_to_string(&data_path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str::<AppData>(&content).map_err(|e| e.to_string()))
+    {
+        Ok(mut data) => {
+            data.migrate();
+            data
+        }
+        Err(e) => {
+            eprintln!("加载数据失败: {}", e);
+            return 1;
+        }
+    };
+
+    let project_ids = query.select_projects(&data);
+    for id in &project_ids {
+        let project = &data.projects[id];
+        println!(
+            "{}\t{}\t{}\t{}%",
+            project.id,
+            project.name,
+            data.status_name(&project.status),
+            project.completion_percentage
+        );
+    }
+    if show_relations {
+        for index in query.select_relations(&data) {
+            let relation = &data.relations[index];
+            println!(
+                "{} -> {}\t{}\t{}",
+                relation.from_id,
+                relation.to_id,
+                data.relation_type_name(&relation.relation_type),
+                relation.tags.join(", ")
+            );
+        }
+    }
+
+    if let Some(output_path) = output_path {
+        let subset = data.subset(&project_ids);
+        let result = serde_json::to_string_pretty(&subset)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(&output_path, json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            eprintln!("导出数据失败: {}", e);
+            return 1;
+        }
+    }
+    0
+}
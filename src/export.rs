@@ -0,0 +1,500 @@
+use crate::models::*;
+use crate::route;
+use crate::render::*;
+use crate::theme::*;
+use egui::Color32;
+use std::fmt::Write;
+
+/// SVG 导出选项（由当前视图的筛选结果决定）
+pub struct SvgOptions {
+    pub background: Color32,           // 背景颜色
+    pub visible_project_ids: Vec<String>,  // 显示的项目
+    pub visible_field_ids: Vec<String>,    // 显示的领域
+    pub visible_relation_indices: Vec<usize>,  // 显示的关系（data.relations 中的序号）
+    pub legend_status_ids: Vec<String>,    // 图例中的状态
+    pub show_legend: bool,             // 是否绘制图例
+}
+
+/// 颜色转为 SVG 属性值（颜色, 不透明度）
+fn svg_color(color: Color32) -> (String, f32) {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    (format!("#{:02x}{:02x}{:02x}", r, g, b), a as f32 / 255.0)
+}
+
+/// 填充属性
+fn fill(color: Color32) -> String {
+    let (c, o) = svg_color(color);
+    format!("fill=\"{}\" fill-opacity=\"{:.2}\"", c, o)
+}
+
+/// 描边属性
+fn stroke(color: Color32, width: f32) -> String {
+    let (c, o) = svg_color(color);
+    format!("stroke=\"{}\" stroke-opacity=\"{:.2}\" stroke-width=\"{:.1}\"", c, o, width)
+}
+
+/// 转义 XML 文本
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// SVG 折线的 points 属性
+fn svg_points(points: &[(f32, f32)]) -> String {
+    points.iter()
+        .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 按关系类型的线型和箭头样式绘制关系（points 为 SVG 坐标，至少两点；与画布的 paint_relation_line 一致）
+fn relation_line(svg: &mut String, points: &[(f32, f32)], line_style: LineStyle, arrow_head: ArrowHead, color: Color32, width: f32, arrow_size: f32) {
+    match (line_style, line_style.dash_pattern()) {
+        (LineStyle::Double, _) => {
+            let (offset, line_width) = double_line_geometry(width);
+            for side in [offset, -offset] {
+                let _ = writeln!(
+                    svg,
+                    "<polyline points=\"{}\" fill=\"none\" {}/>",
+                    svg_points(&route::offset_polyline(points, side)), stroke(color, line_width)
+                );
+            }
+        }
+        (_, dash) => {
+            let dash = dash
+                .map(|(d, g)| format!(" stroke-dasharray=\"{} {}\"", d, g))
+                .unwrap_or_default();
+            let _ = writeln!(
+                svg,
+                "<polyline points=\"{}\" fill=\"none\" {}{}/>",
+                svg_points(points), stroke(color, width), dash
+            );
+        }
+    }
+
+    let n = points.len();
+    let mut head = |tip: (f32, f32), from: (f32, f32), filled: bool| {
+        let length = ((tip.0 - from.0).powi(2) + (tip.1 - from.1).powi(2)).sqrt().max(f32::EPSILON);
+        let (dx, dy) = ((tip.0 - from.0) / length, (tip.1 - from.1) / length);
+        let (px, py) = (-dy, dx);
+        let left = (tip.0 - dx * arrow_size + px * arrow_size * 0.5, tip.1 - dy * arrow_size + py * arrow_size * 0.5);
+        let right = (tip.0 - dx * arrow_size - px * arrow_size * 0.5, tip.1 - dy * arrow_size - py * arrow_size * 0.5);
+        if filled {
+            let _ = writeln!(svg, "<polygon points=\"{}\" {}/>", svg_points(&[tip, left, right]), fill(color));
+        } else {
+            let _ = writeln!(
+                svg,
+                "<polyline points=\"{}\" fill=\"none\" {}/>",
+                svg_points(&[left, tip, right]), stroke(color, width)
+            );
+        }
+    };
+    match arrow_head {
+        ArrowHead::None => {}
+        ArrowHead::Open => head(points[n - 1], points[n - 2], false),
+        ArrowHead::Filled => head(points[n - 1], points[n - 2], true),
+        ArrowHead::Both => {
+            head(points[n - 1], points[n - 2], false);
+            head(points[0], points[1], false);
+        }
+    }
+}
+
+/// 项目状态颜色（未知状态为灰色，与画布一致）
+fn status_color(data: &AppData, theme: &Theme, status_id: &str) -> Color32 {
+    match data.status(status_id) {
+        Some(status) => theme.status_color(status, data.status_index(status_id)),
+        None => Color32::GRAY,
+    }
+}
+
+/// 状态填充图案的间距（画布按圆大小取 2.5~6，导出统一取中间值）
+const PATTERN_SPACING: f32 = 5.0;
+
+/// 填充色上的图案ID后缀：图案墨色与画布 paint_status_pattern 一致，按填充色取深色或浅色
+fn pattern_suffix(fill_color: Color32) -> &'static str {
+    if contrast_text_color(fill_color) == Color32::BLACK { "dark" } else { "light" }
+}
+
+/// 状态填充图案的 <defs>：斜线、网格、横线、点（实心不需要图案），深浅两种墨色各一套
+fn status_pattern_defs(svg: &mut String) {
+    let s = PATTERN_SPACING;
+    svg.push_str("<defs>\n");
+    for (ink, suffix) in [(Color32::BLACK, "dark"), (Color32::WHITE, "light")] {
+        let ink = ink.gamma_multiply(0.6);
+        let line = |y: f32| format!("<line x1=\"0\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" {}/>", y, s, y, stroke(ink, 1.0));
+        let patterns = [
+            (1, 45.0, line(s / 2.0)),
+            (2, 45.0, format!("{}<line x1=\"{:.1}\" y1=\"0\" x2=\"{:.1}\" y2=\"{:.1}\" {}/>", line(s / 2.0), s / 2.0, s / 2.0, s, stroke(ink, 1.0))),
+            (3, 0.0, line(s / 2.0)),
+            (4, 0.0, format!("<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"0.8\" {}/>", s / 2.0, s / 2.0, fill(ink))),
+        ];
+        for (kind, angle, content) in patterns {
+            let _ = writeln!(
+                svg,
+                "<pattern id=\"status-{}-{}\" width=\"{:.1}\" height=\"{:.1}\" patternUnits=\"userSpaceOnUse\" patternTransform=\"rotate({})\">{}</pattern>",
+                kind, suffix, s, s, angle, content
+            );
+        }
+    }
+    svg.push_str("</defs>\n");
+}
+
+/// 按状态序号在圆内叠加填充图案（引用 status_pattern_defs 中的图案）
+fn status_pattern(svg: &mut String, cx: f32, cy: f32, radius: f32, index: usize, fill_color: Color32) {
+    let r = radius - 1.0;
+    let kind = index % 5;
+    if r <= 2.0 || kind == 0 {
+        return;
+    }
+    let _ = writeln!(
+        svg,
+        "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"url(#status-{}-{})\"/>",
+        cx, cy, r, kind, pattern_suffix(fill_color)
+    );
+}
+
+/// 按状态序号在圆右上角绘制形状符号（与画布 paint_status_glyph 一致）
+fn status_glyph(svg: &mut String, cx: f32, cy: f32, radius: f32, index: usize, ink: Color32, background: Color32) {
+    let size = (radius * 0.45).clamp(4.0, 8.0);
+    let offset = radius * std::f32::consts::FRAC_1_SQRT_2;
+    let (gx, gy) = (cx + offset, cy - offset);
+    let _ = writeln!(
+        svg,
+        "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" {} {}/>",
+        gx, gy, size + 1.5, fill(background), stroke(ink, 1.0)
+    );
+    let s = size * 0.7;
+    let p = |dx: f32, dy: f32| (gx + dx, gy + dy);
+    let _ = match index % 5 {
+        0 => writeln!(
+            svg,
+            "<polyline points=\"{}\" fill=\"none\" {}/>",
+            svg_points(&[p(-s, 0.0), p(-s * 0.3, s * 0.7), p(s, -s * 0.7)]), stroke(ink, 1.5)
+        ),
+        1 => writeln!(
+            svg,
+            "<polygon points=\"{}\" {}/>",
+            svg_points(&[p(-s * 0.6, -s), p(s, 0.0), p(-s * 0.6, s)]), fill(ink)
+        ),
+        2 => writeln!(
+            svg,
+            "<polyline points=\"{}\" fill=\"none\" {}/><circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"1.2\" {}/>",
+            svg_points(&[p(0.0, -s), p(0.0, s * 0.3)]), stroke(ink, 1.5), gx, gy + s * 0.8, fill(ink)
+        ),
+        3 => writeln!(
+            svg,
+            "<polygon points=\"{}\" {}/>",
+            svg_points(&[p(0.0, -s), p(s, 0.0), p(0.0, s), p(-s, 0.0)]), fill(ink)
+        ),
+        _ => writeln!(
+            svg,
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"none\" {}/>",
+            gx, gy, s * 0.8, stroke(ink, 1.5)
+        ),
+    };
+}
+
+/// 将维恩图渲染为 SVG 文本
+/// 使用数据坐标（1单位 = 1像素），自动框选显示的内容，图例绘制在右侧
+pub fn venn_svg(data: &AppData, theme: &Theme, settings: &AppSettings, options: &SvgOptions) -> String {
+    let projects: Vec<(usize, &Project)> = data.projects.values()
+        .enumerate()
+        .filter(|(_, p)| options.visible_project_ids.contains(&p.id))
+        .collect();
+    let fields: Vec<&ResearchField> = data.fields.values()
+        .filter(|f| options.visible_field_ids.contains(&f.id))
+        .collect();
+
+    // 内容范围（领域名称在圆圈上方，项目名称在圆圈下方）
+    let mut min_x = f32::MAX;
+    let mut max_x = f32::MIN;
+    let mut min_y = f32::MAX;
+    let mut max_y = f32::MIN;
+    for field in &fields {
+        min_x = min_x.min(field.position.0 - field.radius);
+        max_x = max_x.max(field.position.0 + field.radius);
+        min_y = min_y.min(field.position.1 - field.radius - 30.0);
+        max_y = max_y.max(field.position.1 + field.radius);
+    }
+    for (_, project) in &projects {
+        min_x = min_x.min(project.position.0 - project.radius - 40.0);
+        max_x = max_x.max(project.position.0 + project.radius + 40.0);
+        min_y = min_y.min(project.position.1 - project.radius);
+        max_y = max_y.max(project.position.1 + project.radius + if settings.show_project_tags { 40.0 } else { 25.0 });
+    }
+    // 弯曲或绕行的关系可能超出项目范围
+    let relation_paths = route::relation_paths(data, &options.visible_relation_indices, &options.visible_project_ids);
+    for (x, y) in relation_paths.values().flat_map(|path| path.points.iter()) {
+        min_x = min_x.min(*x);
+        max_x = max_x.max(*x);
+        min_y = min_y.min(*y);
+        max_y = max_y.max(*y);
+    }
+    if min_x > max_x {
+        // 没有内容
+        min_x = 0.0;
+        max_x = 400.0;
+        min_y = 0.0;
+        max_y = 300.0;
+    }
+
+    let margin = 40.0;
+    let legend_width = 140.0;
+    let legend_statuses: Vec<(usize, &ProjectStatus)> = data.sorted_statuses()
+        .into_iter()
+        .enumerate()
+        .filter(|(_, s)| options.legend_status_ids.contains(&s.id))
+        .collect();
+    let legend_relation_types = data.relation_types_used(&options.visible_relation_indices);
+    let relation_types_height = if legend_relation_types.is_empty() {
+        0.0
+    } else {
+        8.0 + 16.0 + legend_relation_types.len() as f32 * 21.0
+    };
+    let legend_fields: Vec<&ResearchField> = data.field_tree().into_iter()
+        .map(|(f, _)| f)
+        .filter(|f| options.visible_field_ids.contains(&f.id))
+        .collect();
+    let fields_height = if legend_fields.is_empty() {
+        0.0
+    } else {
+        16.0 + legend_fields.len() as f32 * 21.0 + 8.0
+    };
+    let legend_height = 12.0 + 22.0 + 3.0 + fields_height + 16.0 + legend_statuses.len() as f32 * 21.0 + 8.0 + 16.0 + 3.0 * 21.0 + relation_types_height + 12.0;
+    let content_width = max_x - min_x + margin * 2.0;
+    let width = if options.show_legend { content_width + legend_width + margin } else { content_width };
+    let height = (max_y - min_y + margin * 2.0).max(if options.show_legend { legend_height + margin * 2.0 } else { 0.0 });
+    let tx = |x: f32| x - min_x + margin;
+    let ty = |y: f32| y - min_y + margin;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"0 0 {:.0} {:.0}\" font-family=\"sans-serif\">",
+        width, height, width, height
+    );
+    let _ = writeln!(svg, "<rect width=\"100%\" height=\"100%\" {}/>", fill(options.background));
+    if settings.status_marker == StatusMarker::Pattern {
+        status_pattern_defs(&mut svg);
+    }
+
+    // 研究领域的半透明填充（先画填充再画边框，相交处颜色叠加）
+    for field in &fields {
+        let (_, fill_color) = field_colors(field);
+        if fill_color.a() > 0 {
+            let _ = writeln!(
+                svg,
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" {}/>",
+                tx(field.position.0), ty(field.position.1), field.radius, fill(fill_color)
+            );
+        }
+    }
+
+    // 研究领域
+    for field in &fields {
+        let (cx, cy) = (tx(field.position.0), ty(field.position.1));
+        let (stroke_color, _) = field_colors(field);
+        // 子领域边框更细、名称更小
+        let (border_width, font_size) = field_stroke_and_font(settings.field_border_width, data.field_depth(&field.id));
+        let _ = writeln!(
+            svg,
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"none\" {}/>",
+            cx, cy, field.radius, stroke(stroke_color, border_width)
+        );
+        // 色块样式的名称：按每个字符约一个字号宽估算底色矩形
+        let (label_color, label_background) = field_label_colors(theme, field);
+        let label_y = cy - field.radius - 12.0;
+        if let Some(background) = label_background {
+            let half_width = field.name.chars().count() as f32 * font_size / 2.0 + 5.0;
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"3\" {}/>",
+                cx - half_width, label_y - font_size, half_width * 2.0, font_size * 1.3, fill(background)
+            );
+        }
+        let _ = writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"{}\" text-anchor=\"middle\" {}>{}</text>",
+            cx, label_y, font_size, fill(label_color), escape(&field.name)
+        );
+    }
+
+    // 项目关系（走线与画布相同）
+    for (index, relation) in data.relations.iter().enumerate() {
+        let Some(path) = relation_paths.get(&index) else {
+            continue;
+        };
+        let points: Vec<(f32, f32)> = path.points.iter().map(|(x, y)| (tx(*x), ty(*y))).collect();
+        let (line_style, arrow_head) = data.relation_style(relation);
+        relation_line(&mut svg, &points, line_style, arrow_head, relation_display_color(theme, relation), relation.width, 10.0);
+        if !relation.tags.is_empty() {
+            let ((mx, my), (mdx, mdy)) = path.midpoint();
+            let _ = writeln!(
+                svg,
+                "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"10\" text-anchor=\"middle\" dominant-baseline=\"middle\" {}>{}</text>",
+                tx(mx) - mdy * 15.0, ty(my) + mdx * 15.0,
+                fill(relation_label_color(theme, data, relation)), escape(&relation.tags.join(", "))
+            );
+        }
+    }
+
+    // 项目
+    for (idx, project) in &projects {
+        let (cx, cy) = (tx(project.position.0), ty(project.position.1));
+        let color = status_color(data, theme, &project.status);
+        let border = theme.completion_percentage_to_color(project.completion_percentage);
+        let _ = writeln!(
+            svg,
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" {} {}/>",
+            cx, cy, project.radius, fill(color), stroke(border, settings.project_border_width)
+        );
+        // 非颜色编码：状态图案/符号（与画布一致）
+        let status_index = data.status_index(&project.status);
+        match settings.status_marker {
+            StatusMarker::None => {}
+            StatusMarker::Pattern => status_pattern(&mut svg, cx, cy, project.radius, status_index, color),
+            StatusMarker::Glyph => status_glyph(&mut svg, cx, cy, project.radius, status_index, theme.canvas_text, options.background),
+        }
+        if settings.show_completion_ring {
+            let r = project.radius + settings.project_border_width / 2.0 + 3.0;
+            let circumference = std::f32::consts::TAU * r;
+            let p = project.completion_percentage.clamp(0.0, 100.0) / 100.0;
+            let _ = writeln!(
+                svg,
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"none\" {}/>",
+                cx, cy, r, stroke(theme.canvas_text.gamma_multiply(0.2), 2.0)
+            );
+            let _ = writeln!(
+                svg,
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"none\" {} stroke-dasharray=\"{:.2} {:.2}\" transform=\"rotate(-90 {:.1} {:.1})\"/>",
+                cx, cy, r, stroke(theme.canvas_text, 2.0), circumference * p, circumference, cx, cy
+            );
+        }
+        if settings.show_project_numbers {
+            let _ = writeln!(
+                svg,
+                "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"10\" text-anchor=\"middle\" dominant-baseline=\"central\" {}>{}</text>",
+                cx, cy, fill(contrast_text_color(color)), idx + 1
+            );
+        }
+        if settings.show_project_names {
+            let _ = writeln!(
+                svg,
+                "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"12\" text-anchor=\"middle\" dominant-baseline=\"central\" {}>{}</text>",
+                cx, cy + project.radius + 15.0, fill(theme.canvas_text), escape(&project.name)
+            );
+        }
+        if let Some(badge) = settings.show_project_tags.then(|| project_tag_badge(&project.tags)).flatten() {
+            // 按字符估算文字宽度（汉字约为字号，其他字符约为一半）
+            let text_width: f32 = badge.chars().map(|c| if c.is_ascii() { 5.0 } else { 9.0 }).sum();
+            let (w, h) = (text_width + 8.0, 13.0);
+            let badge_y = cy + project.radius + if settings.show_project_names { 30.0 } else { 12.0 };
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"{:.1}\" {}/>",
+                cx - w / 2.0, badge_y - h / 2.0, w, h, h / 2.0, fill(theme.canvas_text.gamma_multiply(0.1))
+            );
+            let _ = writeln!(
+                svg,
+                "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"9\" text-anchor=\"middle\" dominant-baseline=\"central\" {}>{}</text>",
+                cx, badge_y, fill(theme.canvas_text_secondary), escape(&badge)
+            );
+        }
+    }
+
+    // 图例
+    if options.show_legend {
+        let x = content_width;
+        let mut y = margin;
+        let padding = 12.0;
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"4\" {} {}/>",
+            x, y, legend_width, legend_height, fill(theme.legend_background), stroke(theme.legend_border, 1.0)
+        );
+        y += padding + 11.0;
+        let _ = writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"12\" text-anchor=\"middle\" dominant-baseline=\"central\" {}>图例</text>",
+            x + legend_width / 2.0, y, fill(theme.canvas_text)
+        );
+        y += 11.0 + 3.0 + 8.0;
+        // 条目：(填充色, 边框色, 名称, 状态序号)，状态条目在启用图案/符号时放大圆圈并绘制标记
+        let section = |svg: &mut String, y: &mut f32, title: &str, items: Vec<(Color32, Color32, String, Option<usize>)>| {
+            let _ = writeln!(
+                svg,
+                "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"10\" dominant-baseline=\"central\" {}>{}</text>",
+                x + padding, *y, fill(theme.canvas_text_secondary), title
+            );
+            *y += 8.0;
+            for (fill_color, border_color, label, status_index) in items {
+                *y += 9.0;
+                let marker = status_index.filter(|_| settings.status_marker != StatusMarker::None);
+                let r = if marker.is_some() { 7.0 } else { 5.0 };
+                let cx = x + padding + r;
+                let _ = writeln!(
+                    svg,
+                    "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" {} {}/>",
+                    cx, *y, r, fill(fill_color), stroke(border_color, 1.5)
+                );
+                match (settings.status_marker, marker) {
+                    (StatusMarker::Pattern, Some(index)) => status_pattern(svg, cx, *y, r, index, fill_color),
+                    (StatusMarker::Glyph, Some(index)) => status_glyph(svg, cx, *y, r, index, theme.canvas_text, theme.legend_background),
+                    _ => {}
+                }
+                let _ = writeln!(
+                    svg,
+                    "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"9\" dominant-baseline=\"central\" {}>{}</text>",
+                    cx + r + 6.0, *y, fill(theme.canvas_text), escape(&label)
+                );
+                *y += 12.0;
+            }
+        };
+        if !legend_fields.is_empty() {
+            let field_items = legend_fields.iter()
+                .map(|f| {
+                    let (stroke_color, fill_color) = field_colors(f);
+                    (fill_color, stroke_color, f.name.clone(), None)
+                })
+                .collect();
+            section(&mut svg, &mut y, "研究领域:", field_items);
+            y += 8.0 + 8.0;
+        }
+        let status_items = legend_statuses.iter()
+            .map(|(index, s)| (theme.status_color(s, *index), Color32::GRAY, s.name.clone(), Some(*index)))
+            .collect();
+        section(&mut svg, &mut y, "项目状态:", status_items);
+        y += 8.0 + 8.0;
+        let completion_items = [0.0, 50.0, 100.0].iter()
+            .map(|p| (Color32::WHITE, theme.completion_percentage_to_color(*p), format!("{}%", *p as i32), None))
+            .collect();
+        section(&mut svg, &mut y, "完成度:", completion_items);
+        if !legend_relation_types.is_empty() {
+            y += 8.0 + 8.0;
+            let _ = writeln!(
+                svg,
+                "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"10\" dominant-baseline=\"central\" {}>关系类型:</text>",
+                x + padding, y, fill(theme.canvas_text_secondary)
+            );
+            y += 8.0;
+            for relation_type in &legend_relation_types {
+                y += 9.0;
+                let sample = [(x + padding, y), (x + padding + 22.0, y)];
+                let color = relation_type_display_color(theme, relation_type);
+                relation_line(&mut svg, &sample, relation_type.line_style, relation_type.arrow_head, color, relation_type.width.min(3.0), 6.0);
+                let _ = writeln!(
+                    svg,
+                    "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"9\" dominant-baseline=\"central\" {}>{}</text>",
+                    x + padding + 28.0, y, fill(theme.canvas_text), escape(&relation_type.name)
+                );
+                y += 12.0;
+            }
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -0,0 +1,1658 @@
+use crate::models::{AppData, Project, ResearchField};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+
+/// 项目之间的最小间距（像素）
+pub const PROJECT_SPACING: f32 = 15.0;
+/// 箭头避让距离（项目半径之外的像素）
+pub const ARROW_SPACING: f32 = 25.0;
+
+/// 网格单元格坐标
+type Cell = (i32, i32);
+
+/// 关系线段（箭头起点到终点）
+struct Segment {
+    relation_index: usize,
+    from_id: String,
+    to_id: String,
+    start: (f32, f32),
+    end: (f32, f32),
+}
+
+/// 项目圆圈和关系线段的均匀网格空间索引
+/// 单元格边长取最大项目直径加间距，位置检查只需查看附近几个单元格，
+/// 避免每个候选点都扫描全部项目和关系
+pub struct SpatialIndex {
+    cell_size: f32,
+    max_radius: f32,
+    ids: Vec<String>,
+    positions: Vec<(f32, f32)>,
+    radii: Vec<f32>,
+    slots: HashMap<String, usize>,
+    project_cells: HashMap<Cell, Vec<usize>>,
+    bounds: Option<(Cell, Cell)>,  // 有项目的单元格范围（最小, 最大），只增不减
+    segments: Vec<Segment>,
+    segment_cells: HashMap<Cell, Vec<usize>>,
+    segments_of: HashMap<String, Vec<usize>>,  // 项目ID -> 以它为端点的线段
+}
+
+impl SpatialIndex {
+    /// 按当前项目位置和关系建立索引
+    pub fn new(data: &AppData) -> Self {
+        let max_radius = data.projects.values().map(|p| p.radius).fold(0.0_f32, f32::max);
+        let mut index = Self {
+            cell_size: (max_radius * 2.0 + PROJECT_SPACING).max(40.0),
+            max_radius,
+            ids: Vec::with_capacity(data.projects.len()),
+            positions: Vec::with_capacity(data.projects.len()),
+            radii: Vec::with_capacity(data.projects.len()),
+            slots: HashMap::with_capacity(data.projects.len()),
+            project_cells: HashMap::new(),
+            bounds: None,
+            segments: Vec::with_capacity(data.relations.len()),
+            segment_cells: HashMap::new(),
+            segments_of: HashMap::new(),
+        };
+        for project in data.projects.values() {
+            index.insert_project(project);
+        }
+        for (relation_index, relation) in data.relations.iter().enumerate() {
+            if let (Some(from), Some(to)) = (
+                data.projects.get(&relation.from_id),
+                data.projects.get(&relation.to_id),
+            ) {
+                let slot = index.segments.len();
+                index.segments.push(Segment {
+                    relation_index,
+                    from_id: relation.from_id.clone(),
+                    to_id: relation.to_id.clone(),
+                    start: from.position,
+                    end: to.position,
+                });
+                index.segments_of.entry(relation.from_id.clone()).or_default().push(slot);
+                index.segments_of.entry(relation.to_id.clone()).or_default().push(slot);
+                index.insert_segment(slot);
+            }
+        }
+        index
+    }
+
+    fn cell_of(&self, (x, y): (f32, f32)) -> Cell {
+        ((x / self.cell_size).floor() as i32, (y / self.cell_size).floor() as i32)
+    }
+
+    /// 与以 (x, y) 为中心、边长 2*reach 的正方形相交的单元格
+    fn cells_around(&self, x: f32, y: f32, reach: f32) -> impl Iterator<Item = Cell> {
+        let (min_x, min_y) = self.cell_of((x - reach, y - reach));
+        let (max_x, max_y) = self.cell_of((x + reach, y + reach));
+        (min_x..=max_x).flat_map(move |cx| (min_y..=max_y).map(move |cy| (cx, cy)))
+    }
+
+    fn insert_project(&mut self, project: &Project) {
+        let slot = self.ids.len();
+        self.ids.push(project.id.clone());
+        self.positions.push(project.position);
+        self.radii.push(project.radius);
+        self.slots.insert(project.id.clone(), slot);
+        self.max_radius = self.max_radius.max(project.radius);
+        self.add_to_cell(slot);
+    }
+
+    fn add_to_cell(&mut self, slot: usize) {
+        let cell = self.cell_of(self.positions[slot]);
+        self.project_cells.entry(cell).or_default().push(slot);
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => ((min.0.min(cell.0), min.1.min(cell.1)), (max.0.max(cell.0), max.1.max(cell.1))),
+            None => (cell, cell),
+        });
+    }
+
+    /// 线段经过的单元格（网格步进遍历）
+    fn cells_on_segment(&self, start: (f32, f32), end: (f32, f32)) -> Vec<Cell> {
+        let (mut cx, mut cy) = self.cell_of(start);
+        let (ex, ey) = self.cell_of(end);
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let step_x = if dx > 0.0 { 1 } else { -1 };
+        let step_y = if dy > 0.0 { 1 } else { -1 };
+        // 沿 x/y 方向跨过一个单元格所需的参数增量，以及到下一条网格线的参数
+        let t_delta_x = if dx != 0.0 { self.cell_size / dx.abs() } else { f32::INFINITY };
+        let t_delta_y = if dy != 0.0 { self.cell_size / dy.abs() } else { f32::INFINITY };
+        let boundary_x = if dx > 0.0 { (cx + 1) as f32 * self.cell_size - start.0 } else { start.0 - cx as f32 * self.cell_size };
+        let boundary_y = if dy > 0.0 { (cy + 1) as f32 * self.cell_size - start.1 } else { start.1 - cy as f32 * self.cell_size };
+        let mut t_max_x = if dx != 0.0 { boundary_x / dx.abs() } else { f32::INFINITY };
+        let mut t_max_y = if dy != 0.0 { boundary_y / dy.abs() } else { f32::INFINITY };
+
+        let steps = (ex - cx).unsigned_abs() + (ey - cy).unsigned_abs();
+        let mut cells = Vec::with_capacity(steps as usize + 1);
+        cells.push((cx, cy));
+        for _ in 0..steps {
+            if t_max_x < t_max_y {
+                t_max_x += t_delta_x;
+                cx += step_x;
+            } else {
+                t_max_y += t_delta_y;
+                cy += step_y;
+            }
+            cells.push((cx, cy));
+        }
+        cells
+    }
+
+    fn insert_segment(&mut self, slot: usize) {
+        let segment = &self.segments[slot];
+        for cell in self.cells_on_segment(segment.start, segment.end) {
+            self.segment_cells.entry(cell).or_default().push(slot);
+        }
+    }
+
+    fn remove_segment(&mut self, slot: usize) {
+        let segment = &self.segments[slot];
+        for cell in self.cells_on_segment(segment.start, segment.end) {
+            if let Some(slots) = self.segment_cells.get_mut(&cell) {
+                slots.retain(|s| *s != slot);
+            }
+        }
+    }
+
+    /// 更新项目位置（不在索引中的项目会被加入），同时更新以它为端点的关系线段
+    pub fn move_project(&mut self, project: &Project) {
+        let Some(&slot) = self.slots.get(&project.id) else {
+            self.insert_project(project);
+            return;
+        };
+        let old_cell = self.cell_of(self.positions[slot]);
+        if let Some(slots) = self.project_cells.get_mut(&old_cell) {
+            slots.retain(|s| *s != slot);
+        }
+        self.positions[slot] = project.position;
+        self.radii[slot] = project.radius;
+        self.max_radius = self.max_radius.max(project.radius);
+        self.add_to_cell(slot);
+
+        for segment_slot in self.segments_of.get(&project.id).cloned().unwrap_or_default() {
+            self.remove_segment(segment_slot);
+            let segment = &mut self.segments[segment_slot];
+            if segment.from_id == project.id {
+                segment.start = project.position;
+            }
+            if segment.to_id == project.id {
+                segment.end = project.position;
+            }
+            self.insert_segment(segment_slot);
+        }
+    }
+
+    /// 圆心在 (x, y) 附近 reach 范围内可能存在的项目（按单元格粗筛，序号为索引内部编号）
+    fn projects_near(&self, x: f32, y: f32, reach: f32) -> impl Iterator<Item = usize> + '_ {
+        self.cells_around(x, y, reach)
+            .filter_map(|cell| self.project_cells.get(&cell))
+            .flat_map(|slots| slots.iter().copied())
+    }
+
+    /// 以 (x, y) 为圆心、radius 为半径的圆是否与除 exclude_id 外的项目重叠（含间距）
+    pub fn overlaps_project(&self, x: f32, y: f32, radius: f32, spacing: f32, exclude_id: &str) -> bool {
+        self.projects_near(x, y, radius + self.max_radius + spacing).any(|slot| {
+            if self.ids[slot] == exclude_id {
+                return false;
+            }
+            let (px, py) = self.positions[slot];
+            let distance = ((x - px).powi(2) + (y - py).powi(2)).sqrt();
+            distance < radius + self.radii[slot] + spacing
+        })
+    }
+
+    /// 到最近项目圆心的距离（不含 exclude_id），没有其他项目时返回 None
+    /// 从所在单元格逐圈向外查找，找到后再多查看能更近的一圈即停止
+    pub fn nearest_project_distance(&self, x: f32, y: f32, exclude_id: &str) -> Option<f32> {
+        let (min, max) = self.bounds?;
+        let (cx, cy) = self.cell_of((x, y));
+        let max_ring = (cx - min.0).abs().max((max.0 - cx).abs())
+            .max((cy - min.1).abs())
+            .max((max.1 - cy).abs());
+        let mut best: Option<f32> = None;
+        for ring in 0..=max_ring {
+            // 第 ring 圈中的点到 (x, y) 的距离至少为 (ring - 1) 个单元格
+            if let Some(distance) = best {
+                if (ring - 1) as f32 * self.cell_size > distance {
+                    break;
+                }
+            }
+            for gx in (cx - ring)..=(cx + ring) {
+                for gy in (cy - ring)..=(cy + ring) {
+                    if (gx - cx).abs() != ring && (gy - cy).abs() != ring {
+                        continue;
+                    }
+                    let Some(slots) = self.project_cells.get(&(gx, gy)) else {
+                        continue;
+                    };
+                    for &slot in slots {
+                        if self.ids[slot] == exclude_id {
+                            continue;
+                        }
+                        let (px, py) = self.positions[slot];
+                        let distance = ((x - px).powi(2) + (y - py).powi(2)).sqrt();
+                        if !best.is_some_and(|b| distance >= b) {
+                            best = Some(distance);
+                        }
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// (x, y) 附近 reach 范围内可能经过的关系线段（去重，序号为索引内部编号）
+    fn segments_near(&self, x: f32, y: f32, reach: f32) -> Vec<usize> {
+        let mut slots: Vec<usize> = self.cells_around(x, y, reach)
+            .filter_map(|cell| self.segment_cells.get(&cell))
+            .flat_map(|slots| slots.iter().copied())
+            .collect();
+        slots.sort_unstable();
+        slots.dedup();
+        slots
+    }
+
+    /// (x, y) 是否离某条关系线段小于 threshold（跳过以 exclude_id 为端点的关系）
+    pub fn near_relation(&self, x: f32, y: f32, threshold: f32, exclude_id: &str) -> bool {
+        self.segments_near(x, y, threshold).into_iter().any(|slot| {
+            let segment = &self.segments[slot];
+            if segment.from_id == exclude_id || segment.to_id == exclude_id {
+                return false;
+            }
+            point_segment_distance((x, y), segment.start, segment.end)
+                .is_some_and(|(distance, _)| distance < threshold)
+        })
+    }
+
+    /// (x, y) 附近小于 threshold 的关系（跳过以 exclude_id 为端点的关系），返回关系在数据中的序号
+    pub fn relations_near(&self, x: f32, y: f32, threshold: f32, exclude_id: &str) -> Vec<usize> {
+        self.segments_near(x, y, threshold).into_iter()
+            .filter(|&slot| {
+                let segment = &self.segments[slot];
+                segment.from_id != exclude_id
+                    && segment.to_id != exclude_id
+                    && point_segment_distance((x, y), segment.start, segment.end)
+                        .is_some_and(|(distance, _)| distance < threshold)
+            })
+            .map(|slot| self.segments[slot].relation_index)
+            .collect()
+    }
+
+    /// 圆圈离线段小于 clearance 的项目（项目ID, 圆心, 半径），按数据顺序
+    pub fn projects_along(&self, start: (f32, f32), end: (f32, f32), clearance: f32) -> Vec<(&str, (f32, f32), f32)> {
+        // 单元格边长不小于最大项目直径加间距，线段经过的单元格及其相邻单元格即可覆盖
+        let mut slots: Vec<usize> = self.cells_on_segment(start, end).into_iter()
+            .flat_map(|(cx, cy)| (cx - 1..=cx + 1).flat_map(move |gx| (cy - 1..=cy + 1).map(move |gy| (gx, gy))))
+            .filter_map(|cell| self.project_cells.get(&cell))
+            .flat_map(|slots| slots.iter().copied())
+            .collect();
+        slots.sort_unstable();
+        slots.dedup();
+        slots.into_iter()
+            .filter(|&slot| {
+                let distance = point_segment_distance(self.positions[slot], start, end)
+                    .map_or_else(|| ((self.positions[slot].0 - start.0).powi(2) + (self.positions[slot].1 - start.1).powi(2)).sqrt(), |(d, _)| d);
+                distance < self.radii[slot] + clearance
+            })
+            .map(|slot| (self.ids[slot].as_str(), self.positions[slot], self.radii[slot]))
+            .collect()
+    }
+
+    /// 所有重叠（含间距）的项目对（按数据顺序，每对只出现一次）
+    pub fn overlapping_pairs(&self, spacing: f32) -> Vec<(String, String)> {
+        let mut pairs = Vec::new();
+        for slot in 0..self.ids.len() {
+            let (x, y) = self.positions[slot];
+            let radius = self.radii[slot];
+            for other in self.projects_near(x, y, radius + self.max_radius + spacing) {
+                if other <= slot {
+                    continue;
+                }
+                let (ox, oy) = self.positions[other];
+                let distance = ((x - ox).powi(2) + (y - oy).powi(2)).sqrt();
+                if distance < radius + self.radii[other] + spacing {
+                    pairs.push((self.ids[slot].clone(), self.ids[other].clone()));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// 是否有任意两个项目重叠（含间距）
+    pub fn any_overlap(&self, spacing: f32) -> bool {
+        (0..self.ids.len()).any(|slot| {
+            let (x, y) = self.positions[slot];
+            let radius = self.radii[slot];
+            self.projects_near(x, y, radius + self.max_radius + spacing).any(|other| {
+                if other <= slot {
+                    return false;
+                }
+                let (ox, oy) = self.positions[other];
+                let distance = ((x - ox).powi(2) + (y - oy).powi(2)).sqrt();
+                distance < radius + self.radii[other] + spacing
+            })
+        })
+    }
+}
+
+/// 点到线段的距离及线段上的最近点；线段太短时返回 None
+fn point_segment_distance(
+    (px, py): (f32, f32),
+    (x1, y1): (f32, f32),
+    (x2, y2): (f32, f32),
+) -> Option<(f32, (f32, f32))> {
+    let line_len_sq = (x2 - x1).powi(2) + (y2 - y1).powi(2);
+    if line_len_sq < 1.0 {
+        return None;  // 线段太短
+    }
+    // 计算点到线段的最近点参数 t
+    let t = (((px - x1) * (x2 - x1) + (py - y1) * (y2 - y1)) / line_len_sq).clamp(0.0, 1.0);
+    let nearest = (x1 + t * (x2 - x1), y1 + t * (y2 - y1));
+    Some((((px - nearest.0).powi(2) + (py - nearest.1).powi(2)).sqrt(), nearest))
+}
+
+/// 检查项目是否在正确的区域内（不检查与其他项目的重叠）
+/// 有领域的项目圆圈必须完全在所有目标领域内（留5像素边距），且完全在非目标领域外；
+/// 目标领域包括所选子领域的各级上级领域；无领域的项目必须在所有领域之外
+pub fn is_in_correct_region(data: &AppData, project: &Project) -> bool {
+    let (x, y) = project.position;
+    let project_radius = project.radius;
+    let member_ids = data.effective_field_ids(project);
+
+    for field in data.fields.values() {
+        let distance = ((x - field.position.0).powi(2) + (y - field.position.1).powi(2)).sqrt();
+        if member_ids.contains(&field.id) {
+            // 项目圆圈最远点到领域中心的距离必须 <= 领域半径 - 5
+            if distance + project_radius > field.radius - 5.0 {
+                return false;
+            }
+        } else if distance - project_radius < field.radius {
+            // 项目圆圈最近点必须在非目标领域外
+            return false;
+        }
+    }
+    true
+}
+
+/// 检查项目位置是否正确：区域正确，且与其他项目保持间距
+pub fn is_position_valid(data: &AppData, index: &SpatialIndex, project: &Project) -> bool {
+    let (x, y) = project.position;
+    is_in_correct_region(data, project)
+        && !index.overlaps_project(x, y, project.radius, PROJECT_SPACING, &project.id)
+}
+
+/// 目标领域中最内层的领域（不是其他目标领域的上级），用于确定放置的中心和方向
+fn innermost_fields<'a>(data: &'a AppData, target_fields: &[&'a ResearchField]) -> Vec<&'a ResearchField> {
+    target_fields.iter()
+        .filter(|f| !target_fields.iter().any(|other| data.field_ancestors(&other.id).contains(&f.id)))
+        .copied()
+        .collect()
+}
+
+/// 找到离当前位置最近的正确位置
+pub fn find_nearest_valid_position(data: &AppData, index: &SpatialIndex, project: &Project) -> (f32, f32) {
+    let (current_x, current_y) = project.position;
+    let project_radius = project.radius;
+
+    // 无领域项目：找到所有领域之外的位置
+    if project.field_ids.is_empty() {
+        let mut best_pos = project.position;
+        let mut best_dist = f32::MAX;
+
+        for attempt in 0..500 {
+            let angle = (attempt as f32) * 0.618 * std::f32::consts::PI * 2.0;
+            let radius = (attempt as f32).sqrt() * 5.0;
+
+            let x = current_x + radius * angle.cos();
+            let y = current_y + radius * angle.sin();
+
+            // 检查是否在所有领域之外
+            let outside_all = data.fields.values().all(|field| {
+                let distance = ((x - field.position.0).powi(2) + (y - field.position.1).powi(2)).sqrt();
+                distance - project_radius >= field.radius
+            });
+            if !outside_all {
+                continue;
+            }
+
+            // 检查与其他项目的距离
+            if index.overlaps_project(x, y, project_radius, PROJECT_SPACING, &project.id) {
+                continue;
+            }
+
+            let dist = ((x - current_x).powi(2) + (y - current_y).powi(2)).sqrt();
+            if dist < best_dist {
+                best_dist = dist;
+                best_pos = (x, y);
+                if dist < 1.0 {
+                    break;
+                }
+            }
+        }
+
+        return best_pos;
+    }
+
+    // 收集目标领域（含上级领域）
+    let member_ids = data.effective_field_ids(project);
+    let target_fields: Vec<&ResearchField> = member_ids.iter()
+        .filter_map(|id| data.fields.get(id))
+        .collect();
+
+    if target_fields.is_empty() {
+        return project.position;
+    }
+
+    // 收集非目标领域
+    let non_target_fields: Vec<&ResearchField> = data.fields.values()
+        .filter(|f| !member_ids.contains(&f.id))
+        .collect();
+
+    let mut best_pos = project.position;
+    let mut best_dist = f32::MAX;
+
+    // 计算最内层目标领域的中心（上级领域包含子领域，不参与计算）
+    let innermost = innermost_fields(data, &target_fields);
+    let mut target_center_x = 0.0;
+    let mut target_center_y = 0.0;
+    for field in &innermost {
+        target_center_x += field.position.0;
+        target_center_y += field.position.1;
+    }
+    target_center_x /= innermost.len() as f32;
+    target_center_y /= innermost.len() as f32;
+
+    // 计算目标领域的最小半径（用于从边缘搜索）
+    let min_target_radius = innermost.iter()
+        .map(|f| f.radius)
+        .min_by(|a, b| a.partial_cmp(b).unwrap())
+        .unwrap_or(100.0);
+
+    // 从当前位置和目标领域边缘两个起点搜索
+    // 边缘起点：远离中心的方向
+    let edge_angle = (current_y - target_center_y).atan2(current_x - target_center_x);
+    let edge_x = target_center_x + (min_target_radius * 0.6) * edge_angle.cos();
+    let edge_y = target_center_y + (min_target_radius * 0.6) * edge_angle.sin();
+
+    let search_starts = [(current_x, current_y), (edge_x, edge_y)];
+
+    for (start_x, start_y) in search_starts {
+        // 螺旋搜索找最近的有效位置
+        for attempt in 0..500 {
+            let angle = (attempt as f32) * 0.618 * std::f32::consts::PI * 2.0;
+            let radius = (attempt as f32).sqrt() * 8.0;  // 增大搜索步长
+
+            let x = start_x + radius * angle.cos();
+            let y = start_y + radius * angle.sin();
+
+            // 检查整个项目圆圈是否完全在所有目标领域内
+            let in_all_targets = target_fields.iter().all(|field| {
+                let distance = ((x - field.position.0).powi(2) + (y - field.position.1).powi(2)).sqrt();
+                // 项目圆圈最远点必须在领域内
+                distance + project_radius <= field.radius - 5.0
+            });
+            if !in_all_targets {
+                continue;
+            }
+
+            // 检查整个项目圆圈是否完全在非目标领域外
+            let in_non_target = non_target_fields.iter().any(|field| {
+                let distance = ((x - field.position.0).powi(2) + (y - field.position.1).powi(2)).sqrt();
+                // 项目圆圈最近点必须在非目标领域外
+                distance - project_radius < field.radius
+            });
+            if in_non_target {
+                continue;
+            }
+
+            // 检查与其他项目的距离（避免重叠，保持间距）
+            if index.overlaps_project(x, y, project_radius, PROJECT_SPACING, &project.id) {
+                continue;
+            }
+
+            // 计算到原位置的距离
+            let dist = ((x - current_x).powi(2) + (y - current_y).powi(2)).sqrt();
+            if dist < best_dist {
+                best_dist = dist;
+                best_pos = (x, y);
+                if dist < 1.0 {
+                    break;  // 足够近了
+                }
+            }
+        }
+    }
+
+    best_pos
+}
+
+/// 计算新项目的位置，根据所属领域自动布局，并避免与已有项目重叠
+/// 单领域项目会放在远离其他领域的方向，避免被误认为在交叉区域；锁定的项目保持原位置
+pub fn calculate_project_position(data: &AppData, index: &SpatialIndex, project: &Project) -> (f32, f32) {
+    if project.locked {
+        return project.position;
+    }
+    let project_radius = project.radius;
+    // 箭头避让距离：项目半径 + 25像素间距
+    let arrow_threshold = project_radius + ARROW_SPACING;
+
+    // 如果项目没有指定领域，放在所有领域之外
+    if project.field_ids.is_empty() {
+        // 计算所有领域的边界
+        let mut max_x = 0.0_f32;
+        let mut max_y = 0.0_f32;
+        for field in data.fields.values() {
+            max_x = max_x.max(field.position.0 + field.radius);
+            max_y = max_y.max(field.position.1 + field.radius);
+        }
+
+        // 在右下角外侧找位置
+        let start_x = max_x + 50.0;
+        let start_y = max_y + 50.0;
+
+        // 螺旋搜索找不重叠的位置
+        for attempt in 0..200 {
+            let angle = (attempt as f32) * 0.618 * std::f32::consts::PI * 2.0;
+            let radius = (attempt as f32).sqrt() * 10.0;
+
+            let x = start_x + radius * angle.cos();
+            let y = start_y + radius * angle.sin();
+
+            // 检查是否在所有领域之外
+            let outside_all = data.fields.values().all(|field| {
+                let distance = ((x - field.position.0).powi(2) + (y - field.position.1).powi(2)).sqrt();
+                distance - project_radius >= field.radius
+            });
+            if !outside_all {
+                continue;
+            }
+
+            // 检查与其他项目的距离
+            if !index.overlaps_project(x, y, project_radius, PROJECT_SPACING, &project.id) {
+                return (x, y);
+            }
+        }
+
+        return (start_x, start_y);
+    }
+
+    // 计算所属领域的中心位置（所选子领域的上级领域也是目标领域）
+    let member_ids = data.effective_field_ids(project);
+    let target_fields: Vec<&ResearchField> = member_ids.iter()
+        .filter_map(|id| data.fields.get(id))
+        .collect();
+
+    if target_fields.is_empty() {
+        return (400.0, 400.0);
+    }
+
+    // 计算多个领域的平均中心位置（只看最内层的目标领域）
+    let innermost = innermost_fields(data, &target_fields);
+    let center_x = innermost.iter().map(|f| f.position.0).sum::<f32>() / innermost.len() as f32;
+    let center_y = innermost.iter().map(|f| f.position.1).sum::<f32>() / innermost.len() as f32;
+
+    // 单领域（或单个子领域及其上级）：在最内层领域内找最空白的位置
+    if innermost.len() == 1 {
+        let target_field = innermost[0];
+
+        // 收集非目标领域和上级领域
+        let other_fields: Vec<&ResearchField> = data.fields.values()
+            .filter(|f| !member_ids.contains(&f.id))
+            .collect();
+        let ancestor_fields: Vec<&ResearchField> = target_fields.iter()
+            .filter(|f| f.id != target_field.id)
+            .copied()
+            .collect();
+        let outside_others = |x: f32, y: f32| {
+            other_fields.iter().all(|other| {
+                let dist_to_other = ((x - other.position.0).powi(2) + (y - other.position.1).powi(2)).sqrt();
+                // 项目圆圈最近点到领域中心的距离 = dist_to_other - project_radius
+                // 必须 >= other.radius（项目圆圈完全在非目标领域外）
+                dist_to_other - project_radius >= other.radius
+            }) && ancestor_fields.iter().all(|ancestor| {
+                // 子领域超出上级领域时，项目圆圈仍须在上级领域内
+                let dist_to_ancestor = ((x - ancestor.position.0).powi(2) + (y - ancestor.position.1).powi(2)).sqrt();
+                dist_to_ancestor + project_radius <= ancestor.radius - 5.0
+            })
+        };
+
+        // 安全边距
+        let safe_margin = project_radius + PROJECT_SPACING;
+        let max_safe_radius = target_field.radius - safe_margin;
+
+        // 生成候选位置并评分（离已有项目越远越好）
+        let golden_angle = 2.399_963_3;  // 黄金角 ≈ 137.5°
+        let mut best_pos: Option<(f32, f32)> = None;
+        let mut best_min_dist = 0.0_f32;
+
+        // 遍历多个候选位置
+        let max_attempts = 200;
+        for attempt in 0..max_attempts {
+            // 黄金角螺旋覆盖整个领域
+            let angle = (attempt as f32) * golden_angle;
+
+            // 半径从边缘向内，覆盖整个领域
+            let radius_factor = 0.8 - (attempt as f32 / max_attempts as f32) * 0.6;
+            let radius = (max_safe_radius * radius_factor).max(project_radius + 10.0);
+
+            let x = target_field.position.0 + radius * angle.cos();
+            let y = target_field.position.1 + radius * angle.sin();
+
+            // 检查是否在领域内
+            let distance_from_center = ((x - target_field.position.0).powi(2) + (y - target_field.position.1).powi(2)).sqrt();
+            if distance_from_center > max_safe_radius {
+                continue;
+            }
+
+            // 检查整个项目圆圈是否与其他领域交叉（项目圆圈不能进入非目标领域）
+            if !outside_others(x, y) {
+                continue;
+            }
+
+            // 检查是否与已有项目重叠（保持间距）
+            if index.overlaps_project(x, y, project_radius, PROJECT_SPACING, &project.id) {
+                continue;
+            }
+
+            // 检查是否被箭头穿过（跳过与当前项目相关的箭头）
+            if index.near_relation(x, y, arrow_threshold, &project.id) {
+                continue;
+            }
+
+            // 这是一个有效位置，记录离已有项目最远的位置
+            let min_dist_to_existing = index.nearest_project_distance(x, y, &project.id).unwrap_or(f32::MAX);
+            if min_dist_to_existing > best_min_dist {
+                best_min_dist = min_dist_to_existing;
+                best_pos = Some((x, y));
+            }
+        }
+
+        // 返回最空白的位置
+        if let Some(pos) = best_pos {
+            return pos;
+        }
+
+        // 回退：扩大搜索范围，放宽重叠和箭头限制，但仍严格检查领域边界
+        for attempt in 0..200 {
+            let angle = (attempt as f32) * 0.618 * std::f32::consts::PI * 2.0;
+            let radius = (attempt as f32).sqrt() * 10.0;
+
+            let x = target_field.position.0 + radius * angle.cos();
+            let y = target_field.position.1 + radius * angle.sin();
+
+            // 检查是否在目标领域内
+            let distance_from_center = ((x - target_field.position.0).powi(2) + (y - target_field.position.1).powi(2)).sqrt();
+            if distance_from_center + project_radius > target_field.radius - 5.0 {
+                continue;
+            }
+
+            // 严格检查：整个项目圆圈不能进入非目标领域
+            if outside_others(x, y) {
+                return (x, y);
+            }
+        }
+
+        // 最终回退：领域中心（不理想但至少在目标领域内）
+        return target_field.position;
+    }
+
+    // 多领域：找到真正的交集区域中心，同时远离非目标领域
+    // 收集非目标领域
+    let non_target_fields: Vec<&ResearchField> = data.fields.values()
+        .filter(|f| !member_ids.contains(&f.id))
+        .collect();
+
+    // 使用迭代方法找到所有领域的交集中心，同时尽量远离非目标领域
+    let mut intersection_center_x = center_x;
+    let mut intersection_center_y = center_y;
+
+    // 迭代优化：将中心点移向所有目标领域都能覆盖且远离非目标领域的位置
+    for _ in 0..100 {
+        let mut move_x = 0.0;
+        let mut move_y = 0.0;
+        let mut need_move = false;
+
+        // 确保在所有目标领域内
+        for field in &target_fields {
+            let dx = intersection_center_x - field.position.0;
+            let dy = intersection_center_y - field.position.1;
+            let dist = (dx * dx + dy * dy).sqrt();
+            let max_dist = field.radius - project_radius - PROJECT_SPACING;
+
+            if dist > max_dist && dist > 0.0 {
+                let ratio = (dist - max_dist) / dist;
+                move_x -= dx * ratio * 0.5;
+                move_y -= dy * ratio * 0.5;
+                need_move = true;
+            }
+        }
+
+        // 远离非目标领域
+        for non_target in &non_target_fields {
+            let dx = intersection_center_x - non_target.position.0;
+            let dy = intersection_center_y - non_target.position.1;
+            let dist = (dx * dx + dy * dy).sqrt();
+            // 如果太靠近非目标领域，向外推
+            if dist < non_target.radius + project_radius + 20.0 && dist > 0.0 {
+                let push_strength = (non_target.radius + project_radius + 20.0 - dist) / dist;
+                move_x += dx * push_strength * 0.3;
+                move_y += dy * push_strength * 0.3;
+                need_move = true;
+            }
+        }
+
+        if !need_move {
+            break;
+        }
+
+        intersection_center_x += move_x;
+        intersection_center_y += move_y;
+    }
+
+    // 从交集中心开始螺旋搜索
+    for attempt in 0..100 {
+        let angle = (attempt as f32) * 0.618 * std::f32::consts::PI * 2.0;  // 黄金角
+        let radius = (attempt as f32).sqrt() * 8.0;  // 螺旋扩展
+
+        let x = intersection_center_x + radius * angle.cos();
+        let y = intersection_center_y + radius * angle.sin();
+
+        // 检查整个项目圆圈是否完全在所有目标领域内（留10像素边距）
+        let in_all_fields = target_fields.iter().all(|field| {
+            let distance = ((x - field.position.0).powi(2) + (y - field.position.1).powi(2)).sqrt();
+            distance + project_radius <= field.radius - 10.0
+        });
+        if !in_all_fields {
+            continue;
+        }
+
+        // 检查整个项目圆圈是否完全在非目标领域外
+        let in_non_target_field = non_target_fields.iter().any(|field| {
+            let distance = ((x - field.position.0).powi(2) + (y - field.position.1).powi(2)).sqrt();
+            distance - project_radius < field.radius
+        });
+        if in_non_target_field {
+            continue;
+        }
+
+        // 检查是否与已有项目重叠（保持间距）
+        if index.overlaps_project(x, y, project_radius, PROJECT_SPACING, &project.id) {
+            continue;
+        }
+
+        // 检查是否被箭头穿过
+        if index.near_relation(x, y, arrow_threshold, &project.id) {
+            continue;
+        }
+
+        return (x, y);
+    }
+
+    // 最后的回退：返回交集中心位置
+    (intersection_center_x, intersection_center_y)
+}
+
+/// 布局进度回调：参数为完成比例（0~1）和当前数据，返回 false 时中止计算
+pub type Progress<'a> = &'a mut dyn FnMut(f32, &AppData) -> bool;
+
+/// 依次为指定项目重新计算位置，每放好一个就更新索引，后面的项目会避开它
+pub fn place_projects(data: &mut AppData, project_ids: &[String]) {
+    place_projects_with_progress(data, project_ids, &mut |_, _| true);
+}
+
+/// 带进度回调的 place_projects，被中止时返回 None
+pub fn place_projects_with_progress(data: &mut AppData, project_ids: &[String], progress: Progress) -> Option<()> {
+    let mut index = SpatialIndex::new(data);
+    let report_every = (project_ids.len() / 20).max(1);
+    for (placed, id) in project_ids.iter().enumerate() {
+        if placed % report_every == 0 && !progress(placed as f32 / project_ids.len() as f32, data) {
+            return None;
+        }
+        let Some(project) = data.projects.get(id) else {
+            continue;
+        };
+        let position = calculate_project_position(data, &index, project);
+        if let Some(project) = data.projects.get_mut(id) {
+            project.position = position;
+            index.move_project(project);
+        }
+    }
+    progress(1.0, data).then_some(())
+}
+
+/// 把区域不正确的项目移到最近的正确位置（锁定的项目除外），返回是否有项目被移动
+fn fix_regions(data: &mut AppData, project_ids: &[String]) -> bool {
+    let mut index = SpatialIndex::new(data);
+    let mut changed = false;
+    for id in project_ids {
+        let Some(project) = data.projects.get(id) else {
+            continue;
+        };
+        if project.locked || is_in_correct_region(data, project) {
+            continue;
+        }
+        let position = find_nearest_valid_position(data, &index, project);
+        if let Some(project) = data.projects.get_mut(id) {
+            project.position = position;
+            index.move_project(project);
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// 验证并修正所有项目位置（使用物理引擎思想），返回是否有改动
+/// 先把项目移回正确区域，再迭代计算项目间和箭头对项目的斥力解决重叠，仍有重叠时扩展领域；
+/// 锁定的项目不移动，但仍推开靠近它的其他项目
+pub fn fix_positions(data: &mut AppData) -> bool {
+    fix_positions_with_progress(data, &mut |_, _| true).unwrap_or(false)
+}
+
+/// 带进度回调的 fix_positions，每轮物理迭代报告一次，被中止时返回 None
+pub fn fix_positions_with_progress(data: &mut AppData, progress: Progress) -> Option<bool> {
+    // 第一步：先确保所有项目在正确的区域内
+    let project_ids: Vec<String> = data.projects.keys().cloned().collect();
+    let mut changed = fix_regions(data, &project_ids);
+
+    // 第二步：物理引擎迭代，解决重叠问题
+    let physics_iterations = 50;
+    let damping = 0.8;  // 阻尼系数
+
+    for iteration in 0..physics_iterations {
+        if !progress(0.1 + 0.8 * iteration as f32 / physics_iterations as f32, data) {
+            return None;
+        }
+        // 索引内部编号与 project_ids 顺序一致
+        let index = SpatialIndex::new(data);
+        let mut forces = vec![(0.0_f32, 0.0_f32); project_ids.len()];
+
+        for (slot, force) in forces.iter_mut().enumerate() {
+            let (x, y) = index.positions[slot];
+            let radius = index.radii[slot];
+
+            // 计算项目之间的斥力
+            for other in index.projects_near(x, y, radius + index.max_radius + PROJECT_SPACING) {
+                if other == slot {
+                    continue;
+                }
+                let dx = x - index.positions[other].0;
+                let dy = y - index.positions[other].1;
+                let distance = (dx * dx + dy * dy).sqrt().max(1.0);
+                let min_dist = radius + index.radii[other] + PROJECT_SPACING;
+                if distance < min_dist {
+                    // 斥力大小与重叠量成正比，方向远离对方
+                    let force_magnitude = (min_dist - distance) * 0.5;
+                    force.0 += (dx / distance) * force_magnitude;
+                    force.1 += (dy / distance) * force_magnitude;
+                }
+            }
+
+            // 计算箭头对项目的斥力（跳过以该项目为端点的箭头）
+            let threshold = radius + ARROW_SPACING;
+            for segment_slot in index.segments_near(x, y, threshold) {
+                let segment = &index.segments[segment_slot];
+                if segment.from_id == index.ids[slot] || segment.to_id == index.ids[slot] {
+                    continue;
+                }
+                let Some((dist, nearest)) = point_segment_distance((x, y), segment.start, segment.end) else {
+                    continue;
+                };
+                if dist < threshold {
+                    let dist = dist.max(1.0);
+                    let force_magnitude = (threshold - dist) * 0.3;
+                    force.0 += ((x - nearest.0) / dist) * force_magnitude;
+                    force.1 += ((y - nearest.1) / dist) * force_magnitude;
+                }
+            }
+        }
+
+        // 应用力并检查边界约束
+        let mut any_moved = false;
+        for (id, (fx, fy)) in project_ids.iter().zip(forces) {
+            if fx.abs() <= 0.1 && fy.abs() <= 0.1 {
+                continue;
+            }
+            let Some(project) = data.projects.get(id).filter(|p| !p.locked) else {
+                continue;
+            };
+            let (old_x, old_y) = project.position;
+            let mut new_x = old_x + fx * damping;
+            let mut new_y = old_y + fy * damping;
+
+            // 边界约束：确保项目仍在正确区域内
+            let mut temp_project = project.clone();
+            temp_project.position = (new_x, new_y);
+            if !is_in_correct_region(data, &temp_project) {
+                // 如果移动后不在正确区域，尝试只移动一个方向
+                temp_project.position = (new_x, old_y);
+                if is_in_correct_region(data, &temp_project) {
+                    new_y = old_y;
+                } else {
+                    temp_project.position = (old_x, new_y);
+                    if is_in_correct_region(data, &temp_project) {
+                        new_x = old_x;
+                    } else {
+                        // 两个方向都不行，不移动
+                        continue;
+                    }
+                }
+            }
+
+            if let Some(p) = data.projects.get_mut(id) {
+                p.position = (new_x, new_y);
+                any_moved = true;
+                changed = true;
+            }
+        }
+
+        if !any_moved {
+            break;  // 已达到平衡
+        }
+    }
+
+    // 第三步：检查是否仍有重叠，如果有则扩展领域，扩展后重新验证位置
+    if SpatialIndex::new(data).any_overlap(PROJECT_SPACING) {
+        expand_fields(data);
+        fix_regions(data, &project_ids);
+        changed = true;
+    }
+
+    progress(1.0, data).then_some(changed)
+}
+
+/// 扩展领域大小以容纳更多项目（锁定的领域不扩展）
+/// 子领域扩展时其上级领域同步扩展，最后把子领域收回上级领域内
+pub fn expand_fields(data: &mut AppData) {
+    let expand_amount = 30.0;  // 每次扩展30像素
+
+    // 统计每个领域内项目所需的面积（属于子领域的项目也计入上级领域）
+    let mut field_required_area: HashMap<String, f32> = HashMap::new();
+    for project in data.projects.values() {
+        for field_id in &data.effective_field_ids(project) {
+            let area = std::f32::consts::PI * (project.radius + PROJECT_SPACING / 2.0).powi(2);  // 项目面积 + 间距
+            *field_required_area.entry(field_id.clone()).or_insert(0.0) += area;
+        }
+    }
+
+    // 检查并扩展需要更多空间的领域
+    let mut expanded: Vec<String> = Vec::new();
+    for (field_id, required_area) in &field_required_area {
+        if let Some(field) = data.fields.get_mut(field_id).filter(|f| !f.locked) {
+            let current_area = std::f32::consts::PI * field.radius.powi(2);
+            // 如果所需面积超过当前面积的40%（考虑交叉区域），则扩展
+            if *required_area > current_area * 0.4 {
+                field.radius += expand_amount;
+                expanded.push(field_id.clone());
+            }
+        }
+    }
+
+    // 为扩展的子领域腾出空间：上级领域至少扩展同样大小（每个上级只扩展一次）
+    let mut grown: Vec<String> = Vec::new();
+    for field_id in &expanded {
+        for ancestor_id in data.field_ancestors(field_id) {
+            if expanded.contains(&ancestor_id) || grown.contains(&ancestor_id) {
+                continue;
+            }
+            if let Some(ancestor) = data.fields.get_mut(&ancestor_id).filter(|f| !f.locked) {
+                ancestor.radius += expand_amount;
+            }
+            grown.push(ancestor_id);
+        }
+    }
+    contain_sub_fields(data);
+}
+
+/// 子领域与上级领域边缘的最小间距（像素）
+const SUB_FIELD_MARGIN: f32 = 10.0;
+
+/// 把超出上级领域的子领域收回上级领域内（锁定的子领域不动）：
+/// 半径过大时缩小到上级半径的 70%，再把圆心移向上级圆心直到整个圆圈在上级内，
+/// 按层级从外到内处理，返回是否有改动
+pub fn contain_sub_fields(data: &mut AppData) -> bool {
+    let order: Vec<String> = data.field_tree().iter()
+        .filter(|(_, depth)| *depth > 0)
+        .map(|(f, _)| f.id.clone())
+        .collect();
+    let mut changed = false;
+    for id in order {
+        let Some(parent) = data.fields.get(&id)
+            .and_then(|f| f.parent_id.as_ref())
+            .and_then(|p| data.fields.get(p))
+            .cloned()
+        else {
+            continue;
+        };
+        let Some(field) = data.fields.get_mut(&id).filter(|f| !f.locked) else {
+            continue;
+        };
+        let max_radius = (parent.radius - SUB_FIELD_MARGIN) * 0.7;
+        if field.radius > max_radius {
+            field.radius = max_radius.max(20.0);
+            changed = true;
+        }
+        let dx = field.position.0 - parent.position.0;
+        let dy = field.position.1 - parent.position.1;
+        let distance = (dx * dx + dy * dy).sqrt();
+        let max_distance = (parent.radius - SUB_FIELD_MARGIN - field.radius).max(0.0);
+        if distance > max_distance {
+            let ratio = if distance > 0.0 { max_distance / distance } else { 0.0 };
+            field.position = (parent.position.0 + dx * ratio, parent.position.1 + dy * ratio);
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// 根据项目分布动态调整三个顶层研究领域圆圈的位置和大小
+/// 确保三个圆圈有合适的交集区域；锁定的领域保持原位置和大小；
+/// 子领域跟随上级领域平移，并收回到上级领域内
+pub fn adjust_field_layout(data: &mut AppData) {
+    // 统计每个领域和交集区域的项目数量
+    let field_ids: Vec<String> = data.fields.keys()
+        .filter(|id| data.field_depth(id) == 0)
+        .cloned()
+        .collect();
+    if field_ids.len() != 3 {
+        contain_sub_fields(data);
+        return; // 只处理3个顶层领域的情况
+    }
+
+    let field1_id = &field_ids[0];
+    let field2_id = &field_ids[1];
+    let field3_id = &field_ids[2];
+
+    // 统计各区域的项目数量
+    let mut count_field1_only = 0;
+    let mut count_field2_only = 0;
+    let mut count_field3_only = 0;
+    let mut count_field12 = 0; // field1 和 field2 的交集
+    let mut count_field13 = 0; // field1 和 field3 的交集
+    let mut count_field23 = 0; // field2 和 field3 的交集
+    let mut count_all = 0; // 三个领域的交集
+
+    for project in data.projects.values() {
+        let member_ids = data.effective_field_ids(project);
+        let has1 = member_ids.contains(field1_id);
+        let has2 = member_ids.contains(field2_id);
+        let has3 = member_ids.contains(field3_id);
+
+        match (has1, has2, has3) {
+            (true, false, false) => count_field1_only += 1,
+            (false, true, false) => count_field2_only += 1,
+            (false, false, true) => count_field3_only += 1,
+            (true, true, false) => count_field12 += 1,
+            (true, false, true) => count_field13 += 1,
+            (false, true, true) => count_field23 += 1,
+            (true, true, true) => count_all += 1,
+            _ => {}
+        }
+    }
+
+    // 计算总项目数
+    let total_projects = data.projects.len() as f32;
+    if total_projects == 0.0 {
+        contain_sub_fields(data);
+        return; // 没有项目，使用默认布局
+    }
+
+    // 计算交集区域需要的空间（基于项目数量）
+    // 三个领域交集的项目数量影响交集区域大小
+    let intersection_weight = (count_all as f32 / total_projects.max(1.0)).min(1.0);
+    
+    // 计算每个领域的项目密度
+    let density1 = (count_field1_only + count_field12 + count_field13 + count_all) as f32 / total_projects;
+    let density2 = (count_field2_only + count_field12 + count_field23 + count_all) as f32 / total_projects;
+    let density3 = (count_field3_only + count_field13 + count_field23 + count_all) as f32 / total_projects;
+
+    // 基础半径（根据项目密度调整，但确保最小值）
+    let base_radius: f32 = 180.0;
+    let radius1 = base_radius.max(150.0) + density1 * 80.0;
+    let radius2 = base_radius.max(150.0) + density2 * 80.0;
+    let radius3 = base_radius.max(150.0) + density3 * 80.0;
+
+    // 计算三个圆圈的中心位置，确保有交集
+    // 使用等边三角形布局，中心在 (400, 400)
+    let center_x = 400.0;
+    let center_y = 400.0;
+    
+    // 根据交集项目数量调整三角形大小
+    // 如果有三个领域的交集项目，需要缩小三角形使圆圈更靠近
+    let base_triangle_size = 120.0;
+    // 交集项目越多，三角形越小（圆圈越靠近）
+    let triangle_size = base_triangle_size * (1.0 - intersection_weight * 0.4);
+    
+    // 确保三角形大小使得三个圆圈有交集
+    // 等边三角形的边长 = triangle_size * 2 * sin(60度) ≈ triangle_size * 1.732
+    let triangle_side = triangle_size * 1.732;
+    let min_radius_sum = (radius1 + radius2).min(radius2 + radius3).min(radius1 + radius3);
+    
+    // 如果三角形太大，调整它以确保有交集
+    let final_triangle_size = if triangle_side > min_radius_sum * 0.8 {
+        let adjusted_size = min_radius_sum * 0.46; // 0.8 / 1.732 ≈ 0.46
+        adjusted_size.max(80.0) // 最小80像素
+    } else {
+        triangle_size
+    };
+    
+    // 第一个圆圈（顶部）
+    let angle1 = -std::f32::consts::PI / 2.0; // -90度
+    let pos1_x = center_x + final_triangle_size * angle1.cos();
+    let pos1_y = center_y + final_triangle_size * angle1.sin();
+    
+    // 第二个圆圈（左下）
+    let angle2 = -std::f32::consts::PI / 2.0 + 2.0 * std::f32::consts::PI / 3.0; // -90 + 120度
+    let pos2_x = center_x + final_triangle_size * angle2.cos();
+    let pos2_y = center_y + final_triangle_size * angle2.sin();
+    
+    // 第三个圆圈（右下）
+    let angle3 = -std::f32::consts::PI / 2.0 + 4.0 * std::f32::consts::PI / 3.0; // -90 + 240度
+    let pos3_x = center_x + final_triangle_size * angle3.cos();
+    let pos3_y = center_y + final_triangle_size * angle3.sin();
+
+    // 更新领域位置和半径，子领域随上级领域平移，并按半径变化比例缩放到上级圆心的距离
+    let targets = [
+        (field1_id, (pos1_x, pos1_y), radius1),
+        (field2_id, (pos2_x, pos2_y), radius2),
+        (field3_id, (pos3_x, pos3_y), radius3),
+    ];
+    for (field_id, position, radius) in targets {
+        let Some(old) = data.fields.get(field_id).filter(|f| !f.locked).cloned() else {
+            continue;
+        };
+        let scale = radius / old.radius.max(1.0);
+        for child_id in data.field_descendants(field_id) {
+            if let Some(child) = data.fields.get_mut(&child_id).filter(|f| !f.locked) {
+                child.position = (
+                    position.0 + (child.position.0 - old.position.0) * scale,
+                    position.1 + (child.position.1 - old.position.1) * scale,
+                );
+            }
+        }
+        if let Some(field) = data.fields.get_mut(field_id) {
+            field.position = position;
+            field.radius = radius;
+        }
+    }
+    contain_sub_fields(data);
+}
+
+/// 给所有项目位置加入随机抖动，避免重新放置时完全重叠（锁定的项目除外）
+pub fn jitter_positions(data: &mut AppData, seed: u64) {
+    for (idx, project) in data.projects.values_mut().enumerate() {
+        if project.locked {
+            continue;
+        }
+        let jitter_seed = seed.wrapping_add(idx as u64);
+        project.position.0 += ((jitter_seed % 100) as f32 - 50.0) * 0.5;
+        project.position.1 += (((jitter_seed / 100) % 100) as f32 - 50.0) * 0.5;
+    }
+}
+
+/// 把 source 中的项目位置和领域位置、半径复制到 target（按ID对应）
+pub fn copy_geometry(target: &mut AppData, source: &AppData) {
+    for (id, project) in &mut target.projects {
+        if let Some(p) = source.projects.get(id) {
+            project.position = p.position;
+        }
+    }
+    for (id, field) in &mut target.fields {
+        if let Some(f) = source.fields.get(id) {
+            field.position = f.position;
+            field.radius = f.radius;
+        }
+    }
+}
+
+/// 领域名称相对圆心的方向（弧度）：放在离最近的相交或相邻领域相反的一侧，默认在上方
+/// 画布与布局诊断共用；others 为参与比较的其他领域（可包含自身）
+pub fn field_label_angle<'a>(field: &ResearchField, others: impl IntoIterator<Item = &'a ResearchField>) -> f32 {
+    let mut best_angle = -std::f32::consts::PI / 2.0;
+    let mut min_distance = f32::MAX;
+    for other in others {
+        if other.id == field.id {
+            continue;
+        }
+        let dx = other.position.0 - field.position.0;
+        let dy = other.position.1 - field.position.1;
+        let distance = (dx * dx + dy * dy).sqrt();
+        // 嵌套的圆圈（子领域与上级领域）互不影响名称方向
+        if distance + other.radius <= field.radius || distance + field.radius <= other.radius {
+            continue;
+        }
+        // 两个圆圈有交集或很接近时，文字放在远离对方的方向
+        if distance < (field.radius + other.radius) * 1.5 && distance < min_distance {
+            min_distance = distance;
+            best_angle = dy.atan2(dx) + std::f32::consts::PI;
+        }
+    }
+    best_angle
+}
+
+/// 领域名称的估算矩形（中心, 半宽, 半高），按画布 100% 缩放时的位置和尺寸计算：
+/// 距圆心 半径 + 25，每个字符约10像素宽，高约20像素
+pub fn field_label_rect(field: &ResearchField, angle: f32) -> ((f32, f32), f32, f32) {
+    let distance = field.radius + 25.0;
+    let center = (
+        field.position.0 + distance * angle.cos(),
+        field.position.1 + distance * angle.sin(),
+    );
+    (center, field.name.chars().count() as f32 * 5.0, 10.0)
+}
+
+/// 矩形与圆是否相交
+fn rect_hits_circle((cx, cy): (f32, f32), half_width: f32, half_height: f32, (x, y): (f32, f32), radius: f32) -> bool {
+    let nearest_x = x.clamp(cx - half_width, cx + half_width);
+    let nearest_y = y.clamp(cy - half_height, cy + half_height);
+    (x - nearest_x).powi(2) + (y - nearest_y).powi(2) < radius * radius
+}
+
+/// 领域名称压住的圆圈
+#[derive(Debug, Clone, PartialEq)]
+pub enum LabelObstacle {
+    Field(String),    // 领域圆圈（可以是自身）
+    Project(String),  // 项目圆圈
+}
+
+/// 布局诊断发现的问题，对应放置算法检查的各项规则
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutIssue {
+    WrongRegion(String),                  // 项目不在所属领域的区域内
+    Overlap(String, String),              // 两个项目的距离小于间距要求
+    OnArrow(String, usize),               // 项目离关系箭头太近（项目ID, 关系序号）
+    LabelOverlap(String, LabelObstacle),  // 领域名称与圆圈重叠（领域ID, 被压住的圆圈）
+}
+
+impl LayoutIssue {
+    /// 问题类别名称
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            LayoutIssue::WrongRegion(_) => "区域错误",
+            LayoutIssue::Overlap(_, _) => "项目重叠",
+            LayoutIssue::OnArrow(_, _) => "压住箭头",
+            LayoutIssue::LabelOverlap(_, _) => "名称重叠",
+        }
+    }
+
+    /// 计入质量分的权重：区域错误和重叠是硬性规则，箭头和名称只影响可读性
+    fn weight(&self) -> f32 {
+        match self {
+            LayoutIssue::WrongRegion(_) | LayoutIssue::Overlap(_, _) => 1.0,
+            LayoutIssue::OnArrow(_, _) | LayoutIssue::LabelOverlap(_, _) => 0.5,
+        }
+    }
+
+    /// 涉及的项目ID
+    pub fn project_ids(&self) -> Vec<String> {
+        match self {
+            LayoutIssue::WrongRegion(id) | LayoutIssue::OnArrow(id, _) => vec![id.clone()],
+            LayoutIssue::Overlap(a, b) => vec![a.clone(), b.clone()],
+            LayoutIssue::LabelOverlap(_, LabelObstacle::Project(id)) => vec![id.clone()],
+            LayoutIssue::LabelOverlap(_, LabelObstacle::Field(_)) => Vec::new(),
+        }
+    }
+
+    /// 问题描述（使用项目和领域名称）
+    pub fn describe(&self, data: &AppData) -> String {
+        let project_name = |id: &str| data.projects.get(id).map(|p| p.name.clone()).unwrap_or_else(|| id.to_string());
+        let field_name = |id: &str| data.fields.get(id).map(|f| f.name.clone()).unwrap_or_else(|| id.to_string());
+        match self {
+            LayoutIssue::WrongRegion(id) => format!("{} 不在所属领域的区域内", project_name(id)),
+            LayoutIssue::Overlap(a, b) => format!("{} 与 {} 间距不足", project_name(a), project_name(b)),
+            LayoutIssue::OnArrow(id, relation_index) => match data.relations.get(*relation_index) {
+                Some(relation) => format!(
+                    "{} 压在关系 {} → {} 上",
+                    project_name(id), project_name(&relation.from_id), project_name(&relation.to_id)
+                ),
+                None => format!("{} 压在关系箭头上", project_name(id)),
+            },
+            LayoutIssue::LabelOverlap(field_id, LabelObstacle::Field(other)) if other == field_id => {
+                format!("领域“{}”的名称压住了自身圆圈", field_name(field_id))
+            }
+            LayoutIssue::LabelOverlap(field_id, LabelObstacle::Field(other)) => {
+                format!("领域“{}”的名称压住了领域“{}”", field_name(field_id), field_name(other))
+            }
+            LayoutIssue::LabelOverlap(field_id, LabelObstacle::Project(id)) => {
+                format!("领域“{}”的名称压住了 {}", field_name(field_id), project_name(id))
+            }
+        }
+    }
+
+    /// 定位到问题时视图的中心点（数据坐标）
+    pub fn focus(&self, data: &AppData) -> Option<(f32, f32)> {
+        let position = |id: &str| data.projects.get(id).map(|p| p.position);
+        match self {
+            LayoutIssue::WrongRegion(id) | LayoutIssue::OnArrow(id, _) => position(id),
+            LayoutIssue::Overlap(a, b) => {
+                let (a, b) = (position(a)?, position(b)?);
+                Some(((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0))
+            }
+            LayoutIssue::LabelOverlap(field_id, _) => {
+                let field = data.fields.get(field_id)?;
+                Some(field_label_rect(field, field_label_angle(field, data.fields.values())).0)
+            }
+        }
+    }
+}
+
+/// 布局质量报告
+#[derive(Debug, Clone, Default)]
+pub struct LayoutReport {
+    pub issues: Vec<LayoutIssue>,
+    pub score: f32,  // 0-100，没有问题时为 100
+}
+
+impl LayoutReport {
+    /// 某一类问题的数量
+    pub fn count(&self, kind_name: &str) -> usize {
+        self.issues.iter().filter(|i| i.kind_name() == kind_name).count()
+    }
+}
+
+/// 检查当前位置下放置算法关心的所有规则：区域是否正确、项目间距、项目是否压住关系箭头、
+/// 领域名称是否与圆圈重叠；质量分按问题权重之和相对项目和领域总数扣减
+pub fn diagnose(data: &AppData) -> LayoutReport {
+    let index = SpatialIndex::new(data);
+    let mut issues = Vec::new();
+
+    for project in data.projects.values() {
+        if !is_in_correct_region(data, project) {
+            issues.push(LayoutIssue::WrongRegion(project.id.clone()));
+        }
+    }
+    for (a, b) in index.overlapping_pairs(PROJECT_SPACING) {
+        issues.push(LayoutIssue::Overlap(a, b));
+    }
+    for project in data.projects.values() {
+        let (x, y) = project.position;
+        for relation_index in index.relations_near(x, y, project.radius + ARROW_SPACING, &project.id) {
+            issues.push(LayoutIssue::OnArrow(project.id.clone(), relation_index));
+        }
+    }
+    for field in data.fields.values() {
+        let angle = field_label_angle(field, data.fields.values());
+        let (center, half_width, half_height) = field_label_rect(field, angle);
+        // 子领域的名称画在上级领域内，不算压住上级领域
+        let ancestors = data.field_ancestors(&field.id);
+        for other in data.fields.values().filter(|f| !ancestors.contains(&f.id)) {
+            if rect_hits_circle(center, half_width, half_height, other.position, other.radius) {
+                issues.push(LayoutIssue::LabelOverlap(field.id.clone(), LabelObstacle::Field(other.id.clone())));
+            }
+        }
+        let reach = half_width.max(half_height) + index.max_radius;
+        let mut hits: Vec<usize> = index.projects_near(center.0, center.1, reach)
+            .filter(|&slot| rect_hits_circle(center, half_width, half_height, index.positions[slot], index.radii[slot]))
+            .collect();
+        hits.sort_unstable();  // 按数据顺序列出
+        for slot in hits {
+            issues.push(LayoutIssue::LabelOverlap(field.id.clone(), LabelObstacle::Project(index.ids[slot].clone())));
+        }
+    }
+
+    let total = (data.projects.len() + data.fields.len()).max(1) as f32;
+    let penalty: f32 = issues.iter().map(LayoutIssue::weight).sum();
+    let score = (100.0 * (1.0 - penalty / total)).clamp(0.0, 100.0);
+    LayoutReport { issues, score }
+}
+
+/// 后台布局任务
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutTask {
+    FixPositions,  // 修正位置不正确和重叠的项目
+    Relayout,      // 重新计算领域布局，重新放置全部项目后再修正
+}
+
+impl LayoutTask {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LayoutTask::FixPositions => "修正位置",
+            LayoutTask::Relayout => "重新布局",
+        }
+    }
+
+    /// 在数据上运行任务，被中止时返回 None，否则返回是否有改动
+    fn run(&self, data: &mut AppData, progress: Progress) -> Option<bool> {
+        match self {
+            LayoutTask::FixPositions => fix_positions_with_progress(data, progress),
+            LayoutTask::Relayout => {
+                use std::time::{SystemTime, UNIX_EPOCH};
+                let seed = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_nanos() as u64;
+                adjust_field_layout(data);
+                jitter_positions(data, seed);
+                // 放置占前一半进度，修正占后一半
+                let project_ids: Vec<String> = data.projects.keys().cloned().collect();
+                place_projects_with_progress(data, &project_ids, &mut |f, d| progress(f * 0.5, d))?;
+                fix_positions_with_progress(data, &mut |f, d| progress(0.5 + f * 0.5, d))?;
+                Some(true)
+            }
+        }
+    }
+}
+
+/// 后台线程发回的消息
+enum LayoutMessage {
+    Progress(f32, Box<AppData>),    // 进度和中间结果（用于预览动画）
+    Finished(Box<AppData>, bool),   // 最终结果和是否有改动
+}
+
+/// 在后台线程中对数据快照运行布局任务，通过通道发回进度和中间结果；
+/// 界面线程每帧调用 poll 取最新结果，完成后由用户接受或放弃
+pub struct LayoutWorker {
+    pub task: LayoutTask,
+    pub original: AppData,  // 任务开始前的数据，放弃时恢复
+    pub progress: f32,
+    pub result: Option<(AppData, bool)>,  // 完成后的数据和是否有改动
+    cancelled: Arc<AtomicBool>,
+    receiver: Receiver<LayoutMessage>,
+}
+
+impl LayoutWorker {
+    /// 在 snapshot 上启动任务；original 为放弃时要恢复的数据
+    pub fn start(task: LayoutTask, snapshot: AppData, original: AppData) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let thread_cancelled = cancelled.clone();
+        std::thread::spawn(move || {
+            let mut data = snapshot;
+            let mut report = |fraction: f32, data: &AppData| {
+                !thread_cancelled.load(Ordering::Relaxed)
+                    && sender.send(LayoutMessage::Progress(fraction, Box::new(data.clone()))).is_ok()
+            };
+            if let Some(changed) = task.run(&mut data, &mut report) {
+                let _ = sender.send(LayoutMessage::Finished(Box::new(data), changed));
+            }
+        });
+        Self {
+            task,
+            original,
+            progress: 0.0,
+            result: None,
+            cancelled,
+            receiver,
+        }
+    }
+
+    /// 接收后台消息，返回最新的中间结果或最终结果（没有新消息时返回 None）
+    pub fn poll(&mut self) -> Option<AppData> {
+        let mut latest = None;
+        while let Ok(message) = self.receiver.try_recv() {
+            match message {
+                LayoutMessage::Progress(fraction, data) => {
+                    self.progress = fraction;
+                    latest = Some(*data);
+                }
+                LayoutMessage::Finished(data, changed) => {
+                    self.progress = 1.0;
+                    latest = Some((*data).clone());
+                    self.result = Some((*data, changed));
+                }
+            }
+        }
+        latest
+    }
+
+    /// 是否已计算完成（等待接受或放弃）
+    pub fn is_finished(&self) -> bool {
+        self.result.is_some()
+    }
+}
+
+impl Drop for LayoutWorker {
+    /// 放弃任务时通知后台线程在下一次报告进度时停止
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProjectRelation;
+
+    /// 简单的线性同余随机数（与布局基准相同，保证每次运行数据相同）
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> f32 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((self.0 >> 40) as f32) / ((1u64 << 24) as f32)
+        }
+
+        fn range(&mut self, min: f32, max: f32) -> f32 {
+            min + self.next() * (max - min)
+        }
+    }
+
+    /// 随机项目和关系：坐标跨越负数区域，部分关系水平、竖直或退化为一个点，
+    /// 用来覆盖网格取整和线段步进的边界情况
+    fn random_data(seed: u64, n: usize) -> AppData {
+        let mut rng = Lcg(seed);
+        let mut data = AppData::default();
+        data.projects.clear();
+        data.relations.clear();
+        for i in 0..n {
+            let id = format!("project_{}", i + 1);
+            let mut position = (rng.range(-600.0, 600.0), rng.range(-600.0, 600.0));
+            if i % 7 == 0 && i > 0 {
+                // 与上一个项目同一水平线或竖直线
+                let previous = data.projects[i - 1].position;
+                if i % 2 == 0 { position.1 = previous.1 } else { position.0 = previous.0 }
+            }
+            data.projects.insert(id.clone(), Project {
+                id,
+                position,
+                radius: rng.range(5.0, 40.0),
+                ..Default::default()
+            });
+        }
+        for _ in 0..n {
+            let from = (rng.next() * n as f32) as usize % n;
+            let to = (rng.next() * n as f32) as usize % n;
+            data.relations.push(ProjectRelation {
+                from_id: format!("project_{}", from + 1),
+                to_id: format!("project_{}", to + 1),
+                ..Default::default()
+            });
+        }
+        data
+    }
+
+    fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+        ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+    }
+
+    fn brute_overlaps(data: &AppData, x: f32, y: f32, radius: f32, spacing: f32, exclude_id: &str) -> bool {
+        data.projects.values()
+            .filter(|p| p.id != exclude_id)
+            .any(|p| distance((x, y), p.position) < radius + p.radius + spacing)
+    }
+
+    fn brute_nearest(data: &AppData, x: f32, y: f32, exclude_id: &str) -> Option<f32> {
+        data.projects.values()
+            .filter(|p| p.id != exclude_id)
+            .map(|p| distance((x, y), p.position))
+            .fold(None, |best: Option<f32>, d| Some(best.map_or(d, |b| b.min(d))))
+    }
+
+    fn brute_near_relation(data: &AppData, x: f32, y: f32, threshold: f32, exclude_id: &str) -> bool {
+        data.relations.iter()
+            .filter(|r| r.from_id != exclude_id && r.to_id != exclude_id)
+            .filter_map(|r| Some((data.projects.get(&r.from_id)?.position, data.projects.get(&r.to_id)?.position)))
+            .any(|(start, end)| point_segment_distance((x, y), start, end).is_some_and(|(d, _)| d < threshold))
+    }
+
+    fn brute_pairs(data: &AppData, spacing: f32) -> Vec<(String, String)> {
+        let projects: Vec<&Project> = data.projects.values().collect();
+        let mut pairs = Vec::new();
+        for (i, a) in projects.iter().enumerate() {
+            for b in &projects[i + 1..] {
+                if distance(a.position, b.position) < a.radius + b.radius + spacing {
+                    pairs.push((a.id.clone(), b.id.clone()));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// 在随机查询点上比较索引和逐个扫描的结果
+    fn assert_matches_brute_force(data: &AppData, index: &SpatialIndex, rng: &mut Lcg) {
+        for _ in 0..300 {
+            let (x, y) = (rng.range(-700.0, 700.0), rng.range(-700.0, 700.0));
+            let radius = rng.range(5.0, 40.0);
+            let exclude = format!("project_{}", (rng.next() * data.projects.len() as f32) as usize + 1);
+            assert_eq!(
+                index.overlaps_project(x, y, radius, PROJECT_SPACING, &exclude),
+                brute_overlaps(data, x, y, radius, PROJECT_SPACING, &exclude),
+                "overlaps_project at ({}, {})", x, y
+            );
+            assert_eq!(index.nearest_project_distance(x, y, &exclude), brute_nearest(data, x, y, &exclude), "nearest_project_distance at ({}, {})", x, y);
+            let threshold = rng.range(1.0, 60.0);
+            assert_eq!(
+                index.near_relation(x, y, threshold, &exclude),
+                brute_near_relation(data, x, y, threshold, &exclude),
+                "near_relation at ({}, {}) within {}", x, y, threshold
+            );
+        }
+        let mut pairs = index.overlapping_pairs(PROJECT_SPACING);
+        let mut expected = brute_pairs(data, PROJECT_SPACING);
+        pairs.sort();
+        expected.sort();
+        assert_eq!(pairs, expected);
+    }
+
+    #[test]
+    fn spatial_index_matches_brute_force() {
+        for (seed, n) in [(1, 10), (2, 80), (3, 300)] {
+            let data = random_data(seed, n);
+            let index = SpatialIndex::new(&data);
+            assert_matches_brute_force(&data, &index, &mut Lcg(seed + 100));
+        }
+    }
+
+    #[test]
+    fn spatial_index_matches_brute_force_after_moves() {
+        let mut data = random_data(4, 120);
+        let mut index = SpatialIndex::new(&data);
+        let mut rng = Lcg(5);
+        for _ in 0..200 {
+            let i = (rng.next() * data.projects.len() as f32) as usize;
+            let project = &mut data.projects[i];
+            project.position = (rng.range(-600.0, 600.0), rng.range(-600.0, 600.0));
+            index.move_project(project);
+        }
+        assert_matches_brute_force(&data, &index, &mut rng);
+    }
+
+    #[test]
+    fn nearest_without_other_projects() {
+        let mut data = random_data(6, 1);
+        assert_eq!(SpatialIndex::new(&data).nearest_project_distance(0.0, 0.0, "project_1"), None);
+        data.projects.clear();
+        assert_eq!(SpatialIndex::new(&data).nearest_project_distance(0.0, 0.0, ""), None);
+    }
+}
@@ -1,13 +1,30 @@
 mod models;
 mod app;
+mod cli;
+mod export;
+mod layout;
+mod pinyin;
+mod route;
+mod query;
+mod render;
+mod search;
+mod theme;
 
 use app::VennCVApp;
 
 fn main() -> eframe::Result<()> {
+    // 命令行查询模式
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if cli::is_cli(&args) {
+        std::process::exit(cli::run(&args));
+    }
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1200.0, 800.0])
             .with_title("科研项目管理系统 - VennCV"),
+        // 获取系统深浅色模式，供"跟随系统"主题使用
+        follow_system_theme: true,
         ..Default::default()
     };
 
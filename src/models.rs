@@ -1,38 +1,57 @@
 use serde::{Deserialize, Serialize};
 use indexmap::IndexMap;
+use chrono::NaiveDate;
 
-/// 项目状态
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum ProjectStatus {
-    Published,      // 已发表 - 绿色
-    Submitted,     // 投稿中 - 蓝色
-    HighPriority,  // 优先做 - 红色
-    SteadyProgress, // 稳步做 - 橙色
-    ToBeStarted,   // 待启动 - 白色
+// 内置状态ID（与旧版枚举的序列化名称一致，旧数据无需转换）
+pub const STATUS_PUBLISHED: &str = "Published";
+pub const STATUS_SUBMITTED: &str = "Submitted";
+pub const STATUS_HIGH_PRIORITY: &str = "HighPriority";
+pub const STATUS_STEADY_PROGRESS: &str = "SteadyProgress";
+pub const STATUS_TO_BE_STARTED: &str = "ToBeStarted";
+
+// 内置关系类型ID（与旧版枚举的序列化名称一致，旧数据无需转换）
+pub const RELATION_DIRECT: &str = "Direct";
+pub const RELATION_INDIRECT: &str = "Indirect";
+
+/// 项目状态（文档内状态目录中的一项）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectStatus {
+    pub id: String,
+    pub name: String,       // 显示名称
+    pub color: [u8; 4],     // 填充颜色 RGBA
+    #[serde(default)]
+    pub order: i32,         // 排序（图例、下拉列表按此升序）
+    #[serde(default)]
+    pub counts_as_done: bool,  // 是否视为已完成
 }
 
 impl ProjectStatus {
-    pub fn color(&self) -> egui::Color32 {
-        match self {
-            ProjectStatus::Published => egui::Color32::from_rgb(76, 175, 80),      // 绿色
-            ProjectStatus::Submitted => egui::Color32::from_rgb(33, 150, 243),    // 蓝色
-            ProjectStatus::HighPriority => egui::Color32::from_rgb(244, 67, 54),  // 红色
-            ProjectStatus::SteadyProgress => egui::Color32::from_rgb(255, 152, 0), // 橙色
-            ProjectStatus::ToBeStarted => egui::Color32::from_rgb(255, 255, 255), // 白色
+    pub fn new(id: &str, name: &str, color: [u8; 4], order: i32, counts_as_done: bool) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            color,
+            order,
+            counts_as_done,
         }
     }
 
-    pub fn name(&self) -> &'static str {
-        match self {
-            ProjectStatus::Published => "已发表",
-            ProjectStatus::Submitted => "投稿中",
-            ProjectStatus::HighPriority => "优先做",
-            ProjectStatus::SteadyProgress => "进行中",
-            ProjectStatus::ToBeStarted => "待启动",
-        }
+    pub fn color32(&self) -> egui::Color32 {
+        egui::Color32::from_rgba_unmultiplied(self.color[0], self.color[1], self.color[2], self.color[3])
     }
 }
 
+/// 默认状态目录（对应旧版的五个固定状态）
+pub fn default_status_catalog() -> Vec<ProjectStatus> {
+    vec![
+        ProjectStatus::new(STATUS_PUBLISHED, "已发表", [76, 175, 80, 255], 0, true),        // 绿色
+        ProjectStatus::new(STATUS_SUBMITTED, "投稿中", [33, 150, 243, 255], 1, false),      // 蓝色
+        ProjectStatus::new(STATUS_HIGH_PRIORITY, "优先做", [244, 67, 54, 255], 2, false),   // 红色
+        ProjectStatus::new(STATUS_STEADY_PROGRESS, "进行中", [255, 152, 0, 255], 3, false), // 橙色
+        ProjectStatus::new(STATUS_TO_BE_STARTED, "待启动", [255, 255, 255, 255], 4, false), // 白色
+    ]
+}
+
 /// 研究方向（大圈）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResearchField {
@@ -41,19 +60,104 @@ pub struct ResearchField {
     pub description: String,
     pub position: (f32, f32),  // 圆心位置
     pub radius: f32,           // 半径
+    #[serde(default)]
+    pub locked: bool,          // 锁定后自动布局不改变位置和半径
+    #[serde(default)]
+    pub parent_id: Option<String>,  // 上级领域：子领域画在上级领域圆圈内，属于子领域的项目也属于上级领域
+    #[serde(default = "default_field_stroke_color")]
+    pub stroke_color: [u8; 4],  // 边框颜色 RGBA
+    #[serde(default = "default_field_fill_color")]
+    pub fill_color: [u8; 4],  // 半透明填充颜色 RGBA，透明度为 0 时不填充
+    #[serde(default = "default_field_label_style")]
+    pub label_style: FieldLabelStyle,  // 领域名称样式
+}
+
+pub fn default_field_stroke_color() -> [u8; 4] {
+    [160, 160, 160, 255]  // 默认灰色
+}
+
+pub fn default_field_fill_color() -> [u8; 4] {
+    [0, 0, 0, 0]  // 默认不填充
+}
+
+pub fn default_field_label_style() -> FieldLabelStyle {
+    FieldLabelStyle::Plain
+}
+
+/// 领域名称样式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FieldLabelStyle {
+    Plain,    // 主题文字颜色
+    Colored,  // 与边框同色
+    Boxed,    // 边框颜色底色的标签块
+}
+
+impl FieldLabelStyle {
+    pub fn all() -> [FieldLabelStyle; 3] {
+        [FieldLabelStyle::Plain, FieldLabelStyle::Colored, FieldLabelStyle::Boxed]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FieldLabelStyle::Plain => "普通",
+            FieldLabelStyle::Colored => "同边框颜色",
+            FieldLabelStyle::Boxed => "色块",
+        }
+    }
+}
+
+/// 项目里程碑
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Milestone {
+    pub name: String,
+    #[serde(default)]
+    pub due_date: Option<NaiveDate>,  // 截止日期
+    #[serde(default)]
+    pub done: bool,  // 是否已完成
 }
 
 /// 项目（小圈）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Project {
     pub id: String,
     pub name: String,
     pub description: String,
-    pub status: ProjectStatus,
+    pub status: String,  // 状态ID，对应 AppData.statuses
     pub field_ids: Vec<String>,  // 所属的研究领域
     pub position: (f32, f32),    // 在可视化中的位置
     pub radius: f32,             // 半径
     pub completion_percentage: f32,  // 完成度百分比 (0.0 - 100.0)
+    #[serde(default)]
+    pub start_date: Option<NaiveDate>,  // 开始日期
+    #[serde(default)]
+    pub target_date: Option<NaiveDate>,  // 目标完成日期
+    #[serde(default)]
+    pub submission_date: Option<NaiveDate>,  // 投稿日期
+    #[serde(default)]
+    pub publication_date: Option<NaiveDate>,  // 发表日期
+    #[serde(default)]
+    pub milestones: Vec<Milestone>,  // 里程碑
+    #[serde(default)]
+    pub locked: bool,  // 锁定后自动布局不移动该项目
+    #[serde(default)]
+    pub tags: Vec<String>,  // 项目标签（关键词，对应 AppData.project_tags）
+}
+
+impl Project {
+    /// 项目的所有日期（四个日期字段和里程碑截止日期）
+    pub fn all_dates(&self) -> Vec<NaiveDate> {
+        [self.start_date, self.target_date, self.submission_date, self.publication_date]
+            .into_iter()
+            .flatten()
+            .chain(self.milestones.iter().filter_map(|m| m.due_date))
+            .collect()
+    }
+
+    /// 时间跨度：最早日期到最晚日期（没有任何日期时为 None）
+    pub fn date_span(&self) -> Option<(NaiveDate, NaiveDate)> {
+        let dates = self.all_dates();
+        Some((*dates.iter().min()?, *dates.iter().max()?))
+    }
 }
 
 /// 项目关系（箭头）
@@ -61,13 +165,16 @@ pub struct Project {
 pub struct ProjectRelation {
     pub from_id: String,
     pub to_id: String,
-    pub relation_type: RelationType,
+    #[serde(default = "default_relation_type")]
+    pub relation_type: String,  // 关系类型ID，对应 AppData.relation_types
     #[serde(default)]
     pub tags: Vec<String>,  // 关系标签（代表意义）
     #[serde(default = "default_relation_color")]
     pub color: [u8; 4],  // 关系颜色 RGBA
     #[serde(default = "default_relation_width")]
     pub width: f32,  // 线宽
+    #[serde(default = "default_relation_route")]
+    pub route: RelationRoute,  // 走线方式
 }
 
 pub fn default_relation_color() -> [u8; 4] {
@@ -78,10 +185,106 @@ pub fn default_relation_width() -> f32 {
     2.0  // 默认线宽
 }
 
+pub fn default_relation_type() -> String {
+    RELATION_DIRECT.to_string()
+}
+
+pub fn default_relation_route() -> RelationRoute {
+    RelationRoute::Straight  // 默认直线
+}
+
+/// 关系线型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineStyle {
+    Solid,   // 实线
+    Dashed,  // 虚线
+    Dotted,  // 点线
+    Double,  // 双线
+}
+
+impl LineStyle {
+    pub fn all() -> [LineStyle; 4] {
+        [LineStyle::Solid, LineStyle::Dashed, LineStyle::Dotted, LineStyle::Double]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LineStyle::Solid => "实线",
+            LineStyle::Dashed => "虚线",
+            LineStyle::Dotted => "点线",
+            LineStyle::Double => "双线",
+        }
+    }
+
+    /// 虚线和点线的（线段长度, 间隔长度），其他线型为 None
+    pub fn dash_pattern(&self) -> Option<(f32, f32)> {
+        match self {
+            LineStyle::Dashed => Some((5.0, 5.0)),
+            LineStyle::Dotted => Some((1.5, 3.5)),
+            LineStyle::Solid | LineStyle::Double => None,
+        }
+    }
+}
+
+/// 箭头头部样式
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum RelationType {
-    Direct,    // 实线箭头
-    Indirect,  // 虚线箭头
+pub enum ArrowHead {
+    None,    // 无箭头
+    Open,    // 终点开放箭头（两条短线）
+    Filled,  // 终点实心三角
+    Both,    // 两端开放箭头（双向关系）
+}
+
+impl ArrowHead {
+    pub fn all() -> [ArrowHead; 4] {
+        [ArrowHead::None, ArrowHead::Open, ArrowHead::Filled, ArrowHead::Both]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ArrowHead::None => "无",
+            ArrowHead::Open => "开放",
+            ArrowHead::Filled => "实心",
+            ArrowHead::Both => "两端",
+        }
+    }
+}
+
+/// 关系类型（文档内关系类型目录中的一项）：线型、箭头样式，以及新建关系时使用的颜色和线宽
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelationType {
+    pub id: String,
+    pub name: String,  // 显示名称
+    pub line_style: LineStyle,
+    pub arrow_head: ArrowHead,
+    #[serde(default = "default_relation_color")]
+    pub color: [u8; 4],  // 默认颜色 RGBA
+    #[serde(default = "default_relation_width")]
+    pub width: f32,  // 默认线宽
+    #[serde(default)]
+    pub order: i32,  // 排序（图例、下拉列表按此升序）
+}
+
+impl RelationType {
+    pub fn new(id: &str, name: &str, line_style: LineStyle, arrow_head: ArrowHead, order: i32) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            line_style,
+            arrow_head,
+            color: default_relation_color(),
+            width: default_relation_width(),
+            order,
+        }
+    }
+}
+
+/// 默认关系类型目录（对应旧版的实线和虚线两种关系）
+pub fn default_relation_type_catalog() -> Vec<RelationType> {
+    vec![
+        RelationType::new(RELATION_DIRECT, "直接", LineStyle::Solid, ArrowHead::Open, 0),
+        RelationType::new(RELATION_INDIRECT, "间接", LineStyle::Dashed, ArrowHead::Open, 1),
+    ]
 }
 
 impl Default for ProjectRelation {
@@ -89,15 +292,83 @@ impl Default for ProjectRelation {
         Self {
             from_id: String::new(),
             to_id: String::new(),
-            relation_type: RelationType::Direct,
+            relation_type: default_relation_type(),
             tags: Vec::new(),
             color: default_relation_color(),
             width: default_relation_width(),
+            route: default_relation_route(),
+        }
+    }
+}
+
+/// 关系箭头的走线方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RelationRoute {
+    Straight,  // 圆心连线上的直线
+    Curved,    // 向一侧弯曲的贝塞尔曲线
+    Routed,    // 绕开途经的项目圆圈
+}
+
+impl RelationRoute {
+    pub fn all() -> [RelationRoute; 3] {
+        [RelationRoute::Straight, RelationRoute::Curved, RelationRoute::Routed]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RelationRoute::Straight => "直线",
+            RelationRoute::Curved => "曲线",
+            RelationRoute::Routed => "绕开项目",
         }
     }
 }
 
+/// 保存的视图：筛选条件、显示的领域和状态、缩放平移及图例状态
+/// 记录的是隐藏的领域和状态，之后新增的领域和状态默认显示
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedView {
+    pub name: String,
+    #[serde(default)]
+    pub filter: String,  // 筛选表达式（只显示匹配的项目，空表示全部）
+    #[serde(default)]
+    pub hidden_field_ids: Vec<String>,  // 隐藏的领域
+    #[serde(default)]
+    pub hidden_status_ids: Vec<String>,  // 隐藏的状态
+    #[serde(default)]
+    pub hidden_relation_tags: Vec<String>,  // 隐藏的关系标签
+    #[serde(default = "default_default_zoom")]
+    pub zoom: f32,  // 缩放比例
+    #[serde(default)]
+    pub offset: (f32, f32),  // 平移偏移
+    #[serde(default = "default_show_legend")]
+    pub show_legend: bool,  // 是否显示图例
+    #[serde(default)]
+    pub legend_position: (f32, f32),  // 图例位置偏移
+}
+
+/// 剪贴板格式标识（避免把其他 JSON 文本当作项目粘贴）
+pub const CLIPBOARD_FORMAT: &str = "venncv-projects";
+
+/// 复制到剪贴板的项目（JSON）：项目、它们之间的关系，以及引用到的领域、状态和关系类型定义，
+/// 粘贴到其他文档时用于匹配领域和补全状态、关系类型
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectClipboard {
+    pub format: String,
+    pub projects: Vec<Project>,
+    #[serde(default)]
+    pub relations: Vec<ProjectRelation>,
+    #[serde(default)]
+    pub fields: Vec<ResearchField>,
+    #[serde(default)]
+    pub statuses: Vec<ProjectStatus>,
+    #[serde(default)]
+    pub relation_types: Vec<RelationType>,
+    #[serde(default)]
+    pub relation_tag_colors: IndexMap<String, [u8; 4]>,
+}
+
 /// 用户数据
+#[allow(dead_code)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     pub username: String,
@@ -120,6 +391,98 @@ pub struct UserConfigEntry {
     pub last_edited_file: Option<String>,  // 最近编辑的文件路径
 }
 
+/// 界面主题模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeMode {
+    Light,   // 浅色（Light+）
+    Dark,    // 深色（Dark+）
+    System,  // 跟随系统
+}
+
+impl ThemeMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ThemeMode::Light => "浅色",
+            ThemeMode::Dark => "深色",
+            ThemeMode::System => "跟随系统",
+        }
+    }
+}
+
+/// 项目状态配色方案
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatusPalette {
+    Theme,     // 使用主题中的状态颜色
+    OkabeIto,  // Okabe–Ito 色盲友好配色
+}
+
+impl StatusPalette {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusPalette::Theme => "跟随主题",
+            StatusPalette::OkabeIto => "Okabe–Ito（色盲友好）",
+        }
+    }
+}
+
+/// 完成度配色方案
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompletionPalette {
+    Theme,    // 使用主题中的完成度渐变
+    Viridis,  // viridis 渐变（色盲友好，明度单调递增）
+}
+
+impl CompletionPalette {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CompletionPalette::Theme => "跟随主题",
+            CompletionPalette::Viridis => "Viridis（色盲友好）",
+        }
+    }
+}
+
+/// 项目状态的非颜色标记
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatusMarker {
+    None,     // 仅用颜色区分
+    Pattern,  // 圆内填充图案（斜线、网格、横线、点）
+    Glyph,    // 圆右上角的形状符号
+}
+
+impl StatusMarker {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusMarker::None => "无",
+            StatusMarker::Pattern => "填充图案",
+            StatusMarker::Glyph => "形状符号",
+        }
+    }
+}
+
+/// 中央区域视图
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CentralView {
+    Venn,      // 维恩图
+    Timeline,  // 时间线（甘特图）
+    Kanban,    // 看板（按状态分列）
+    Table,     // 表格
+}
+
+impl CentralView {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CentralView::Venn => "维恩图",
+            CentralView::Timeline => "时间线",
+            CentralView::Kanban => "看板",
+            CentralView::Table => "表格",
+        }
+    }
+
+    pub fn all() -> [CentralView; 4] {
+        [CentralView::Venn, CentralView::Timeline, CentralView::Kanban, CentralView::Table]
+    }
+}
+
 /// 应用设置（存储在app_settings.yaml）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
@@ -143,6 +506,22 @@ pub struct AppSettings {
     pub visualization_bg_color: [u8; 4],  // 可视化背景颜色 RGBA
     #[serde(default = "default_show_legend")]
     pub show_legend: bool,  // 是否显示图例
+    #[serde(default = "default_theme_mode")]
+    pub theme_mode: ThemeMode,  // 界面主题
+    #[serde(default)]
+    pub canvas_bg_follow_theme: bool,  // 可视化背景跟随主题（否则使用 visualization_bg_color；旧设置文件默认不跟随，保留原来的背景色）
+    #[serde(default)]
+    pub custom_theme: Option<String>,  // 自定义主题名称（themes 目录中的主题，优先于 theme_mode）
+    #[serde(default = "default_status_palette")]
+    pub status_palette: StatusPalette,  // 项目状态配色（非"跟随主题"时覆盖主题中的状态颜色）
+    #[serde(default = "default_completion_palette")]
+    pub completion_palette: CompletionPalette,  // 完成度配色（非"跟随主题"时覆盖主题中的渐变）
+    #[serde(default = "default_status_marker")]
+    pub status_marker: StatusMarker,  // 项目状态的非颜色标记
+    #[serde(default)]
+    pub show_completion_ring: bool,  // 在项目外侧绘制完成度弧线（弧长表示完成度）
+    #[serde(default)]
+    pub show_project_tags: bool,  // 在项目下方显示项目标签小徽章
 }
 
 fn default_auto_save() -> bool { false }
@@ -155,6 +534,10 @@ fn default_field_border_width() -> f32 { 2.0 }
 fn default_project_border_width() -> f32 { 3.0 }
 fn default_visualization_bg_color() -> [u8; 4] { [255, 255, 255, 255] }  // 白色
 fn default_show_legend() -> bool { true }
+fn default_theme_mode() -> ThemeMode { ThemeMode::Light }
+fn default_status_palette() -> StatusPalette { StatusPalette::Theme }
+fn default_completion_palette() -> CompletionPalette { CompletionPalette::Theme }
+fn default_status_marker() -> StatusMarker { StatusMarker::None }
 
 impl Default for AppSettings {
     fn default() -> Self {
@@ -169,6 +552,14 @@ impl Default for AppSettings {
             project_border_width: 3.0,
             visualization_bg_color: [255, 255, 255, 255],
             show_legend: true,
+            theme_mode: ThemeMode::Light,
+            canvas_bg_follow_theme: true,
+            custom_theme: None,
+            status_palette: StatusPalette::Theme,
+            completion_palette: CompletionPalette::Theme,
+            status_marker: StatusMarker::None,
+            show_completion_ring: false,
+            show_project_tags: false,
         }
     }
 }
@@ -181,6 +572,16 @@ pub struct AppData {
     pub relations: Vec<ProjectRelation>,
     #[serde(default)]
     pub relation_tags: Vec<String>,  // 关系标签列表（全局标签库）
+    #[serde(default)]
+    pub relation_tag_colors: IndexMap<String, [u8; 4]>,  // 关系标签颜色（未设置的标签使用主题颜色）
+    #[serde(default)]
+    pub project_tags: Vec<String>,  // 项目标签库（跨领域分组用的关键词）
+    #[serde(default = "default_status_catalog")]
+    pub statuses: Vec<ProjectStatus>,  // 项目状态目录（旧文件缺省时使用默认五个状态）
+    #[serde(default = "default_relation_type_catalog")]
+    pub relation_types: Vec<RelationType>,  // 关系类型目录（旧文件缺省时使用直接、间接两种）
+    #[serde(default)]
+    pub saved_views: Vec<SavedView>,  // 保存的视图
 }
 
 impl Default for AppData {
@@ -196,6 +597,11 @@ impl Default for AppData {
             description: "RNA结构研究".to_string(),
             position: (400.0, 200.0),
             radius: 200.0,
+            locked: false,
+            parent_id: None,
+            stroke_color: default_field_stroke_color(),
+            fill_color: default_field_fill_color(),
+            label_style: default_field_label_style(),
         });
 
         fields.insert("microbial_ecology".to_string(), ResearchField {
@@ -204,6 +610,11 @@ impl Default for AppData {
             description: "微生物生态研究".to_string(),
             position: (200.0, 500.0),
             radius: 200.0,
+            locked: false,
+            parent_id: None,
+            stroke_color: default_field_stroke_color(),
+            fill_color: default_field_fill_color(),
+            label_style: default_field_label_style(),
         });
 
         fields.insert("bioinfo_tools".to_string(), ResearchField {
@@ -212,6 +623,11 @@ impl Default for AppData {
             description: "生物信息学工具开发".to_string(),
             position: (600.0, 500.0),
             radius: 200.0,
+            locked: false,
+            parent_id: None,
+            stroke_color: default_field_stroke_color(),
+            fill_color: default_field_fill_color(),
+            label_style: default_field_label_style(),
         });
 
         // 添加一些示例项目
@@ -219,32 +635,35 @@ impl Default for AppData {
             id: "project_1".to_string(),
             name: "itol.toolkit".to_string(),
             description: "iTOL工具包".to_string(),
-            status: ProjectStatus::Published,
+            status: STATUS_PUBLISHED.to_string(),
             field_ids: vec!["bioinfo_tools".to_string()],
             position: (550.0, 450.0),
             radius: 20.0,
             completion_percentage: 100.0,
+            ..Default::default()
         });
 
         projects.insert("project_2".to_string(), Project {
             id: "project_2".to_string(),
             name: "VnFinder".to_string(),
             description: "VnFinder工具".to_string(),
-            status: ProjectStatus::Published,
+            status: STATUS_PUBLISHED.to_string(),
             field_ids: vec!["microbial_ecology".to_string()],
             position: (250.0, 450.0),
             radius: 20.0,
             completion_percentage: 100.0,
+            ..Default::default()
         });
 
         // 添加关系
         relations.push(ProjectRelation {
             from_id: "project_1".to_string(),
             to_id: "project_2".to_string(),
-            relation_type: RelationType::Direct,
+            relation_type: RELATION_DIRECT.to_string(),
             tags: Vec::new(),
             color: default_relation_color(),
             width: default_relation_width(),
+            route: default_relation_route(),
         });
 
         Self {
@@ -252,6 +671,11 @@ impl Default for AppData {
             projects,
             relations,
             relation_tags: Vec::new(),
+            relation_tag_colors: IndexMap::new(),
+            project_tags: Vec::new(),
+            statuses: default_status_catalog(),
+            relation_types: default_relation_type_catalog(),
+            saved_views: Vec::new(),
         }
     }
 }
@@ -265,6 +689,346 @@ impl AppData {
             projects: IndexMap::new(),
             relations: Vec::new(),
             relation_tags: Vec::new(),
+            relation_tag_colors: IndexMap::new(),
+            project_tags: Vec::new(),
+            statuses: default_status_catalog(),
+            relation_types: default_relation_type_catalog(),
+            saved_views: Vec::new(),
+        }
+    }
+
+    /// 按ID查找状态
+    pub fn status(&self, id: &str) -> Option<&ProjectStatus> {
+        self.statuses.iter().find(|s| s.id == id)
+    }
+
+    /// 按 order 排序的状态目录
+    pub fn sorted_statuses(&self) -> Vec<&ProjectStatus> {
+        let mut statuses: Vec<&ProjectStatus> = self.statuses.iter().collect();
+        statuses.sort_by_key(|s| s.order);
+        statuses
+    }
+
+    /// 状态显示名称（未知状态显示ID）
+    pub fn status_name(&self, id: &str) -> String {
+        self.status(id).map(|s| s.name.clone()).unwrap_or_else(|| id.to_string())
+    }
+
+    /// 状态在排序后目录中的序号（用于配色方案、图案和符号）
+    pub fn status_index(&self, id: &str) -> usize {
+        self.sorted_statuses().iter().position(|s| s.id == id).unwrap_or(0)
+    }
+
+    /// 项目状态是否视为已完成
+    pub fn is_done(&self, project: &Project) -> bool {
+        self.status(&project.status).map(|s| s.counts_as_done).unwrap_or(false)
+    }
+
+    /// 新项目的默认状态：优先"待启动"，否则为排序最后的状态
+    pub fn default_status_id(&self) -> String {
+        if self.status(STATUS_TO_BE_STARTED).is_some() {
+            return STATUS_TO_BE_STARTED.to_string();
+        }
+        self.sorted_statuses().last().map(|s| s.id.clone()).unwrap_or_default()
+    }
+
+    /// 加载、导入或粘贴数据后统一补全：状态目录、关系类型目录、关系标签库和项目标签库
+    /// （旧数据文件没有这些目录，或其中引用了目录外的条目）
+    pub fn migrate(&mut self) {
+        self.ensure_status_catalog();
+        self.ensure_relation_type_catalog();
+        self.ensure_relation_tag_library();
+        self.ensure_project_tag_library();
+    }
+
+    /// 补全状态目录：项目引用了目录中不存在的状态时，按ID添加一个灰色状态
+    pub fn ensure_status_catalog(&mut self) {
+        let mut next_order = self.statuses.iter().map(|s| s.order).max().map(|o| o + 1).unwrap_or(0);
+        let default_catalog = default_status_catalog();
+        for project in self.projects.values() {
+            if self.statuses.iter().any(|s| s.id == project.status) {
+                continue;
+            }
+            let status = match default_catalog.iter().find(|s| s.id == project.status) {
+                Some(builtin) => ProjectStatus { order: next_order, ..builtin.clone() },
+                None => ProjectStatus::new(&project.status, &project.status, [158, 158, 158, 255], next_order, false),
+            };
+            self.statuses.push(status);
+            next_order += 1;
+        }
+    }
+
+    /// 按ID查找关系类型
+    pub fn relation_type(&self, id: &str) -> Option<&RelationType> {
+        self.relation_types.iter().find(|t| t.id == id)
+    }
+
+    /// 按顺序排列的关系类型
+    pub fn sorted_relation_types(&self) -> Vec<&RelationType> {
+        let mut types: Vec<&RelationType> = self.relation_types.iter().collect();
+        types.sort_by_key(|t| t.order);
+        types
+    }
+
+    /// 关系类型的显示名称（不在目录中时显示ID）
+    pub fn relation_type_name(&self, id: &str) -> String {
+        self.relation_type(id).map(|t| t.name.clone()).unwrap_or_else(|| id.to_string())
+    }
+
+    /// 关系的线型和箭头样式（类型不在目录中时为实线开放箭头）
+    pub fn relation_style(&self, relation: &ProjectRelation) -> (LineStyle, ArrowHead) {
+        self.relation_type(&relation.relation_type)
+            .map(|t| (t.line_style, t.arrow_head))
+            .unwrap_or((LineStyle::Solid, ArrowHead::Open))
+    }
+
+    /// 指定关系（data.relations 中的序号）用到的关系类型，按顺序排列（用于图例）
+    pub fn relation_types_used(&self, relation_indices: &[usize]) -> Vec<&RelationType> {
+        self.sorted_relation_types()
+            .into_iter()
+            .filter(|t| relation_indices.iter().any(|&i| self.relations.get(i).is_some_and(|r| r.relation_type == t.id)))
+            .collect()
+    }
+
+    /// 新建关系：颜色和线宽取关系类型的默认值
+    pub fn new_relation(&self, from_id: &str, to_id: &str, relation_type: &str) -> ProjectRelation {
+        let mut relation = ProjectRelation {
+            from_id: from_id.to_string(),
+            to_id: to_id.to_string(),
+            relation_type: relation_type.to_string(),
+            ..Default::default()
+        };
+        if let Some(t) = self.relation_type(relation_type) {
+            relation.color = t.color;
+            relation.width = t.width;
+        }
+        relation
+    }
+
+    /// 补全关系类型目录：关系引用了目录中不存在的类型时，内置类型按默认定义添加，其他按ID添加一个实线类型
+    pub fn ensure_relation_type_catalog(&mut self) {
+        let mut next_order = self.relation_types.iter().map(|t| t.order).max().map(|o| o + 1).unwrap_or(0);
+        let default_catalog = default_relation_type_catalog();
+        for relation in &self.relations {
+            if self.relation_types.iter().any(|t| t.id == relation.relation_type) {
+                continue;
+            }
+            let relation_type = match default_catalog.iter().find(|t| t.id == relation.relation_type) {
+                Some(builtin) => RelationType { order: next_order, ..builtin.clone() },
+                None => RelationType::new(&relation.relation_type, &relation.relation_type, LineStyle::Solid, ArrowHead::Open, next_order),
+            };
+            self.relation_types.push(relation_type);
+            next_order += 1;
+        }
+    }
+
+    /// 补全关系标签库：关系上使用但不在标签库中的标签加到末尾
+    pub fn ensure_relation_tag_library(&mut self) {
+        for relation in &self.relations {
+            for tag in &relation.tags {
+                if !self.relation_tags.contains(tag) {
+                    self.relation_tags.push(tag.clone());
+                }
+            }
+        }
+    }
+
+    /// 补全项目标签库：项目上使用但不在标签库中的标签加到末尾
+    pub fn ensure_project_tag_library(&mut self) {
+        for project in self.projects.values() {
+            for tag in &project.tags {
+                if !self.project_tags.contains(tag) {
+                    self.project_tags.push(tag.clone());
+                }
+            }
+        }
+    }
+
+    /// 使用某个标签的关系数
+    pub fn relation_tag_usage(&self, tag: &str) -> usize {
+        self.relations.iter().filter(|r| r.tags.iter().any(|t| t == tag)).count()
+    }
+
+    /// 关系的第一个设置了颜色的标签的颜色（用于关系标签文字）
+    pub fn relation_label_color(&self, relation: &ProjectRelation) -> Option<[u8; 4]> {
+        relation.tags.iter().find_map(|t| self.relation_tag_colors.get(t).copied())
+    }
+
+    /// 重命名关系标签；新名称已存在时合并到该标签（关系中的重复标签去掉，保留目标标签的颜色）
+    /// 同时改写所有关系和保存的视图中的隐藏标签
+    pub fn rename_relation_tag(&mut self, old: &str, new: &str) {
+        if old == new {
+            return;
+        }
+        let rename = |tags: &mut Vec<String>| {
+            if let Some(index) = tags.iter().position(|t| t == old) {
+                if tags.iter().any(|t| t == new) {
+                    tags.remove(index);
+                } else {
+                    tags[index] = new.to_string();
+                }
+            }
+        };
+        for relation in &mut self.relations {
+            rename(&mut relation.tags);
+        }
+        for view in &mut self.saved_views {
+            rename(&mut view.hidden_relation_tags);
+        }
+        rename(&mut self.relation_tags);
+        if let Some(color) = self.relation_tag_colors.shift_remove(old) {
+            self.relation_tag_colors.entry(new.to_string()).or_insert(color);
+        }
+    }
+
+    /// 删除关系标签：从标签库、所有关系和保存的视图中移除，并清除颜色
+    pub fn delete_relation_tag(&mut self, tag: &str) {
+        for relation in &mut self.relations {
+            relation.tags.retain(|t| t != tag);
+        }
+        for view in &mut self.saved_views {
+            view.hidden_relation_tags.retain(|t| t != tag);
+        }
+        self.relation_tags.retain(|t| t != tag);
+        self.relation_tag_colors.shift_remove(tag);
+    }
+
+    /// 领域的所有上级领域ID（由近到远），上级不存在或出现循环时停止
+    pub fn field_ancestors(&self, id: &str) -> Vec<String> {
+        let mut ancestors: Vec<String> = Vec::new();
+        let mut current = self.fields.get(id).and_then(|f| f.parent_id.clone());
+        while let Some(parent_id) = current {
+            if parent_id == id || ancestors.contains(&parent_id) || !self.fields.contains_key(&parent_id) {
+                break;
+            }
+            current = self.fields.get(&parent_id).and_then(|f| f.parent_id.clone());
+            ancestors.push(parent_id);
+        }
+        ancestors
+    }
+
+    /// 领域的嵌套层级：顶层领域为 0
+    pub fn field_depth(&self, id: &str) -> usize {
+        self.field_ancestors(id).len()
+    }
+
+    /// 直接下级领域ID（按领域顺序）
+    pub fn child_field_ids(&self, id: &str) -> Vec<String> {
+        self.fields.values()
+            .filter(|f| f.parent_id.as_deref() == Some(id))
+            .map(|f| f.id.clone())
+            .collect()
+    }
+
+    /// 所有下级领域ID（含多层嵌套）
+    pub fn field_descendants(&self, id: &str) -> Vec<String> {
+        self.fields.keys()
+            .filter(|f| self.field_ancestors(f).iter().any(|a| a == id))
+            .cloned()
+            .collect()
+    }
+
+    /// 项目实际所属的领域ID：直接选择的领域及其所有上级领域（去重，保持顺序）
+    pub fn effective_field_ids(&self, project: &Project) -> Vec<String> {
+        let mut ids: Vec<String> = Vec::new();
+        for field_id in project.field_ids.iter().filter(|f| self.fields.contains_key(*f)) {
+            for id in std::iter::once(field_id.clone()).chain(self.field_ancestors(field_id)) {
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            }
+        }
+        ids
+    }
+
+    /// 按层级排列的领域：上级领域后紧跟其下级领域，返回 (领域, 层级)
+    pub fn field_tree(&self) -> Vec<(&ResearchField, usize)> {
+        fn visit<'a>(data: &'a AppData, field: &'a ResearchField, depth: usize, out: &mut Vec<(&'a ResearchField, usize)>) {
+            if out.iter().any(|(f, _)| f.id == field.id) {
+                return;
+            }
+            out.push((field, depth));
+            for child_id in data.child_field_ids(&field.id) {
+                if let Some(child) = data.fields.get(&child_id) {
+                    visit(data, child, depth + 1, out);
+                }
+            }
+        }
+        let mut out = Vec::new();
+        let roots = self.fields.values()
+            .filter(|f| f.parent_id.as_ref().and_then(|p| self.fields.get(p)).is_none());
+        for field in roots {
+            visit(self, field, 0, &mut out);
+        }
+        // 上级链成环的领域（旧数据或手工编辑的文件）作为顶层领域列出
+        for field in self.fields.values() {
+            visit(self, field, 0, &mut out);
+        }
+        out
+    }
+
+    /// 设置领域的上级领域；不能设为自身或自身的下级领域
+    pub fn set_field_parent(&mut self, id: &str, parent_id: Option<String>) -> Result<(), String> {
+        if let Some(parent) = &parent_id {
+            if !self.fields.contains_key(parent) {
+                return Err(format!("上级领域不存在: {}", parent));
+            }
+            if parent == id || self.field_ancestors(parent).iter().any(|a| a == id) {
+                return Err("不能把领域设为自身或其下级领域的子领域".to_string());
+            }
+        }
+        let field = self.fields.get_mut(id).ok_or_else(|| format!("领域不存在: {}", id))?;
+        field.parent_id = parent_id;
+        Ok(())
+    }
+
+    /// 删除领域：下级领域改挂到被删领域的上级，项目中选择的该领域改为其上级领域（没有上级时直接移除）
+    pub fn delete_field(&mut self, id: &str) {
+        let Some(removed) = self.fields.shift_remove(id) else {
+            return;
+        };
+        for field in self.fields.values_mut() {
+            if field.parent_id.as_deref() == Some(id) {
+                field.parent_id = removed.parent_id.clone();
+            }
+        }
+        for project in self.projects.values_mut() {
+            if let Some(index) = project.field_ids.iter().position(|f| f == id) {
+                match &removed.parent_id {
+                    Some(parent) if !project.field_ids.contains(parent) => project.field_ids[index] = parent.clone(),
+                    _ => {
+                        project.field_ids.remove(index);
+                    }
+                }
+            }
+        }
+    }
+
+    /// 只包含指定项目的数据子集：保留这些项目所属的领域（含上级领域）和它们之间的关系，
+    /// 状态目录、关系类型目录、关系标签库和保存的视图原样保留（用于按查询范围导出）
+    pub fn subset(&self, project_ids: &[String]) -> AppData {
+        let projects: IndexMap<String, Project> = self.projects.iter()
+            .filter(|(id, _)| project_ids.contains(id))
+            .map(|(id, p)| (id.clone(), p.clone()))
+            .collect();
+        let fields = self.fields.iter()
+            .filter(|(id, _)| projects.values().any(|p| self.effective_field_ids(p).contains(id)))
+            .map(|(id, f)| (id.clone(), f.clone()))
+            .collect();
+        let relations = self.relations.iter()
+            .filter(|r| projects.contains_key(&r.from_id) && projects.contains_key(&r.to_id))
+            .cloned()
+            .collect();
+        AppData {
+            fields,
+            projects,
+            relations,
+            relation_tags: self.relation_tags.clone(),
+            relation_tag_colors: self.relation_tag_colors.clone(),
+            project_tags: self.project_tags.clone(),
+            statuses: self.statuses.clone(),
+            relation_types: self.relation_types.clone(),
+            saved_views: self.saved_views.clone(),
         }
     }
 }
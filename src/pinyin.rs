@@ -0,0 +1,456 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// 拼音匹配：汉字名称可以用全拼、首字母或两者混合搜索
+/// 如"wsw"、"weishengwu"、"weisw"都匹配"微生物"，多音字的常见读音都可匹配
+pub fn pinyin_matches(name: &str, term: &str) -> bool {
+    let term: Vec<char> = term.chars().map(|c| if c == 'ü' { 'v' } else { c }).collect();
+    if term.len() < 2 || !term.iter().all(|c| c.is_ascii_alphanumeric()) || !term.iter().any(|c| c.is_ascii_alphabetic()) {
+        return false;
+    }
+    let chars: Vec<char> = name.chars().collect();
+    if !chars.iter().any(|c| !syllables(*c).is_empty()) {
+        return false;
+    }
+    (0..chars.len()).any(|start| match_from(&chars[start..], &term))
+}
+
+/// 从名称开头逐字匹配搜索词：每个汉字消耗其某个读音的非空前缀，其他字符须原样匹配
+fn match_from(chars: &[char], term: &[char]) -> bool {
+    if term.is_empty() {
+        return true;
+    }
+    let (c, rest) = match chars.split_first() {
+        Some((&c, rest)) => (c, rest),
+        None => return false,
+    };
+    let readings = syllables(c);
+    if readings.is_empty() {
+        return c == term[0] && match_from(rest, &term[1..]);
+    }
+    readings.iter().any(|syllable| {
+        let common = syllable.chars().zip(term).take_while(|(s, t)| s == *t).count();
+        (1..=common).rev().any(|n| match_from(rest, &term[n..]))
+    })
+}
+
+/// 汉字的全部读音（无声调，ü 写作 v），非汉字或不在字表中时为空
+fn syllables(c: char) -> &'static [&'static str] {
+    static INDEX: OnceLock<HashMap<char, Vec<&'static str>>> = OnceLock::new();
+    let index = INDEX.get_or_init(|| {
+        let mut index: HashMap<char, Vec<&'static str>> = HashMap::new();
+        for (syllable, chars) in PINYIN_TABLE {
+            for c in chars.chars() {
+                index.entry(c).or_default().push(syllable);
+            }
+        }
+        index
+    });
+    index.get(&c).map(|v| v.as_slice()).unwrap_or(&[])
+}
+
+/// 拼音字表：读音 → 该读音的常用汉字（据 glibc 拼音排序表整理，多音字出现在多个读音下）
+const PINYIN_TABLE: &[(&str, &str)] = &[
+    ("a", "吖腌锕阿嗄啊"),
+    ("ai", "哀哎唉埃挨捱欸诶锿癌皑騃嗳噯毐矮蔼藹霭靄叆嫒愛暧曖爱瑷瞹碍礙艾譪隘鱫"),
+    ("an", "安庵桉氨盫諳谙鞍鹌俺唵揞铵岸按暗案犴胺菴闇鮟黯"),
+    ("ang", "肮骯昂盎"),
+    ("ao", "凹坳熬嗷囂廒摮敖獒磝翱聱螯遨鏖骜鳌媪袄襖傲奥奧岙懊澳鏊"),
+    ("ba", "八叭吧岜巴扒捌疤笆粑芭拔菝跋钹魃把鈀钯靶坝灞爸罢罷耙霸鲅杷"),
+    ("bai", "掰白佰捭摆擺百襬呗唄拜敗稗蛽败"),
+    ("ban", "扳搬斑班瘢癍般頒颁坂板版舨钣闆阪伴办半怑扮拌柈瓣絆绊辦"),
+    ("bang", "帮幫梆浜邦搒榜氆綁绑膀髈傍棒棓磅蒡蚌謗谤镑"),
+    ("bao", "剥勹包孢枹煲胞苞褒龅薄雹保堡媬宝寶葆褓飽饱鴇鸨刨嚗報报抱暴爆豹趵骲鮑鲍"),
+    ("bei", "俾卑埤悲揹杯盃碑禆裨陂鹎北倍備备孛悖惫憊焙狈狽碚糒背蓓被褙貝贝輩辈鐴钡鞴"),
+    ("ben", "奔泍賁贲锛本畚苯坌撪笨"),
+    ("beng", "嘣崩絣繃绷甭堋泵甏蹦迸镚"),
+    ("bi", "屄逼荸鼻匕吡妣彼比秕笔筆纰鄙哔嗶壁婢嬖币幣庇庳弊弻弼必愊愎敝斃毕毖毙泌濞狴璧畀畢痹痺碧筚箅篦聛臂苾荜蓖蔽薜襞贔跸躄辟避铋閉閟闢闭陛饆髀"),
+    ("bian", "煸砭笾編编蝙边邉邊鞭鳊匾扁碥窆褊貶贬便卞变弁忭抃汴苄變辨辩辫辯遍釆閞"),
+    ("biao", "幖彪摽杓标標灬瘭磦膘鏢鑣镖镳飆飑飙飚骠髟麃婊表裱錶俵鳔"),
+    ("bie", "憋瘪癟鳖別别徶蹩彆"),
+    ("bin", "傧宾彬斌槟檳滨濒濱瀕繽缤豳賓镔摈殡殯膑髌鬓鬢"),
+    ("bing", "兵冫冰丙廪昺柄炳禀秉稟鞞餅饼並併并摒病"),
+    ("bo", "剝啵拨撥播波玻缽菠钵饽亳伯僰勃博帛搏柏桲檗泊泺渤礴箔脖膊舶鎛铂駁驳髆鹁簸跛擗擘蘗卜薄蕃"),
+    ("bu", "晡逋钸餔不卟哺捕补補佈埔埗埠布怖步瓿簿部钚"),
+    ("ca", "傪嚓擦"),
+    ("cai", "偲猜才材纔裁財财彩採睬踩采菜蔡"),
+    ("can", "参參歺餐骖惭慚残殘蚕蠶惨慘孱摻灿燦璨粲"),
+    ("cang", "仓伧倉沧滄舱艙苍蒼藏"),
+    ("cao", "操糙嘈曹槽漕螬艸艹草肏"),
+    ("ce", "侧側冊册厕廁恻惻测測策粣"),
+    ("cen", "岑梣涔参"),
+    ("ceng", "噌层層嶒曽曾缯蹭"),
+    ("cha", "叉喳扠插杈锸馇垞察搽查楂槎檫猹碴茬茶衩蹅镲刹剎奼姹岔差汊詫诧"),
+    ("chai", "拆钗侪儕柴豺瘥虿差"),
+    ("chan", "掺搀攙觇單婵巉廛潺澶禅禪纏缠蝉蟬蟾谗躔馋产冁刬剗剷產蕆諂谄铲闡阐骣忏懺羼韂顫"),
+    ("chang", "伥娼昌猖菖阊鲳偿償嘗嚐場嫦尝常徜粻肠腸苌裳長长厂场廠惝敞氅倡唱怅悵暢畅鬯"),
+    ("chao", "剿抄绰超鈔钞嘲巢晁潮濤吵炒朝"),
+    ("che", "砗車车扯坼彻徹掣撤澈轍"),
+    ("chen", "嗔抻琛瞋郴塵娠宸尘忱晨沉臣蔯谌辰陈陳碜沈"),
+    ("cheng", "榇称稱衬襯讖谶趁龀撐撑柽牚琤瞠竀蛏赪鐺铛丞乘呈城埕塍惩懲成承晟枨橙澄珹盛程裎誠诚逞骋秤"),
+    ("chi", "吃哧喫嗤媸瓻痴癡眵笞蚩螭郗魑鸱黐匙坻墀弛持池沱痄箎篪茌踟迟遲馳驰侈叺呎尺恥耻褫豉齒齿傺勅叱啻彳敕斥栻炽熾瘛眙翅赤饬"),
+    ("chong", "傭充冲忡憧沖湧舂衝崇虫蟲宠寵铳重种"),
+    ("chou", "抽犨瘳仇俦惆愁椆畴疇稠筹籌綢绸裯讎踌躊酬雠丑吜瞅醜溴臭"),
+    ("chu", "出初樗儲刍厨廚恹懨橱櫥滁蜍蹰躇锄除雏鶵储憷杵楚楮础礎處褚亍俶处怵搐欪畜矗絀绌触觸踀鄐黜"),
+    ("chuai", "揣膗嘬踹"),
+    ("chuan", "川氚穿传傳椽舡船遄喘舛蝽串釧钏"),
+    ("chuang", "創牎疮瘡窗噇幢床疒搶闖闯创怆"),
+    ("chui", "吹炊垂捶搥桘棰椎槌箠錘锤陲"),
+    ("chun", "春椿瑃萅唇淳純纯脣莼醇鶉鹑蠢"),
+    ("chuo", "戳踔啜娖惙辍辶逴齱龊"),
+    ("ci", "呲庛疵赼兹慈枱濨瓷磁祠粢糍茨詞词辞辭雌鹚此泚跐伺刺次蚝螅賜赐差"),
+    ("cong", "匆囱從枞瑽璁聪聰苁葱蓯蔥骢丛从叢淙琮錝欉"),
+    ("cou", "凑湊腠辏"),
+    ("cu", "粗麄徂殂促卒猝簇蔟趨蹙蹴酢醋"),
+    ("cuan", "撺汆蹿镩攒攢濽爨窜竄篡"),
+    ("cui", "催崔摧榱獕隹璀皠啐悴橇毳淬瘁粹綷翠脆萃"),
+    ("cun", "村皴邨存忖吋寸"),
+    ("cuo", "搓撮磋蒫蹉嵯痤矬鹾脞剒厝挫措錯锉错"),
+    ("da", "哒嗒噠搭答耷褡妲怛沓瘩笪达達靼鞑打大疸跶"),
+    ("dai", "呆呔嘚傣歹逮代叇埭岱带帶待怠戴殆玳甙绐袋貸贷迨骀黛大"),
+    ("dan", "丹儋单擔殚瘅眈箪耽聃襌郸亶掸撢胆膽黵但啖嘾噉彈惮憚担旦氮淡澹膻萏蛋誕诞赡钽駳弹"),
+    ("dang", "噹当珰當裆襠党挡擋檔谠黨凼宕档氹盪砀荡菪蕩"),
+    ("dao", "刀刂叨啁氘倒导導岛島捣搗梼祷禂禱蹈到悼焘盗盜稻纛道"),
+    ("de", "得徳德锝的地"),
+    ("dei", "得"),
+    ("deng", "哋噔灯燈登蹬鐙镫戥等凳嶝櫈瞪磴邓鄧"),
+    ("di", "低嘀堤氐滴羝袛镝嫡敌敵涤滌犒狄笛籴翟苖荻莜藋觌迪呧底抵柢砥诋邸骶地娣帝弟旳棣睇碲第缔菂蒂諦谛踶递遞的提"),
+    ("dia", "嗲"),
+    ("dian", "巅巔掂攧敁滇癫癲顛颠典点碘蕇踮點佃坫垫墊奠店惦殿淀澱玷电甸癜簟钿阽電靛"),
+    ("diao", "凋刁叼彫碉貂雕鲷鵰屌吊弔掉調调釣钓铞铫"),
+    ("die", "爹褺跌叠喋垤堞揲牒瓞疊碟絰绖耋蝶詄諜谍蹀迭鰈鲽鳎"),
+    ("ding", "丁仃叮玎町疔盯耵虰酊釘钉頂顶鼎啶定碇腚訂订錠铤锭饤"),
+    ("diu", "丟丢"),
+    ("dong", "东冬咚岽東氡涷鸫鼕懂董侗冻凍动動峒恫栋棟洞硐胨胴"),
+    ("dou", "兜吺篼蔸都唗抖斗蚪陡鬥痘窦竇讀豆逗饾"),
+    ("du", "嘟督闍椟毒渎瀆牍犊独獨读頓髑黩堵睹笃篤覩賭赌妒妬度杜橐渡肚蠹镀都"),
+    ("duan", "端短断斷椴段煅緞缎鍛锻"),
+    ("dui", "堆兑对對怼憝碓薱队隊"),
+    ("dun", "吨噸墩惇敦礅蹲盹趸囤扽楯沌炖燉盾砘遁鈍钝顿"),
+    ("duo", "咄哆多掇裰夺奪敠踱铎哚垛埵朵缍躲锗剁堕墮惰柁舵跺驮鵽"),
+    ("e", "婀屙俄囮娥峨莪蛾訛誐讹锇額额鵝鹅惡桠椏卾厄呃啞噩垩垭堊愕扼腭萼蕚谔轭遏鄂锷阏阨颚餓饿鰐鱷鳄鹗"),
+    ("en", "恩蒽摁嗯"),
+    ("er", "儿兒唲而尔洱爾珥耳迩铒饵駬二佴贰"),
+    ("fa", "发発發乏伐垡筏罚罰藅阀法灋砝珐髪髮"),
+    ("fan", "帆幡旛番繙翻蕃藩凡樊烦煩燔璠矾籵繁蘩蹯钒反返梵氾泛犯畈範范販贩飯饭"),
+    ("fang", "匚坊方枋芳邡钫妨房肪防鲂仿彷昉紡纺舫訪访放"),
+    ("fei", "啡妃扉绯菲蜚霏非飛飞騑鲱淝肥腓匪悱斐榧篚翡誹诽俷吠屝废廢怫沸狒痱癈砩肺芾費费镄"),
+    ("fen", "分吩昐氛紛纷芬衯酚坟墳枌棼汾焚鼢粉份偾奋奮忿愤憤粪糞"),
+    ("feng", "丰封峯峰枫楓沣烽疯瘋砜葑蜂蠭諷豊豐酆鋒锋風风冯縫缝逢讽俸凤奉甮鳳"),
+    ("fiao", "覅"),
+    ("fo", "佛"),
+    ("fou", "罘否缶"),
+    ("fu", "伕呋夫孵敷稃紨肤膚趺跗鄜麸伏俘凫匐咈孚巿幅幞弗彿扶拂服桴氟浮涪祓福符紼絥縛绂绋芙苻茀茯莩菔蚨蜉蝠袱辐郛鵩黻俯呒府抚拊撫斧滏甫簠脯腐腑莆輔辅釜頫黼付傅副咐复妇婏婦富復榑父缚胕腹蝮複覆讣負賦负赋赙赴阜阝附馥驸鲋"),
+    ("ga", "伽呷嘎嘠旮噶軋轧钆尕尬"),
+    ("gai", "垓絯荄該该赅陔改絠丐概溉盖芥葢蓋鈣钙"),
+    ("gan", "咁坩尴尷干幹杆柑泔甘疳矸竿筸肝苷酐感擀敢桿橄秆赶趕旰淦紺绀贛赣"),
+    ("gang", "冈刚剛岗岡崗扛杠綱纲缸罡肛鋼钢颃港戆"),
+    ("gao", "槔櫜皋睪睾篙糕羔膏高搞攪杲槁稿缟藁镐吿告诰郜锆"),
+    ("ge", "仡割咯哥圪戈搁擱歌疙纥肐胳菏袼鴿鸽嗝嘅塥搿格槅膈葛蛤裓轕铬閣閤阁隔革颌骼髂鬲哿笴舸騔个個各箇虼合"),
+    ("gei", "給给"),
+    ("gen", "根跟哏艮亘亙茛"),
+    ("geng", "庚畊絙絚羹耕赓鹒哽埂梗绠耿頚頸颈骾鲠恆更"),
+    ("gong", "供公功宫宮工弓恭攻肱蚣觥躬龚巩廾拱汞珙礦鞏共貢贡"),
+    ("gou", "佝勾枸沟溝篝缑鉤钩鞲岣狗苟茍唦垢够夠媾彀撀构構瞉觏诟購购遘"),
+    ("gu", "估呱咕嗗姑孤柧沽痼箍菇菰蛄觚轱辜酤钴鮕鸪古嘏榖毂汩牯瞽穀罟羖股臌蛊蠱詁诂谷賈骨鵠鹄鹘鼓僱固崮故梏錮锢雇顧顾鲴"),
+    ("gua", "刮栝瓜瘑胍腡蝸趏颳鸹剐叧寡卦挂掛罣褂诖"),
+    ("guai", "乖拐柺怪恠"),
+    ("guan", "倌关官棺瘝矜綸纶莞觀观關鳏琯管館馆冠惯慣掼摜灌盥罐貫贯鹳"),
+    ("guang", "光咣桄胱銧广廣犷獷逛"),
+    ("gui", "傀圭归歸瑰璝皈硅袿規规閨闺鬶鮭鲑龜龟姽宄庋晷氿癸簋詭诡軌轨鬼刽刿匮柜桂桧櫃炔貴贵跪鳜"),
+    ("gun", "滚滾磙绲衮袞辊鲧棍"),
+    ("guo", "啯嘓埚崞涡渦聒蝈過郭鍋锅国國帼掴腘虢馘果椁猓蜾裹过"),
+    ("ha", "哈铪蝦"),
+    ("hai", "咳嗨孩还還頦颏骸海醢亥嗐害氦駭骇"),
+    ("han", "憨蚶酣頇顸鼾函含寒晗涵焓邗邯韓韩喊罕悍憾扞捍撖撼旱汉汗漢瀚焊熯翰菡蛿頷颔"),
+    ("hang", "夯吭杭桁絎航行鸻巷沆"),
+    ("hao", "侾蒿薅嗥嘷嚎壕毫濠號蠔豪貉鶴好郝号昊浩灏皓耗颢"),
+    ("he", "呵喝嗬訶诃何劾合和曷核河涸盍盒禾翮荷蚵趷闔阂阖鞨龢嚇壑癋褐賀贺赫鶮鹤"),
+    ("hei", "嘿黑黒"),
+    ("hen", "痕很狠恨"),
+    ("heng", "亨哼佷恒横橫珩蘅衡黉撔"),
+    ("hong", "吽呍哄烘薨訇轟轰宏弘泓洪玒竤粠紅红纮蕻虹讧鉷闳鴻鸿"),
+    ("hou", "澒齁侯喉猴瘊篌糇骺吼候厚后堠後逅鲎"),
+    ("hu", "乎吰呼唿忽惚戲滹烀猢餬囫壶壺弧搰斛槲湖煳狐瑚糊胡葫蝴衚觳醐鬍鹕唬浒琥虎許互嚛岵怙戶户戸戽扈护楛沍沪瓠祜笏護核和"),
+    ("hua", "哗嘩砉花劃华浍滑狯猾華豁铧骅划化婳桦画畫話话"),
+    ("huai", "徊怀懷槐淮踝咶坏壞孬"),
+    ("huan", "欢歡獾圜寰桓洹澴狟环環缳苋萑郇锾鬟緩缓唤喚奂宦幻患换換浣涣漶焕煥痪瘓瞣脘豢逭鰀鲩还"),
+    ("huang", "慌肓荒偟凰徨惶湟潢煌璜皇磺篁簧蝗蟥遑隍黃黄幌恍晃謊谎"),
+    ("hui", "咴徽恢挥揮晖灰珲虺褌詼诙輝辉隳麾囘回囬廻廽洄茴蚘蛔迴逥悔毀毁譭会匯卉叀哕喙嘒彗恚惠慧晦會汇潰烩秽穢繪绘荟蕙蟪諱讳诲贿钺"),
+    ("hun", "婚昏荤葷阍浑混渾馄魂溷諢诨"),
+    ("huo", "嚄活钬伙夥火嚯嚿惑或攉濩獲祸禍穫获藿蠖貨货镬霍和"),
+    ("ji", "乩击剞叽咭唧嘰圾基墼姬屐嵇幾擊机機激犄玑璣畸畿矶磯积稽積笄箕緝績绩缉羁羈肌脔芨萁虮譏讥赍跡跻蹟迹雞飢饥鷄鸡齑亟佶即及吉嫉岌急戢极棘楫極殛汲疾瘠秸笈籍級级脊蒺蕀蕺藉蝍輯辑集鹡几己戟挤掎擠濟麂伎偈冀剂劑哜妓季寂寄彐忌悸技既暨洎济祭稷紀繫繼纪继芰荠蓟觊計記计记跽际際霁騎骥髻鯽鲫系给奇期"),
+    ("jia", "佳傢加嘉夹夾家枷浃浹珈痂笳茄葭袈跏迦镓唊恝戛挾硈荚莢蛱袷郏铗頬頰颊假岬撟甲矯絞繳胛贾钾餃价價嫁架稼駕驾"),
+    ("jian", "兼坚堅奸姦尖戋揃搛樫歼殲淺湔溅漸濺煎牋犍监監笺緘缄缣肩艰艱菅蒹間间鞬鞯鬋鲣鳒鶼鹣俭儉减剪囝戩戬拣捡撿暕枧柬检檢減睑硷碱笕简簡翦茧裥襇謇谫趼蹇锏鹼件健僭剑劍劔建槛檻毽洊涧渐澗箭繝腱舰艦荐薦見见谏谮賤贱践踐鉴鍵鑑鑒键閒饯鰎"),
+    ("jiang", "僵姜将將殭江浆漿疆缰薑豇韁奖桨槳獎耩蒋蔣講讲匠強洚犟糨绛酱醬降强"),
+    ("jiao", "交喬姣娇嬌教椒浇湫澆焦矫礁胶膠茭荞菽蕉蛟蟭跤郊驕骄鮫鲛鹪嚼佼侥僥徼挢搅敫狡皎绞缴脚腳蟜角蹻較铰饺叫噍峤窖覺轎轿较酵醮校"),
+    ("jie", "喈嗟接揭疖癤皆節結街锴阶階傑劫劼婕孑截拮捷杰栉桀桔洁潔睫碣竭絜结羯节詰讦诘迼頡颉鲒姐檞解介借唶屆届戒玠界疥蚧褯誡诫"),
+    ("jin", "今巾斤津筋衿襟金钅仅僅儘卺堇廑槿瑾盡紧緊菫覲謹谨錦锦馑勁噤妗尽晉晋殣浸烬燼禁缙荩蓳觐近进進靳"),
+    ("jing", "京兢惊旌旍晶泾涇猄睛箐粳精經经茎荆荊莖菁驚鯨鲸井儆刭剄憬景暻烃璟肼警阱净凈劲境婧弪径徑敬淨獍痉竞竟競胫迳逕鏡镜陉靓靖静靜"),
+    ("jiong", "冂冋坰垧扃絅駉泂炯窘迥"),
+    ("jiu", "啾揪牞究糾纠觓赳阄鬏鳩鸠久九灸玖酒韭韮僦厩咎就廄救旧柩桕疚臼舅舊鹫"),
+    ("ju", "俱居拘掬據椐狙琚疽苴裾趄鋸锔雎鞠鞫駒驹侷局椈橘焗菊鶪举咀擧榉榘櫸沮矩筥舉莒蒟蝺踽龃佢倨具剧劇句屦巨惧懼拒据炬犋瞿窭簴絇聚苣菹讵距踞遽鉅钜锯飓"),
+    ("juan", "圏娟捐涓睃蠲镌鵑鹃卷捲锩倦儁狷眷绢鄄隽"),
+    ("jue", "噘撅鞒倔决劂厥噱堀孓崛抉掘攫桷橛決爝爵獗珏矍絕绝蕨蠼觉訣譎诀谲蹶镢角"),
+    ("jun", "军君均皲筠莙菌軍鈞钧麇麕俊峻懏捃浚濬珺竣箘蕈郡駿骏"),
+    ("ka", "咔咖喀卡"),
+    ("kai", "开揩锎開闿凯凱剀垲恺愷慨楷蒈豈铠忾愾"),
+    ("kan", "刊勘堪戡龕龛侃坎崁欿砍嵌看瞰磡阚"),
+    ("kang", "嵻康慷槺糠鏮闶鱇亢伉抗炕犺钪"),
+    ("kao", "尻拷栲烤考铐靠"),
+    ("ke", "嗑坷峇柯棵珂疴瞌砢磕科稞窠苛蝌軻轲顆颗髁壳殼可岢渴克刻剋客恪氪溘缂課课锞骒"),
+    ("ken", "啃垦恳懇肯颀龈掯裉"),
+    ("keng", "坑鏗铿"),
+    ("kong", "倥崆箜孔恐控空"),
+    ("kou", "抠摳眍芤口叩寇扣筘蔲蔻釦"),
+    ("ku", "刳哭枯矻窟骷苦喾库庫絝绔袴裤褲趶酷"),
+    ("kua", "夸誇侉垮挎胯跨"),
+    ("kuai", "蒯侩哙块塊快筷脍膾蒉郐会"),
+    ("kuan", "宽寬髋款"),
+    ("kuang", "匡哐框筐诓狂诳夼况圹壙旷曠況眶矿絋纩贶邝"),
+    ("kui", "亏岿盔窥窺虧喹夔奎揆暌櫆睽葵蘷蝰逵隗馗骙魁跬喟愦愧溃篑簣聩聵馈"),
+    ("kun", "坤堃崐崑昆琨裈醌锟髡鯤鲲壼悃捆綑裍阃困睏"),
+    ("kuo", "廓扩括擴蛞適闊阔"),
+    ("la", "喇垃拉邋剌旯砬瘌腊臘蜡蝲蠟辣镴啦"),
+    ("lai", "來唻崃徕来涞莱萊铼濑瀨癞睐籁籟賴赉赖"),
+    ("lan", "兰婪岚嵐拦攔斓栏欄澜瀾篮籃籣蓝藍蘭褴襤谰钄镧闌阑壈懒懶揽攬榄欖漤纜缆覽览滥濫烂爛"),
+    ("lang", "啷廊榔狼琅稂艆莨蓈螂郎锒阆崀悢朗浪"),
+    ("lao", "捞撈劳勞唠崂栎涝牢痨醪铹佬姥栳潦老铑嫪烙絡酪"),
+    ("le", "嘞肋乐仂叻扐樂泐簕了"),
+    ("lei", "勒擂嫘檑礌纍缧羸镭雷儡垒壘漯磊絫耒蕾诔泪淚类累酹類"),
+    ("leng", "棱塄崚楞稜冷愣"),
+    ("li", "厘喱嫠梨漓犁狸璃离篱籬缡罹蓠藜蜊蠡貍酾醨釐離骊鹂麗黎黧俚哩娌李浬澧理礼禮裏裡逦邐醴里锂鯉鲤鳢丽例俐俪傈儷利力励勵历厉厲吏呖唳嚦坜壢慄戾暦曆枥栗歷沥溧瀝猁疠疬痢砺砾礪礫立笠粒粝荔莅莉蘚蛎詈跞轹郦隶隸雳靂"),
+    ("lia", "倆"),
+    ("lian", "奁帘廉怜憐槤涟漣濂簾联聯莲蓮裢连連鐮镰鲢敛斂琏脸臉裣娈恋戀楝殓潋炼煉練练鍊鏈链鰊"),
+    ("liang", "凉墚梁椋樑涼粮粱糧良輬量两俩兩魉亮哴晾諒谅踉輛辆"),
+    ("liao", "撩蹽僚嘹嫽寥寮摎燎獠疗療繚缭聊辽遼鹩憭暸瞭蓼蟟钌尥廖撂料镣了"),
+    ("lie", "咧冽列劣埒捩洌烈猎獵裂趔躐鬣"),
+    ("lin", "临啉嶙林淋燐琳痳瞵磷粼臨辚遴邻鄰霖鱗鳞麐麟凛凜懍懔檩吝恡悋蔺赁躏躪拎"),
+    ("ling", "伶凌呤囹岭柃棂櫺泠淩灵玲琌瓴睖绫羚翎聆舲苓菱蛉酃鈴铃陵零靈鯪鸰齡龄嶺領领令另"),
+    ("liu", "溜熘刘劉嬼旒榴流浏瀏琉留瘤硫蓅遛鎏鏐镏馏骝柳绺蒌铆六碌蹓陸鹨"),
+    ("long", "咙嚨朧栊泷瀧珑瓏眬矓砻窿笼籠聋聾胧茏蘢隆龍龙垄垅拢攏陇衖"),
+    ("lou", "搂摟偻喽嘍娄婁楼樓耧艛蔞蝼髅嵝甊篓簍漏瘘镂陋露"),
+    ("lu", "噜嚕撸卢垆庐栌泸炉爐盧纑胪舻芦蘆蠦轳顱颅鲈鸬卤掳擄橹櫓氇虏虜镥魯鲁鹵录戮渌漉潞璐祿禄箓簏籙赂路辂辘醁錄陆騄鷺鹭鹿麓露"),
+    ("luan", "圝孪峦巒挛栾滦癴銮鸾卵乱亂"),
+    ("lue", "掠略"),
+    ("lun", "抡仑伦侖倫囵崙惀沦淪論輪轮论"),
+    ("luo", "啰囉捋羅椤猡箩罗萝蘿螺逻邏锣镙饠骡瘰蠃裸鎯摞洛珞硌络荦落雒駱骆"),
+    ("lv", "榈櫚闾驢驴侣侶吕呂屡屢履旅縷缕膂褛褸鋁铝卛律慮氯滤濾率綠绿虑"),
+    ("ma", "嗎妈媽嬤嬷蔴蚂螞吗蟆麻麼杩犸玛瑪码碼馬马罵骂嘛"),
+    ("mai", "埋霾买荬買劢卖脈脉賣迈邁麥麦"),
+    ("man", "嫚颟瞒瞞蛮蠻谩蹣鞔饅馒鬘鳗満满滿螨墁幔慢曼漫熳缦蔓镘"),
+    ("mang", "牤尨忙氓盲硭芒茫邙铓莽蟒"),
+    ("mao", "猫貓旄毛牦矛茅蝥蟊錨锚髦冇卯峁昴泖茆冒帽懋毷瑁眊瞀耄茂袤貌貿贸"),
+    ("me", "么"),
+    ("mei", "呅媒嵋枚梅楣沒没湄煤玫眉脢苺莓酶镅霉每浼渼美镁妹媚寐昧沬痗眛袂謎谜魅"),
+    ("men", "悶们們扪捫钔門门懑懣焖闷"),
+    ("meng", "幪曚朦檬濛甍盟瞢矇苎萌虻黾勐懵猛艋蒙蜢蠓锰夢孟梦溕"),
+    ("mi", "咪眯瞇弥彌攠瀰猕祢糜縻蘼迷醚靡麋弭敉眫米脒芈冖嘧宓密幂汨祕秘糸纟蜜覓觅謐谧"),
+    ("mian", "棉眠綿绵免冕勉娩沔渑湎眄緬缅腼面麵"),
+    ("miao", "喵描瞄苗杪淼渺眇秒緲缈藐邈妙庙廟繆缪"),
+    ("mie", "乜咩搣滅灭篾蔑"),
+    ("min", "岷旻民玟珉缗悯愍憫抿敏泯湣皿闵闽鳘"),
+    ("ming", "冥名明暝溟眀瞑茗蓂螟銘铭鳴鸣酩命"),
+    ("miu", "謬谬"),
+    ("mo", "摸嫫摩摹模無磨糢膜蘑谟馍魔麽抹嗼塻墨寞末歿殁沫漠獏瘼秣茉莫萬蓦蟔貊镆陌靺驀默"),
+    ("mou", "哞侔呣牟眸謀谋鍪某"),
+    ("mu", "亩姆拇母牡仫募坶墓幕慕暮木沐牧目睦穆苜钼鹜"),
+    ("na", "拿挐镎哪吶呐娜捺納纳肭衲那钠魶"),
+    ("nai", "乃奶妳氖艿倷奈柰耐萘鼐"),
+    ("nan", "囡侽南喃楠男难難腩赧"),
+    ("nang", "囊囔馕攮曩"),
+    ("nao", "呶挠撓桡猱蛲铙垴恼惱瑙脑腦淖闹鬧"),
+    ("ne", "讷呢"),
+    ("nei", "餒馁內内"),
+    ("nen", "嫩恁"),
+    ("neng", "能"),
+    ("ni", "妮倪尼怩泥猊蜺铌霓鲵伲你拟擬旎匿惄慝昵暱溺睨腻膩逆"),
+    ("nian", "拈蔫年粘鲇鲶黏捻撵碾辇辗唸埝廿念"),
+    ("niang", "娘孃酿釀"),
+    ("niao", "嬲茑袅裊鳥鸟尿氽脲"),
+    ("nie", "捏苶啮嗫囁孽涅聂臬蘖蹑躡镊镍陧颞"),
+    ("nin", "您"),
+    ("ning", "凝咛嚀宁寧拧擰柠檸狞獰甯聍苧佞泞"),
+    ("niu", "妞牛忸扭紐纽鈕钮拗蚴"),
+    ("nong", "侬农哝噥浓濃秾脓膿農弄"),
+    ("nou", "耨"),
+    ("nu", "奴孥胬驽努弩怒"),
+    ("nuan", "暖"),
+    ("nue", "疟虐謔谑"),
+    ("nuo", "傩挪喏懦挼搦糯諾诺锘"),
+    ("nv", "女钕恧衄"),
+    ("o", "喔噢哦"),
+    ("ou", "區呕嘔欧歐殴毆沤瓯讴鷗鸥偶吘耦藕怄区"),
+    ("pa", "啪葩趴爬琶筢帕怕"),
+    ("pai", "拍俳徘排牌派湃蒎"),
+    ("pan", "攀潘丬槃爿盘盤磐縏蟠跘踫蹒判叛拚泮畔盼袢襻"),
+    ("pang", "乓滂厖庞徬旁螃逄龐耪胖"),
+    ("pao", "抛拋脬匏咆庖狍袍跑泡炮疱砲"),
+    ("pei", "呸胚醅培裴賠赔锫陪佩帔旆沛珮辔配霈"),
+    ("pen", "喷噴湓盆"),
+    ("peng", "嘭怦抨澎烹砰閛彭朋棚淜硼篷膨芃蓬蟛鬅鵬鹏捧碰"),
+    ("pi", "丕劈匹噼坯批披砒苤邳铍霹啤枇毗毘琵疲皮罴脾膍蚍蜱貔郫陴鼙仳圮庀疋痞癖僻媲屁淠甓睤睥譬鸊"),
+    ("pian", "偏篇翩胼谝蹁骈片騙骗便"),
+    ("piao", "剽嘌慓漂縹缥飄飘嫖朴瓢殍瞟票"),
+    ("pie", "撇瞥丿"),
+    ("pin", "姘拼嫔蘋貧贫頻顰频颦品牝聘"),
+    ("ping", "乒俜娉凭呯坪屏平憑枰玶瓶苹萍評评鲆"),
+    ("po", "坡泼潑癹钋钷颇婆皤鄱叵笸頗岶珀破粕迫魄"),
+    ("pou", "剖吥抔掊裒哣"),
+    ("pu", "仆僕噗扑撲攵鋪铺匍樸濮璞菩葡蒲酺镤圃普浦溥譜谱蹼镨曝瀑舖"),
+    ("qi", "七凄嘁妻悽慼戚期柒栖棲槭欹欺沏淒漆萋諆谿蹊郪亓俟其圻埼奇岐崎扺旗棋歧淇琦琪璂畦碁祁祇祈祺綦耆脐臍芪蕲蘄蛴跂錡锜骐骑鳍麒齊齐乞企启啟屺岂杞綮綺绮起器契弃憇憩棄欫气氣汔汽泣砌碛葺訖讫趿迄"),
+    ("qia", "掐恰殎洽卡"),
+    ("qian", "仟佥千奷悭愆扦搴牵牽签簽籤羟芊褰謙谦迁遷鉛钎铅阡韆骞鹐乾前掮潛潜箝荨蕁虔錢钤钱钳黔浅缱膁譴谴遣倩堑慊椠欠歉纤芡茜蒨蔳"),
+    ("qiang", "呛嗆戕戗枪槍羌腔蜣跄蹌鏘锵镪墙嫱强樯牆蔷薔抢襁炝"),
+    ("qiao", "劁悄敲缲跷蹺鍬锹骹乔侨僑憔桥樵橋瞧翘翹谯巧愀鵲俏峭撬窍竅诮鞘"),
+    ("qie", "切且唼妾怯惬挈窃竊箧鍥锲"),
+    ("qin", "亲侵欽衾親钦勤嗪噙擒檎琴禽秦耹芩芹螓覃寝寢吣唚揿沁"),
+    ("qing", "倾傾卿圊氢氫清狅蜻輕轻青頃鲭勍情擎晴檠氰黥請请顷庆慶磬罄"),
+    ("qiong", "銎卭琼璚穷穹窮筇茕蛩跫邛"),
+    ("qiu", "丘偢楸秋蚯邱鞦鳅俅叴囚巯毬求泅球虬虯蝤裘逑遒酋糗"),
+    ("qu", "佉区屈岖岨嶇祛蛆蛐覷诎趋躯軀驅驱麯麴麹黢劬欋氍渠癯磲蕖蘧衢取娶曲龋去觑趣闃阒戌"),
+    ("quan", "圈悛絟全拳权權泉犬痊荃蜷詮诠踡醛铨颧鬈犭畎绻券劝"),
+    ("que", "勸缺闕阙瘸却卻悫慤榷确確阕雀鹊"),
+    ("qun", "逡羣群裙"),
+    ("ran", "然燃蚺髯冉染苒"),
+    ("rang", "嚷瓤禳穰壤攘讓让"),
+    ("rao", "娆荛饒饶扰擾繞绕"),
+    ("re", "惹热熱"),
+    ("ren", "人亻仁壬銋忍稔荏仞任刃妊絍纫葚衽認认轫韌韧饪"),
+    ("reng", "扔仍礽"),
+    ("ri", "日"),
+    ("rong", "容嵘戎榕榮溶熔狨絨绒茸荣蓉蝾融蠑鎔镕鰫冗"),
+    ("rou", "揉柔糅葇蹂鞣肉"),
+    ("ru", "儒嚅如孺濡茹薷蠕襦铷颥乳汝辱傉入洳溽缛蓐褥壖"),
+    ("ruan", "朊軟软阮"),
+    ("rui", "蕤蕊蕋叡枘瑞睿芮蚋銳锐"),
+    ("run", "润潤闰"),
+    ("ruo", "偌弱爇箬若蒻"),
+    ("sa", "仨撒洒灑靸卅萨薩飒"),
+    ("sai", "噻塞揌毢腮鳃賽赛"),
+    ("san", "三叁毵毿糁伞傘繖鏾馓散"),
+    ("sang", "丧喪桑嗓搡磉颡"),
+    ("sao", "搔缫臊騷骚埽嫂扫掃瘙"),
+    ("se", "啬嗇涩澀瑟穑色轖铯"),
+    ("sen", "森"),
+    ("seng", "僧"),
+    ("sha", "杀杉殺沙煞痧砂紗纱莎蔱裟赊铩鲨啥傻厦帹廈歃霎"),
+    ("shai", "筛篩晒曬色"),
+    ("shan", "删刪姗山扇搧杦栅潸煽珊舢芟苫衫跚钐睒閃闪陕陝剡善嬗掞摲擅汕疝缮膳蟮訕讪鄯骟鳝单"),
+    ("shang", "伤傷商墒殇殤湯漡熵觞丄晌賞赏上尚绱"),
+    ("shao", "捎梢烧燒稍筲艄蛸勺芍苕韶少劭哨潲紹绍邵"),
+    ("she", "奢猞畲佘舌蛇铊捨厍射慑懾摄攝歙涉渉社舍葉設设赦麝折"),
+    ("shen", "伸呻深燊珅申砷紳绅莘诜身神哂婶审審沈渖瀋瞫矧谂愼慎椹渗滲甚瘆瘮肾脤腎蜃参什"),
+    ("sheng", "升声昇牲生甥笙聲繩绳冼省眚剩勝圣嵊聖胜賸"),
+    ("shi", "噓失尸屍师師施湿溼濕狮獅蓍虱詩诗鰤鲺什十实寔實拾时時湜石碩莳蚀蝕識识食饣鲥使史始屎矢豕駛驶世事仕佀侍势勢唑嗜噬士室峙市式弑弒恃拭是柿氏示礻筮舐螫褆視视試誓试谥跩轼适逝释釋铈飾饰鳀似"),
+    ("shou", "收守手扌艏首兽受售壽寿授狩獸痩瘦綬绶"),
+    ("shu", "书倏叔姝抒摅摴書枢梳樞殊殳毹淑疏紓纾舒蔬輸输塾孰熟秫襡贖赎属屬數暑曙署薯蜀黍鼠兪墅庶恕戍数术束树樹沭漱澍竖絉腧蒁術豎述"),
+    ("shua", "刷唰耍"),
+    ("shuai", "摔衰甩帅帥蟀率"),
+    ("shuan", "拴栓閂闩涮"),
+    ("shuang", "双孀雙霜骦鸘塽爽"),
+    ("shui", "誰谁水氵帨睡稅税說説"),
+    ("shun", "吮瞬舜順顺"),
+    ("shuo", "说嗍妁搠朔槊烁爍硕蒴鑠铄数"),
+    ("si", "丝俬厮厶司咝嘶廝思撕斯澌私絲缌罳虒蛳锶鷥鸶死亖似兕嗣四姒寺巳汜泗祀禩笥耜肂肆飼饲饴駟驷"),
+    ("song", "凇崧嵩忪松枩淞菘鬆怂悚愯竦耸聳宋誦讼诵送頌颂"),
+    ("sou", "嗖廋搜溲艘蒐蓃螋锼颼飕餿馊叟嗾擞擻瞍籔薮藪嗽"),
+    ("su", "甦稣穌窣苏蘇酥俗僳嗉塑夙宿愫憟樕殐涑溯簌粟素縮肃肅膆蔌觫訴诉谡速骕鷫"),
+    ("suan", "狻酸算蒜"),
+    ("sui", "睢荽虽雖绥遂隋随隨巂髓岁歲燧碎祟穗繸谇邃隧"),
+    ("sun", "孙孫狲荪蓀飧损損榫笋筍箰隼潠"),
+    ("suo", "唆嗦娑挲梭缩羧蓑唢所摵琐瑣索鎖锁"),
+    ("ta", "他塌她它牠祂塔獭拓挞撻榻溻踏蹋遢闼"),
+    ("tai", "胎臺苔台抬檯炱薹跆邰太态態汰泰肽钛"),
+    ("tan", "坍摊攤滩灘瘫癱貪贪倓坛壇弹昙檀潭痰罈談谈谭郯黮坦忐毯袒叹嘆埮探歎炭碳"),
+    ("tang", "嘡汤趟蹚镗唐堂塘搪棠溏瑭禟糖膅膛螳醣饧倘偒傥帑淌躺镋烫燙"),
+    ("tao", "弢掏涛滔絛縚绦韬饕匋啕桃洮淘萄逃陶騊鼗討讨套"),
+    ("te", "忑忒特铽"),
+    ("teng", "滕疼腾藤誊騰"),
+    ("ti", "剔梯踢锑鷉啼提禔禵绨缇荑蹄醍題题騠鹈体體倜剃嚏屉屜悌惕揥替涕薙裼逖錫鬀鬄"),
+    ("tian", "天添填恬甜田畋阗忝殄腆舔瑱"),
+    ("tiao", "佻祧聎条條笤蜩迢髫鲦龆挑窕眺粜絩跳调"),
+    ("tie", "帖萜貼贴鐵铁餮"),
+    ("ting", "厅听廳汀聽鞓亭停婷庭廷筳葶蜓霆挺梃珽脡艇"),
+    ("tong", "嗵通仝佟僮同彤桐潼瞳童絧茼詷酮銅铜捅桶筒統统恸慟痛"),
+    ("tou", "偷亠头投頭骰钭透"),
+    ("tu", "凸禿秃突图圖塗屠徒涂荼菟途酴駼吐土钍兔堍"),
+    ("tuan", "湍剸团團抟摶疃彖"),
+    ("tui", "推頹颓腿煺蜕褪退"),
+    ("tun", "吞暾屯臀豚饨"),
+    ("tuo", "乇咃托拕拖脫脱託佗坨砣紽跎酡阤陀駝駞驼鸵鼍妥庹椭橢唾柝箨"),
+    ("wa", "哇娲挖洼漥窪蛙娃佤瓦袜襪"),
+    ("wai", "歪崴外"),
+    ("wan", "剜弯彎湾灣蜿豌丸完烷玩纨頑顽婉宛惋挽晚晩椀琬畹皖盌碗绾菀輓万卍卐捥腕"),
+    ("wang", "尢汪亡王往惘枉網网罔誷辋魍妄忘旺望"),
+    ("wei", "偎危威巍微煨葳薇逶隈鰄为唯囗围圍圩嵬帏帷幃惟桅涠潍為爲維维违違闱韋韦亹伟伪偉委娓尾暐洧炜猥玮瑋痿纬艉苇萎葦蓶蔿诿韪鲔位偽卫味喂尉慰未渭猬畏胃蔚蝟衛謂谓遺餵魏"),
+    ("wen", "温溫瑥瘟輼文璺紋纹聞蚊闻阌雯刎吻呡煴稳穩紊問揾汶问"),
+    ("weng", "嗡翁蓊瓮甕蕹齆"),
+    ("wo", "倭挝窝窩莴蜗我卧幄握斡沃渥臥龌"),
+    ("wu", "乌呜嗚圬屋巫污烏腛誣诬邬钨吳吴吾唔庑无梧毋浯牾瞴芜蕪蜈鋘鼯五仵伍侮午妩嫵忤怃捂摀武瑦舞迕鹉兀务務勿噁坞塢婺寤恶悟戊晤杌焐物痦誤误阢雾霧靰骛"),
+    ("xi", "傒僖兮吸唏嘻噏夕奚嬉希恓息悉惜昔晞晰晳曦析桸樨欷汐浠淅溪烯熄熙熹牺犀犧皙矽硒稀穸窸粞羲翕膝蜥蟋西觹锡鼷习媳席檄漝習蓆袭襲觋隰喜屣徙憙洗玺禧縰葸铣係屃戏禊系細细舄覤郄郤阋隙饩"),
+    ("xia", "岈瞎虾侠俠匣峡峽暇柙烚狎狭狹瑕筪轄辖遐霞黠丅下吓夏罅"),
+    ("xian", "仙仚先掀暹氙祆籼纖跹酰锨鮮鲜咸啣娴嫌弦挦撏涎痫絃舷衔賢贤銜閑闲鹇鹹显燹猃癣筅藓蚬跣险險顯伣县娨宪岘憲献獻现現絤線縣线羡羨腺限陷霰馅"),
+    ("xiang", "乡厢廂湘相箱緗缃芗葙襄鄉鑲镶香骧庠祥翔詳详享响想響飨饷鲞像向嚮橡象項项降"),
+    ("xiao", "削哓哮嚣宵枭枵消潇瀟硝箫綃绡萧蕭虓逍銷销霄驍骁魈鸮淆小晓曉筱篠傚啸嘨嘯孝效校笑肖"),
+    ("xie", "些楔歇蝎偕勰协協挟携撷攜斜缬胁脅諧谐邪鞋写寫亵卸屑廨懈械榍榭泄泻洩澥瀉瀣燮獬絬绁缷薤蟹褻謝谢躞邂解"),
+    ("xin", "心忄忻新昕欣歆芯薪訢辛鑫锌馨尋伈伩信囟舋衅釁"),
+    ("xing", "兴惺星猩腥興騂刑型形硎荥邢擤醒倖姓幸性悻杏荇行省"),
+    ("xiong", "兄兇凶匈汹洶胸芎詾熊雄敻"),
+    ("xiu", "休修咻庥羞脩貅馐髹鸺朽嗅岫琇璓秀繍绣袖褎銹鏽锈"),
+    ("xu", "吁嘘墟歔盱籲繻胥虚虛訏需須须顼鬚魆徐喣栩詡许诩醑侐勖叙婿序恤慉敘旭昫洫溆煦絮緒續绪续蓄藚酗蓿"),
+    ("xuan", "儇吅喧埙宣揎暄煊萱谖軒轩悬懸旋漩玄璇蜁烜选選馔楦泫渲炫眩碹绚衒铉镟"),
+    ("xue", "吙薛靴学學穴踅雪鳕血"),
+    ("xun", "勋勛勳峋曛熏燻窨薰醺噚寻巡循恂旬洵浔畃荀詢询馴驯鲟巽徇殉汛訊訓训讯賐迅逊遜"),
+    ("ya", "丫压哑圧壓押鴉鴨鵶鸦鸭伢崖涯牙琊睚芽蚜衙亞雅亚亜娅揠氩猰玡砑訝讶迓錏铔呀"),
+    ("yan", "厭咽嫣崦殷淹湮烟焉煙胭菸鄢醃阉严嚴埏妍岩巖延檐沿炎琂盐研筵簷綖芫莚蜒言讠閻闫阎顏顔颜鹽俨偃儼兖厣奄巘弇掩演琰眼罨衍魇魘鼹龑厌唁嚥堰宴彥彦晏滟焰焱燄燕砚艳艷諺谚谳豔赝酽醼雁餍驗验鴳鷃"),
+    ("yang", "央殃泱秧鞅鴦鸯佯徉扬揚旸杨楊洋炀烊疡羊阳陽颺飏仰养氧痒癢養駚怏恙样樣漾"),
+    ("yao", "吆喓夭妖幺約腰邀垚堯姚尧峣崤徭搖摇爻瑶窑窯繇肴謠谣遙遥餚鳐咬崾杳殀窅窈舀騕曜燿耀药藥要鑰钥鹞"),
+    ("ye", "噎掖椰耶揶爷爺也冶吔嘢埜野业叶夜晔曳業液烨燁腋謁谒邺靥頁页"),
+    ("yi", "一伊依医咿噫壹揖漪猗祎繄衣衤醫铱黟乁仪儀匜咦圯夷姨宜宧嶷彝怡沂疑痍移笫胰衪訑誼诒贻迤迻遗颐乙以倚已扆旖椅矣胣苡蚁蟻钇齮乂义亦亿佚佾億刈劓呓囈埸奕屹异弈弋役忆怿悒意憶懿抑挹易棭槷殪毅溢熠異疫瘗癔益繹绎缢義羿翊翌翳翼肄臆艺薏藝蜴裔譯議讛议译诣谊轶逸邑镒镱驛驿鷁"),
+    ("yin", "喑因堙姻慇氤洇瘖絪茵荫蔭铟阴陰音骃吟垠夤寅淫狺鄞銀银霪乚尹引瘾癮蚓隐隱飲饮印慭胤"),
+    ("ying", "嘤婴嬰应應撄樱櫻瑛璎缨罂膺英莺鶯鷹鹦鹰嬴楹滢潆瀛營瑩盈縈茔荧莹萤营萦蓥蝇螢蠅贏赢迎影瘿穎郢颍颖媵映硬"),
+    ("yo", "哟唷喲"),
+    ("yong", "佣噰墉壅庸慵拥擁痈臃邕镛雍雝饔喁颙俑勇咏埇恿永泳涌甬蛹詠踊用"),
+    ("you", "优優呦幽忧悠憂攸尤柚楢油游犹猶猷由疣莸蚰蝣遊邮郵鈾铀鱿友有牖莠酉铕黝佑侑又右囿宥幼狖祐誘诱釉鼬"),
+    ("yu", "於淤瘀纡迂于余俞妤娛娱嵛愉愚揄榆欤渔渝漁玗玙瑜盂禺窬竽腴臾舁舆萸蕍虞蝓覦觎諛谀踰輿逾隅雩餘馀魚鱼与予伛俣圄圉宇屿嶼庾敔瑀瘐禹窳羽與語语雨龉喻域堉妪寓峪彧御愈慾昱欲毓浴淯煜熨燠狱獄玉癒硲禦聿育芋萮薁蜮裕誉諭譽谕豫遇郁钰阈預预饫驭鬱鬻鹆鹬"),
+    ("yuan", "冤淵渊鳶鴛鵷鸢鸳元原员員园圆園圓垣塬媛援橼沅湲源爰猿緣缘蚖螈袁轅辕鼋远遠怨愿掾瑗苑院願"),
+    ("yue", "曰约刖岳嶽悅悦月樾瀹玥籆粤粵越趯跃躍閱閲阅龠乐"),
+    ("yun", "晕暈氲氳赟云勻匀昀榅纭耘芸郧雲允殒殞狁陨孕恽愠慍蕴蘊运運郓酝醞韫韵韻"),
+    ("za", "匝咂拶紮臜臢偺杂砸雜咋"),
+    ("zai", "哉栽災灾甾仔宰崽載载再在"),
+    ("zan", "簪糌咱喒撍昝趱暂暫瓒讚賛贊赞錾"),
+    ("zang", "脏臧赃髒驵奘臟葬藏"),
+    ("zao", "糟遭凿鑿早枣棗澡璪藻蚤唣噪灶燥皂躁造"),
+    ("ze", "则則啧嘖帻择擇泽澤箦舴責责赜迮仄昃"),
+    ("zei", "賊贼"),
+    ("zen", "怎"),
+    ("zeng", "増增憎罾甑綜贈赠锃"),
+    ("zha", "吒哳扎揸渣拃札炸铡閘闸眨砟苲鲊乍咤柞柵榨膪蚱詐诈"),
+    ("zhai", "摘斋斎齋宅窄债債寨砦"),
+    ("zhan", "旃毡氈沾瞻詹谵霑展崭嶄搌斩斬盏盞輾佔偡占战戰栈棧椾湛站綻绽蘸颤"),
+    ("zhang", "张張彰暲樟漳獐璋章蟑鄣仉掌涨漲丈仗嶂帐帳幛杖瘴胀脹賬账障长"),
+    ("zhao", "招昭朝钊鸼找沼爪兆召枛棹照笊罩肇詔诏赵趙着"),
+    ("zhe", "嗻蜇遮哲喆折摺磔蛰谪輒辄辙者褶赭柘浙蔗这這鹧着"),
+    ("zhen", "侦偵帪斟桢榛珍甄真砧碪祯禛箴缜胗臻蒖蓁診貞贞針鍼针枕畛疹眕稹诊轸圳振朕赈鎮镇阵陣震鸩"),
+    ("zheng", "争峥征徵怔挣掙烝爭狰猙症癥睁睜筝蒸诤錚钲铮拯整帧幀政正証證证郑鄭"),
+    ("zhi", "之卮吱掷支枝枳栀汁知祗織织肢胝脂芝蜘觯隻鴲侄值埴執姪执摭擿桎植殖直絷职職跖踯只咫址恉指旨止祉紙纸芷趾轵酯黹制帙帜幟彘志忮挚摯擲智治滞滯炙畤痔痣秩稚稺窒緻置至致蛭袠製誌豸質质贽踬躓轾郅陟雉骘鸷"),
+    ("zhong", "中妐忠盅終终舯螽衷蹱鍾鐘钟锺冢塚种種肿腫踵仲众眾緟重"),
+    ("zhou", "周州洲矪粥舟謅诌赒週妯碡軸轴帚肘冑咒宙昼晝甃皱皺籀纣绉胄驟驺骤"),
+    ("zhu", "侏朱株槠橥櫫洙潴猪珠茱蛛誅諸诛诸豬铢朮烛燭竹竺築舳蠋躅逐丶主嘱囑拄渚煮瞩矚麈伫佇住助杼柱柷注炷祝筑筯箸紵紸纻翥著蛀註贮鑄铸駐驻属"),
+    ("zhua", "抓"),
+    ("zhuai", "拽"),
+    ("zhuan", "专專砖磚颛轉转啭撰篆賺赚传"),
+    ("zhuang", "妆妝庄桩樁莊装裝壮壯撞状狀"),
+    ("zhui", "追錐锥骓坠墜惴硾綴缀缒贅赘"),
+    ("zhun", "窀肫諄谆准準"),
+    ("zhuo", "倬拙捉桌涿卓啄擢斫斲晫汋浊浞濁濯灼焯琢茁诼酌镯着"),
+    ("zi", "咨嗞姿孖孜孳嵫淄滋緇缁茲觜訾諮谘資赀资趑辎锱髭鴜龇姉姊子梓滓秭籽紫耔字恣渍漬牸眦自仔"),
+    ("zong", "宗棕縱综踪蹤鬃偬总總粽纵"),
+    ("zou", "棸诹邹鄹陬走奏揍"),
+    ("zu", "租捽族足蹵镞俎祖組组詛诅阻"),
+    ("zuan", "躜躦鑽钻籫纂缵攥"),
+    ("zui", "嘴墬晬最絊罪蕞醉"),
+    ("zun", "墫尊樽遵鳟撙"),
+    ("zuo", "昨佐咗左作做坐座怍祚胙"),
+];
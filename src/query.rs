@@ -0,0 +1,670 @@
+use crate::models::*;
+use crate::search::{halfwidth, normalize, text_matches};
+use chrono::NaiveDate;
+
+/// 查询语法说明（用于界面提示和命令行帮助）
+pub const QUERY_HELP: &str = "\
+普通词：匹配名称、描述、领域名称、项目标签和关系标签，如 RNA 或 \"follow up\"
+字段条件：status:published  field:rna_structure  tag:\"follow-up\"  keyword:nsfc  completion<50  start>=2024-01-01
+比较符：: 包含，= 等于，!= 不等于，< <= > >= 用于完成度和日期
+字段：id name desc status field keyword tag type completion start target submission publication done has
+（keyword 为项目标签，tag 为关系标签；field 也匹配属于其子领域的项目）
+组合：空格或 AND 表示同时满足，OR 表示满足其一，NOT 或 - 表示排除，括号改变优先级";
+
+/// 比较运算符
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    Contains,  // :
+    Eq,        // =
+    Ne,        // !=
+    Lt,        // <
+    Le,        // <=
+    Gt,        // >
+    Ge,        // >=
+}
+
+impl CompareOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            CompareOp::Contains => ":",
+            CompareOp::Eq => "=",
+            CompareOp::Ne => "!=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+        }
+    }
+
+    /// 比较大小（用于数字和日期，`:` 视为等于）
+    fn compare<T: PartialOrd>(self, left: T, right: T) -> bool {
+        match self {
+            CompareOp::Contains | CompareOp::Eq => left == right,
+            CompareOp::Ne => left != right,
+            CompareOp::Lt => left < right,
+            CompareOp::Le => left <= right,
+            CompareOp::Gt => left > right,
+            CompareOp::Ge => left >= right,
+        }
+    }
+}
+
+/// 查询字段
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueryKey {
+    Id,
+    Name,
+    Description,
+    Status,
+    Field,
+    Keyword,
+    Tag,
+    Type,
+    Completion,
+    Start,
+    Target,
+    Submission,
+    Publication,
+    Done,
+    Has,
+}
+
+/// 字段名称表：每个字段的英文名（第一个为主名称，用于错误提示）和中文别名
+const KEY_NAMES: &[(QueryKey, &[&str])] = &[
+    (QueryKey::Id, &["id"]),
+    (QueryKey::Name, &["name", "名称"]),
+    (QueryKey::Description, &["desc", "description", "描述"]),
+    (QueryKey::Status, &["status", "状态"]),
+    (QueryKey::Field, &["field", "领域"]),
+    (QueryKey::Keyword, &["keyword", "kw", "关键词", "项目标签"]),
+    (QueryKey::Tag, &["tag", "标签"]),
+    (QueryKey::Type, &["type", "类型"]),
+    (QueryKey::Completion, &["completion", "完成度"]),
+    (QueryKey::Start, &["start", "开始"]),
+    (QueryKey::Target, &["target", "目标"]),
+    (QueryKey::Submission, &["submission", "submitted", "投稿"]),
+    (QueryKey::Publication, &["publication", "published", "发表"]),
+    (QueryKey::Done, &["done", "完成"]),
+    (QueryKey::Has, &["has", "有"]),
+];
+
+impl QueryKey {
+    /// 按名称查找字段（支持英文名和中文别名，不区分大小写）
+    fn parse(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        KEY_NAMES.iter()
+            .find(|(_, names)| names.contains(&name.as_str()))
+            .map(|(key, _)| *key)
+    }
+
+    /// 全部字段的主名称，以空格分隔
+    fn all_names() -> String {
+        KEY_NAMES.iter().map(|(_, names)| names[0]).collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// has: 条件的对象
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HasKind {
+    Relation,
+    Milestone,
+    Date,
+    Field,
+}
+
+/// 条件值（解析时按字段类型转换）
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryValue {
+    Text(String),  // 已规范化的文本
+    Number(f32),
+    Date(NaiveDate),
+    Bool(bool),
+    Has(HasKind),
+}
+
+/// 查询表达式
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+    Not(Box<Expr>),
+    Text(String),  // 普通词或引号短语
+    Compare { key: QueryKey, op: CompareOp, value: QueryValue },
+}
+
+/// 解析后的查询（空查询匹配全部）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Query {
+    pub expr: Option<Expr>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(CompareOp),
+    LParen,
+    RParen,
+    Not,
+    And,
+    Or,
+}
+
+/// 带位置（字符序号，从0开始）的词法单元
+struct Spanned {
+    token: Token,
+    pos: usize,
+}
+
+/// 错误信息：位置按字符从1开始计数
+fn error_at(pos: usize, message: impl AsRef<str>) -> String {
+    format!("第 {} 个字符：{}", pos + 1, message.as_ref())
+}
+
+/// 词法分析（全角字符按半角处理，中文引号等同英文引号）
+fn tokenize(text: &str) -> Result<Vec<Spanned>, String> {
+    let chars: Vec<char> = text.chars()
+        .map(halfwidth)
+        .map(|c| if c == '“' || c == '”' { '"' } else { c })
+        .collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    // 上一个字符是否为空白、左括号或开头（用于判断 - 是否为排除符）
+    let mut term_start = true;
+    while i < chars.len() {
+        let c = chars[i];
+        let pos = i;
+        let next = chars.get(i + 1).copied();
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                term_start = true;
+                continue;
+            }
+            '(' => {
+                i += 1;
+                Token::LParen
+            }
+            ')' => {
+                i += 1;
+                Token::RParen
+            }
+            '"' => {
+                let mut value = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(error_at(pos, "引号没有闭合")),
+                        Some('"') => break,
+                        Some('\\') if matches!(chars.get(i + 1), Some('"') | Some('\\')) => {
+                            value.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        Some(c) => {
+                            value.push(*c);
+                            i += 1;
+                        }
+                    }
+                }
+                i += 1;
+                Token::Quoted(value)
+            }
+            ':' => {
+                i += 1;
+                Token::Op(CompareOp::Contains)
+            }
+            '=' => {
+                i += if next == Some('=') { 2 } else { 1 };
+                Token::Op(CompareOp::Eq)
+            }
+            '!' if next == Some('=') => {
+                i += 2;
+                Token::Op(CompareOp::Ne)
+            }
+            '<' | '>' => {
+                let or_equal = next == Some('=');
+                i += if or_equal { 2 } else { 1 };
+                Token::Op(match (c, or_equal) {
+                    ('<', false) => CompareOp::Lt,
+                    ('<', true) => CompareOp::Le,
+                    ('>', false) => CompareOp::Gt,
+                    _ => CompareOp::Ge,
+                })
+            }
+            '!' => {
+                i += 1;
+                Token::Not
+            }
+            '-' if term_start && next.is_some_and(|n| !n.is_whitespace()) => {
+                i += 1;
+                Token::Not
+            }
+            '&' => {
+                i += if next == Some('&') { 2 } else { 1 };
+                Token::And
+            }
+            '|' => {
+                i += if next == Some('|') { 2 } else { 1 };
+                Token::Or
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.get(i) {
+                    let ends = c.is_whitespace()
+                        || matches!(c, '(' | ')' | '"' | ':' | '=' | '<' | '>' | '&' | '|')
+                        || (c == '!' && chars.get(i + 1) == Some(&'='));
+                    if ends {
+                        break;
+                    }
+                    word.push(c);
+                    i += 1;
+                }
+                match word.as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => Token::Word(word),
+                }
+            }
+        };
+        term_start = token == Token::LParen;
+        tokens.push(Spanned { token, pos });
+    }
+    Ok(tokens)
+}
+
+/// 递归下降语法分析：OR 优先级最低，其次 AND（空格相当于 AND），再次 NOT
+struct Parser {
+    tokens: Vec<Spanned>,
+    index: usize,
+    end: usize,  // 查询文本的字符数（用于"结尾处"的错误位置）
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|t| &t.token)
+    }
+
+    fn pos(&self) -> usize {
+        self.tokens.get(self.index).map(|t| t.pos).unwrap_or(self.end)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut terms = vec![self.parse_and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.index += 1;
+            terms.push(self.parse_and()?);
+        }
+        Ok(if terms.len() == 1 { terms.remove(0) } else { Expr::Or(terms) })
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut terms = vec![self.parse_unary()?];
+        loop {
+            match self.peek() {
+                None | Some(Token::RParen) | Some(Token::Or) => break,
+                Some(Token::And) => {
+                    self.index += 1;
+                    terms.push(self.parse_unary()?);
+                }
+                Some(_) => terms.push(self.parse_unary()?),
+            }
+        }
+        Ok(if terms.len() == 1 { terms.remove(0) } else { Expr::And(terms) })
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some(&Token::Not) {
+            self.index += 1;
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        let pos = self.pos();
+        let Some(token) = self.peek().cloned() else {
+            return Err(error_at(pos, "查询不完整，缺少条件"));
+        };
+        self.index += 1;
+        match token {
+            Token::LParen => {
+                if self.peek() == Some(&Token::RParen) {
+                    return Err(error_at(pos, "括号内缺少条件"));
+                }
+                let expr = self.parse_or()?;
+                if self.peek() != Some(&Token::RParen) {
+                    return Err(error_at(pos, "左括号没有闭合"));
+                }
+                self.index += 1;
+                Ok(expr)
+            }
+            Token::RParen => Err(error_at(pos, "多余的右括号")),
+            Token::And => Err(error_at(pos, "AND 前缺少条件")),
+            Token::Or => Err(error_at(pos, "OR 前缺少条件")),
+            Token::Not => Err(error_at(pos, "NOT 后缺少条件")),
+            Token::Op(op) => Err(error_at(pos, format!("比较符 {} 前缺少字段名", op.symbol()))),
+            Token::Quoted(text) => Ok(Expr::Text(text)),
+            Token::Word(word) => match self.peek() {
+                Some(Token::Op(op)) => {
+                    let op = *op;
+                    self.index += 1;
+                    self.parse_condition(&word, pos, op)
+                }
+                _ => Ok(Expr::Text(word)),
+            },
+        }
+    }
+
+    /// 解析"字段 比较符 值"，按字段类型检查比较符和值
+    fn parse_condition(&mut self, name: &str, pos: usize, op: CompareOp) -> Result<Expr, String> {
+        let Some(key) = QueryKey::parse(name) else {
+            return Err(error_at(pos, format!(
+                "未知字段 \"{}\"（可用字段：{}）",
+                name,
+                QueryKey::all_names()
+            )));
+        };
+        let value_pos = self.pos();
+        let raw = match self.peek() {
+            Some(Token::Word(w)) | Some(Token::Quoted(w)) => w.clone(),
+            _ => return Err(error_at(value_pos, format!("字段 {} 缺少值", name))),
+        };
+        self.index += 1;
+        let text_only = |op: CompareOp| -> Result<(), String> {
+            if matches!(op, CompareOp::Contains | CompareOp::Eq | CompareOp::Ne) {
+                Ok(())
+            } else {
+                Err(error_at(pos, format!("字段 {} 不支持比较符 {}，只能使用 :、= 或 !=", name, op.symbol())))
+            }
+        };
+        let value = match key {
+            QueryKey::Id | QueryKey::Name | QueryKey::Description | QueryKey::Status
+            | QueryKey::Field | QueryKey::Keyword | QueryKey::Tag | QueryKey::Type => {
+                text_only(op)?;
+                QueryValue::Text(normalize(&raw))
+            }
+            QueryKey::Completion => {
+                let number = raw.trim_end_matches('%').parse::<f32>()
+                    .map_err(|_| error_at(value_pos, format!("{} 的值应为数字，而不是 \"{}\"", name, raw)))?;
+                QueryValue::Number(number)
+            }
+            QueryKey::Start | QueryKey::Target | QueryKey::Submission | QueryKey::Publication => {
+                let date = NaiveDate::parse_from_str(&raw, "%Y-%m-%d")
+                    .map_err(|_| error_at(value_pos, format!("{} 的值应为日期（YYYY-MM-DD），而不是 \"{}\"", name, raw)))?;
+                QueryValue::Date(date)
+            }
+            QueryKey::Done => {
+                text_only(op)?;
+                let value = match raw.to_lowercase().as_str() {
+                    "true" | "yes" | "1" | "是" => true,
+                    "false" | "no" | "0" | "否" => false,
+                    _ => return Err(error_at(value_pos, format!("{} 的值应为 true 或 false，而不是 \"{}\"", name, raw))),
+                };
+                QueryValue::Bool(value)
+            }
+            QueryKey::Has => {
+                text_only(op)?;
+                let kind = match raw.to_lowercase().as_str() {
+                    "relation" | "relations" | "关系" => HasKind::Relation,
+                    "milestone" | "milestones" | "里程碑" => HasKind::Milestone,
+                    "date" | "dates" | "日期" => HasKind::Date,
+                    "field" | "fields" | "领域" => HasKind::Field,
+                    _ => return Err(error_at(value_pos, format!(
+                        "has 的值应为 relation、milestone、date 或 field，而不是 \"{}\"", raw
+                    ))),
+                };
+                QueryValue::Has(kind)
+            }
+        };
+        Ok(Expr::Compare { key, op, value })
+    }
+}
+
+/// 把任意文本写成查询中的引号字符串（转义引号和反斜杠），用于由界面拼接的条件
+pub fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// 解析查询文本，语法错误时返回带位置的错误信息
+pub fn parse(text: &str) -> Result<Query, String> {
+    let tokens = tokenize(text)?;
+    if tokens.is_empty() {
+        return Ok(Query::default());
+    }
+    let mut parser = Parser { tokens, index: 0, end: text.chars().count() };
+    let expr = parser.parse_or()?;
+    if parser.index < parser.tokens.len() {
+        // parse_or 只会停在多余的右括号处
+        return Err(error_at(parser.pos(), "多余的右括号"));
+    }
+    Ok(Query { expr: Some(expr) })
+}
+
+/// 文本比较：`:` 任一候选包含，`=` 任一候选相等，`!=` 没有候选相等
+fn text_compare<'a>(op: CompareOp, candidates: impl IntoIterator<Item = &'a str>, value: &str) -> bool {
+    let mut candidates = candidates.into_iter().map(normalize);
+    match op {
+        CompareOp::Contains => candidates.any(|c| c.contains(value)),
+        CompareOp::Ne => !candidates.any(|c| c == value),
+        _ => candidates.any(|c| c == value),
+    }
+}
+
+/// 关系类型的名称（ID和目录中的名称）
+fn relation_type_names<'a>(data: &'a AppData, relation: &'a ProjectRelation) -> [&'a str; 2] {
+    let name = data.relation_type(&relation.relation_type).map(|t| t.name.as_str()).unwrap_or_default();
+    [relation.relation_type.as_str(), name]
+}
+
+/// 与项目相连的关系
+fn project_relations<'a>(data: &'a AppData, project: &'a Project) -> impl Iterator<Item = &'a ProjectRelation> {
+    data.relations.iter().filter(move |r| r.from_id == project.id || r.to_id == project.id)
+}
+
+fn eval_project(expr: &Expr, data: &AppData, project: &Project) -> bool {
+    match expr {
+        Expr::And(terms) => terms.iter().all(|t| eval_project(t, data, project)),
+        Expr::Or(terms) => terms.iter().any(|t| eval_project(t, data, project)),
+        Expr::Not(inner) => !eval_project(inner, data, project),
+        Expr::Text(text) => text_matches(data, project, text),
+        Expr::Compare { key, op, value } => compare_project(*key, *op, value, data, project),
+    }
+}
+
+fn compare_project(key: QueryKey, op: CompareOp, value: &QueryValue, data: &AppData, project: &Project) -> bool {
+    match (key, value) {
+        (QueryKey::Id, QueryValue::Text(v)) => text_compare(op, [project.id.as_str()], v),
+        (QueryKey::Name, QueryValue::Text(v)) => text_compare(op, [project.name.as_str()], v),
+        (QueryKey::Description, QueryValue::Text(v)) => text_compare(op, [project.description.as_str()], v),
+        (QueryKey::Status, QueryValue::Text(v)) => {
+            let name = data.status(&project.status).map(|s| s.name.as_str()).unwrap_or_default();
+            text_compare(op, [project.status.as_str(), name], v)
+        }
+        (QueryKey::Field, QueryValue::Text(v)) => {
+            let member_ids = data.effective_field_ids(project);
+            let candidates = member_ids.iter().flat_map(|id| {
+                let name = data.fields.get(id).map(|f| f.name.as_str()).unwrap_or_default();
+                [id.as_str(), name]
+            });
+            text_compare(op, candidates, v)
+        }
+        (QueryKey::Keyword, QueryValue::Text(v)) => text_compare(op, project.tags.iter().map(|t| t.as_str()), v),
+        (QueryKey::Tag, QueryValue::Text(v)) => {
+            let candidates = project_relations(data, project).flat_map(|r| r.tags.iter().map(|t| t.as_str()));
+            text_compare(op, candidates, v)
+        }
+        (QueryKey::Type, QueryValue::Text(v)) => {
+            let candidates = project_relations(data, project).flat_map(|r| relation_type_names(data, r));
+            text_compare(op, candidates, v)
+        }
+        (QueryKey::Completion, QueryValue::Number(v)) => op.compare(project.completion_percentage, *v),
+        (QueryKey::Start, QueryValue::Date(v)) => compare_date(op, project.start_date, *v),
+        (QueryKey::Target, QueryValue::Date(v)) => compare_date(op, project.target_date, *v),
+        (QueryKey::Submission, QueryValue::Date(v)) => compare_date(op, project.submission_date, *v),
+        (QueryKey::Publication, QueryValue::Date(v)) => compare_date(op, project.publication_date, *v),
+        (QueryKey::Done, QueryValue::Bool(v)) => (data.is_done(project) == *v) != (op == CompareOp::Ne),
+        (QueryKey::Has, QueryValue::Has(kind)) => {
+            let has = match kind {
+                HasKind::Relation => project_relations(data, project).next().is_some(),
+                HasKind::Milestone => !project.milestones.is_empty(),
+                HasKind::Date => project.date_span().is_some(),
+                HasKind::Field => !project.field_ids.is_empty(),
+            };
+            has != (op == CompareOp::Ne)
+        }
+        _ => false,
+    }
+}
+
+/// 日期比较：未设置的日期只满足 !=
+fn compare_date(op: CompareOp, date: Option<NaiveDate>, value: NaiveDate) -> bool {
+    match date {
+        Some(date) => op.compare(date, value),
+        None => op == CompareOp::Ne,
+    }
+}
+
+/// 关系求值：tag 和 type 作用于关系本身，其他条件要求两端项目都满足，
+/// 普通词匹配关系标签或两端项目
+fn eval_relation(expr: &Expr, data: &AppData, relation: &ProjectRelation) -> bool {
+    let endpoints = || [data.projects.get(&relation.from_id), data.projects.get(&relation.to_id)];
+    match expr {
+        Expr::And(terms) => terms.iter().all(|t| eval_relation(t, data, relation)),
+        Expr::Or(terms) => terms.iter().any(|t| eval_relation(t, data, relation)),
+        Expr::Not(inner) => !eval_relation(inner, data, relation),
+        Expr::Text(text) => {
+            let term = normalize(text);
+            relation.tags.iter().any(|t| normalize(t).contains(&term))
+                || endpoints().iter().all(|p| p.is_some_and(|p| text_matches(data, p, text)))
+        }
+        Expr::Compare { key: QueryKey::Tag, op, value: QueryValue::Text(v) } => {
+            text_compare(*op, relation.tags.iter().map(|t| t.as_str()), v)
+        }
+        Expr::Compare { key: QueryKey::Type, op, value: QueryValue::Text(v) } => {
+            text_compare(*op, relation_type_names(data, relation), v)
+        }
+        Expr::Compare { key, op, value } => {
+            endpoints().iter().all(|p| p.is_some_and(|p| compare_project(*key, *op, value, data, p)))
+        }
+    }
+}
+
+impl Query {
+    /// 是否为空查询（匹配全部）
+    pub fn is_empty(&self) -> bool {
+        self.expr.is_none()
+    }
+
+    /// 项目是否匹配
+    pub fn matches(&self, data: &AppData, project: &Project) -> bool {
+        match &self.expr {
+            Some(expr) => eval_project(expr, data, project),
+            None => true,
+        }
+    }
+
+    /// 关系是否匹配
+    pub fn matches_relation(&self, data: &AppData, relation: &ProjectRelation) -> bool {
+        match &self.expr {
+            Some(expr) => eval_relation(expr, data, relation),
+            None => true,
+        }
+    }
+
+    /// 按数据中的顺序返回匹配的项目ID
+    pub fn select_projects(&self, data: &AppData) -> Vec<String> {
+        data.projects.values()
+            .filter(|p| self.matches(data, p))
+            .map(|p| p.id.clone())
+            .collect()
+    }
+
+    /// 返回匹配的关系序号
+    pub fn select_relations(&self, data: &AppData) -> Vec<usize> {
+        data.relations.iter()
+            .enumerate()
+            .filter(|(_, r)| self.matches_relation(data, r))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expr(text: &str) -> Expr {
+        parse(text).unwrap().expr.unwrap()
+    }
+
+    fn word(text: &str) -> Expr {
+        Expr::Text(text.to_string())
+    }
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn or_binds_looser_than_and() {
+        assert_eq!(expr("a b OR c"), Expr::Or(vec![Expr::And(vec![word("a"), word("b")]), word("c")]));
+        assert_eq!(expr("a OR b AND c"), Expr::Or(vec![word("a"), Expr::And(vec![word("b"), word("c")])]));
+        assert_eq!(expr("(a OR b) c"), Expr::And(vec![Expr::Or(vec![word("a"), word("b")]), word("c")]));
+    }
+
+    #[test]
+    fn not_and_minus_negate() {
+        assert_eq!(expr("NOT a b"), Expr::And(vec![Expr::Not(Box::new(word("a"))), word("b")]));
+        assert_eq!(expr("-a OR b"), Expr::Or(vec![Expr::Not(Box::new(word("a"))), word("b")]));
+        // 词中间的 - 不是排除符
+        assert_eq!(expr("follow-up"), word("follow-up"));
+    }
+
+    #[test]
+    fn quoted_value_with_escaped_quote() {
+        assert_eq!(expr(r#""say \"hi\"""#), word("say \"hi\""));
+        assert_eq!(
+            expr(r#"keyword="a \"b\"""#),
+            Expr::Compare { key: QueryKey::Keyword, op: CompareOp::Eq, value: QueryValue::Text("a \"b\"".to_string()) }
+        );
+        // quote 生成的字符串解析后还原为原文本，包括以反斜杠结尾的文本
+        for text in [r"a\", r#"a\"b"#, r"c:\dir\", "say \"hi\""] {
+            assert_eq!(expr(&quote(text)), word(text));
+        }
+    }
+
+    #[test]
+    fn date_and_percentage_comparisons() {
+        assert_eq!(
+            expr("start>=2024-01-01"),
+            Expr::Compare { key: QueryKey::Start, op: CompareOp::Ge, value: QueryValue::Date(date("2024-01-01")) }
+        );
+        assert_eq!(
+            expr("completion<50%"),
+            Expr::Compare { key: QueryKey::Completion, op: CompareOp::Lt, value: QueryValue::Number(50.0) }
+        );
+
+        let data = AppData::default();
+        let started = Project { start_date: Some(date("2024-03-01")), completion_percentage: 30.0, ..Default::default() };
+        let unstarted = Project { completion_percentage: 80.0, ..Default::default() };
+        let query = parse("start>=2024-01-01 completion<50%").unwrap();
+        assert!(query.matches(&data, &started));
+        assert!(!query.matches(&data, &unstarted));
+        // 未设置的日期只满足 !=
+        assert!(parse("start!=2024-01-01").unwrap().matches(&data, &unstarted));
+        assert!(!parse("start<2024-01-01").unwrap().matches(&data, &unstarted));
+    }
+
+    #[test]
+    fn error_positions_count_characters() {
+        assert_eq!(parse(r#"name:"abc"#).unwrap_err(), "第 6 个字符：引号没有闭合");
+        // 位置按字符而不是字节计算
+        assert_eq!(parse(r#"名称:"微生物"#).unwrap_err(), "第 4 个字符：引号没有闭合");
+        assert_eq!(parse("status:").unwrap_err(), "第 8 个字符：字段 status 缺少值");
+        assert_eq!(parse("status: OR b").unwrap_err(), "第 9 个字符：字段 status 缺少值");
+        let error = parse("a foo:bar").unwrap_err();
+        assert!(error.starts_with("第 3 个字符：未知字段 \"foo\""), "{}", error);
+        assert!(error.contains("keyword"), "{}", error);
+    }
+}
@@ -0,0 +1,246 @@
+use crate::models::{default_relation_color, AppData, ArrowHead, FieldLabelStyle, LineStyle, ProjectRelation, RelationType, ResearchField};
+use crate::route;
+use crate::theme::{contrast_text_color, Theme};
+use egui::*;
+
+/// 按状态序号在圆内绘制填充图案：实心、斜线、网格、横线、点，依次循环
+pub fn paint_status_pattern(painter: &Painter, center: Pos2, radius: f32, index: usize, fill: Color32) {
+    let ink = contrast_text_color(fill).gamma_multiply(0.6);
+    let stroke = Stroke::new(1.0, ink);
+    let spacing = (radius / 3.0).clamp(2.5, 6.0);
+    let r = radius - 1.0;
+    if r <= 2.0 {
+        return;
+    }
+
+    // 与圆相交的一组平行线（方向角 angle）
+    let hatch = |angle: f32| {
+        let dir = vec2(angle.cos(), angle.sin());
+        let normal = vec2(-dir.y, dir.x);
+        let mut d = -r + spacing / 2.0;
+        while d < r {
+            let half = (r * r - d * d).sqrt();
+            let mid = center + normal * d;
+            painter.line_segment([mid - dir * half, mid + dir * half], stroke);
+            d += spacing;
+        }
+    };
+
+    match index % 5 {
+        0 => {}  // 实心
+        1 => hatch(std::f32::consts::FRAC_PI_4),
+        2 => {
+            hatch(std::f32::consts::FRAC_PI_4);
+            hatch(-std::f32::consts::FRAC_PI_4);
+        }
+        3 => hatch(0.0),
+        _ => {
+            let mut y = -r + spacing / 2.0;
+            while y < r {
+                let mut x = -r + spacing / 2.0;
+                while x < r {
+                    if x * x + y * y < r * r {
+                        painter.circle_filled(center + vec2(x, y), 0.8, ink);
+                    }
+                    x += spacing;
+                }
+                y += spacing;
+            }
+        }
+    }
+}
+
+/// 按状态序号在圆右上角绘制形状符号：对勾、三角、感叹号、菱形、空心圆，依次循环
+pub fn paint_status_glyph(painter: &Painter, center: Pos2, radius: f32, index: usize, ink: Color32, background: Color32) {
+    let size = (radius * 0.45).clamp(4.0, 8.0);
+    let offset = radius * std::f32::consts::FRAC_1_SQRT_2;
+    let c = center + vec2(offset, -offset);
+    let stroke = Stroke::new(1.5, ink);
+
+    // 底板，保证符号在任何填充色上都清晰
+    painter.circle_filled(c, size + 1.5, background);
+    painter.circle_stroke(c, size + 1.5, Stroke::new(1.0, ink));
+
+    let s = size * 0.7;
+    match index % 5 {
+        0 => {
+            painter.line_segment([c + vec2(-s, 0.0), c + vec2(-s * 0.3, s * 0.7)], stroke);
+            painter.line_segment([c + vec2(-s * 0.3, s * 0.7), c + vec2(s, -s * 0.7)], stroke);
+        }
+        1 => {
+            painter.add(Shape::convex_polygon(
+                vec![c + vec2(-s * 0.6, -s), c + vec2(s, 0.0), c + vec2(-s * 0.6, s)],
+                ink,
+                Stroke::NONE,
+            ));
+        }
+        2 => {
+            painter.line_segment([c + vec2(0.0, -s), c + vec2(0.0, s * 0.3)], stroke);
+            painter.circle_filled(c + vec2(0.0, s * 0.8), 1.2, ink);
+        }
+        3 => {
+            painter.add(Shape::convex_polygon(
+                vec![c + vec2(0.0, -s), c + vec2(s, 0.0), c + vec2(0.0, s), c + vec2(-s, 0.0)],
+                ink,
+                Stroke::NONE,
+            ));
+        }
+        _ => {
+            painter.circle_stroke(c, s * 0.8, stroke);
+        }
+    }
+}
+
+/// 在圆外侧绘制完成度弧线：从正上方顺时针，弧长与完成度成正比
+pub fn paint_completion_ring(painter: &Painter, center: Pos2, radius: f32, percentage: f32, ink: Color32) {
+    let p = percentage.clamp(0.0, 100.0) / 100.0;
+    // 底部轨道
+    painter.circle_stroke(center, radius, Stroke::new(2.0, ink.gamma_multiply(0.2)));
+    if p <= 0.0 {
+        return;
+    }
+    let segments = ((64.0 * p).ceil() as usize).max(2);
+    let start = -std::f32::consts::FRAC_PI_2;
+    let sweep = std::f32::consts::TAU * p;
+    let points: Vec<Pos2> = (0..=segments)
+        .map(|i| {
+            let a = start + sweep * i as f32 / segments as f32;
+            center + vec2(a.cos(), a.sin()) * radius
+        })
+        .collect();
+    painter.add(Shape::line(points, Stroke::new(2.0, ink)));
+}
+
+/// 双线中两条线各自偏离中心线的距离和线宽
+pub fn double_line_geometry(width: f32) -> (f32, f32) {
+    (width * 0.75 + 1.0, (width * 0.5).max(1.0))
+}
+
+/// 按关系类型的线型和箭头样式沿折线（屏幕坐标，至少两点）绘制关系
+pub fn paint_relation_line(painter: &Painter, points: &[Pos2], line_style: LineStyle, arrow_head: ArrowHead, stroke: Stroke, arrow_size: f32) {
+    match (line_style, line_style.dash_pattern()) {
+        (LineStyle::Double, _) => {
+            let (offset, width) = double_line_geometry(stroke.width);
+            let tuples: Vec<(f32, f32)> = points.iter().map(|p| (p.x, p.y)).collect();
+            for side in [offset, -offset] {
+                let line = route::offset_polyline(&tuples, side).into_iter().map(|(x, y)| pos2(x, y)).collect();
+                painter.add(Shape::line(line, Stroke::new(width, stroke.color)));
+            }
+        }
+        (_, Some((dash, gap))) => {
+            painter.extend(Shape::dashed_line(points, stroke, dash, gap));
+        }
+        _ => {
+            painter.add(Shape::line(points.to_vec(), stroke));
+        }
+    }
+
+    let n = points.len();
+    let head = |tip: Pos2, from: Pos2, filled: bool| {
+        let dir = (tip - from).normalized();
+        let perp = vec2(-dir.y, dir.x);
+        let left = tip - dir * arrow_size + perp * arrow_size * 0.5;
+        let right = tip - dir * arrow_size - perp * arrow_size * 0.5;
+        if filled {
+            painter.add(Shape::convex_polygon(vec![tip, left, right], stroke.color, Stroke::NONE));
+        } else {
+            painter.line_segment([tip, left], stroke);
+            painter.line_segment([tip, right], stroke);
+        }
+    };
+    match arrow_head {
+        ArrowHead::None => {}
+        ArrowHead::Open => head(points[n - 1], points[n - 2], false),
+        ArrowHead::Filled => head(points[n - 1], points[n - 2], true),
+        ArrowHead::Both => {
+            head(points[n - 1], points[n - 2], false);
+            head(points[0], points[1], false);
+        }
+    }
+}
+
+/// 关系的显示颜色：默认颜色跟随主题（保证深色背景下可见），否则使用关系设置的颜色
+pub fn relation_display_color(theme: &Theme, relation: &ProjectRelation) -> Color32 {
+    arrow_display_color(theme, relation.color)
+}
+
+/// 关系类型在图例中的颜色（规则与关系相同）
+pub fn relation_type_display_color(theme: &Theme, relation_type: &RelationType) -> Color32 {
+    arrow_display_color(theme, relation_type.color)
+}
+
+/// 关系标签文字的颜色：第一个设置了颜色的标签的颜色，都未设置时使用主题的次要文字颜色
+pub fn relation_label_color(theme: &Theme, data: &AppData, relation: &ProjectRelation) -> Color32 {
+    match data.relation_label_color(relation) {
+        Some(color) => Color32::from_rgba_unmultiplied(color[0], color[1], color[2], color[3]),
+        None => theme.canvas_text_secondary,
+    }
+}
+
+/// 项目标签徽章的文字：第一个标签（过长时截断），其余标签数量以 +N 表示；没有标签时为 None
+pub fn project_tag_badge(tags: &[String]) -> Option<String> {
+    const MAX_CHARS: usize = 8;
+    let first = tags.first()?;
+    let mut text: String = first.chars().take(MAX_CHARS).collect();
+    if first.chars().count() > MAX_CHARS {
+        text.push('…');
+    }
+    if tags.len() > 1 {
+        text.push_str(&format!(" +{}", tags.len() - 1));
+    }
+    Some(text)
+}
+
+/// 领域圆圈的边框宽度和名称字号：子领域每深一层边框和字号依次变小，画布与图片导出共用
+pub fn field_stroke_and_font(border_width: f32, depth: usize) -> (f32, f32) {
+    let level = depth.min(3) as f32;
+    ((border_width * (1.0 - 0.25 * level)).max(0.5), 16.0 - 2.0 * level)
+}
+
+/// 领域圆圈的（边框颜色, 填充颜色），填充为半透明，多个领域相交处颜色叠加
+pub fn field_colors(field: &ResearchField) -> (Color32, Color32) {
+    let [r, g, b, a] = field.stroke_color;
+    let [fr, fg, fb, fa] = field.fill_color;
+    (Color32::from_rgba_unmultiplied(r, g, b, a), Color32::from_rgba_unmultiplied(fr, fg, fb, fa))
+}
+
+/// 自动配色的领域颜色：按序号循环取一组区分度高的颜色
+pub fn field_palette_color(index: usize) -> [u8; 4] {
+    const PALETTE: [[u8; 4]; 8] = [
+        [31, 119, 180, 255],
+        [255, 127, 14, 255],
+        [44, 160, 44, 255],
+        [214, 39, 40, 255],
+        [148, 103, 189, 255],
+        [140, 86, 75, 255],
+        [227, 119, 194, 255],
+        [23, 190, 207, 255],
+    ];
+    PALETTE[index % PALETTE.len()]
+}
+
+/// 领域名称的（文字颜色, 底色）：普通样式用主题文字颜色，同色样式用边框颜色，色块样式以边框颜色为底
+pub fn field_label_colors(theme: &Theme, field: &ResearchField) -> (Color32, Option<Color32>) {
+    let (stroke, _) = field_colors(field);
+    match field.label_style {
+        FieldLabelStyle::Plain => (theme.canvas_text, None),
+        FieldLabelStyle::Colored => (stroke, None),
+        FieldLabelStyle::Boxed => (contrast_text_color(stroke), Some(stroke)),
+    }
+}
+
+/// 标签小块的背景色：标签设置的颜色，未设置时为浅灰色
+pub fn tag_chip_color(color: Option<&[u8; 4]>) -> Color32 {
+    match color {
+        Some(c) => Color32::from_rgba_unmultiplied(c[0], c[1], c[2], c[3]),
+        None => Color32::from_rgb(200, 200, 200),
+    }
+}
+
+fn arrow_display_color(theme: &Theme, color: [u8; 4]) -> Color32 {
+    if color == default_relation_color() {
+        theme.canvas_arrow
+    } else {
+        Color32::from_rgba_unmultiplied(color[0], color[1], color[2], color[3])
+    }
+}
@@ -0,0 +1,213 @@
+use crate::layout::SpatialIndex;
+use crate::models::{AppData, Project, RelationRoute};
+use std::collections::{HashMap, HashSet};
+
+/// 双向关系的两条箭头各自偏离圆心连线的距离
+const PARALLEL_OFFSET: f32 = 6.0;
+/// 曲线弯曲程度（控制点偏离中点的距离与两端距离之比）
+const CURVE_BEND: f32 = 0.2;
+/// 绕行时与途经项目圆圈保持的距离（不超过项目间距，保证空间索引只需查看相邻单元格）
+const ROUTE_CLEARANCE: f32 = 8.0;
+/// 绕行的最大递归深度（每层在最先遇到的项目旁加一个途经点）
+const ROUTE_DEPTH: usize = 4;
+/// 贝塞尔曲线的采样段数
+const CURVE_SAMPLES: usize = 24;
+/// 绕行样条每两个途经点之间的采样段数
+const SPLINE_SAMPLES: usize = 8;
+
+type Point = (f32, f32);
+
+/// 关系箭头的走线：从起点项目圆圈边缘到终点项目圆圈边缘的折线（数据坐标）
+/// 曲线已按固定点数采样，画布和 SVG 导出绘制同一组点
+pub struct RelationPath {
+    pub points: Vec<Point>,
+}
+
+impl RelationPath {
+    /// 终点处的方向（单位向量），用于绘制箭头头部
+    pub fn end_direction(&self) -> Point {
+        let n = self.points.len();
+        direction(self.points[n - 2], self.points[n - 1])
+    }
+
+    /// 沿路径一半长度处的点及该处方向，用于放置关系标签
+    pub fn midpoint(&self) -> (Point, Point) {
+        let lengths: Vec<f32> = self.points.windows(2).map(|w| distance(w[0], w[1])).collect();
+        let mut remaining = lengths.iter().sum::<f32>() / 2.0;
+        for (i, length) in lengths.iter().enumerate() {
+            if remaining <= *length && *length > 0.0 {
+                let (a, b) = (self.points[i], self.points[i + 1]);
+                let t = remaining / length;
+                return ((a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t), direction(a, b));
+            }
+            remaining -= length;
+        }
+        (self.points[0], self.end_direction())
+    }
+}
+
+fn distance(a: Point, b: Point) -> f32 {
+    ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt()
+}
+
+/// a 指向 b 的单位向量（两点重合时为零向量）
+fn direction(a: Point, b: Point) -> Point {
+    let length = distance(a, b);
+    if length <= 0.0 {
+        return (0.0, 0.0);
+    }
+    ((b.0 - a.0) / length, (b.1 - a.1) / length)
+}
+
+/// 折线向左侧（相对前进方向）平移 distance 后的折线，用于绘制双线
+pub fn offset_polyline(points: &[Point], distance: f32) -> Vec<Point> {
+    points.iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let (dx, dy) = direction(points[i.saturating_sub(1)], points[(i + 1).min(points.len() - 1)]);
+            (p.0 - dy * distance, p.1 + dx * distance)
+        })
+        .collect()
+}
+
+/// 计算关系的走线（relation_indices 为 data.relations 中的序号）
+/// 只把 visible_project_ids 中的项目当作障碍；端点项目不存在或重合的关系没有走线
+/// 互为反向的一对关系各自向左偏移，避免两条箭头重叠（曲线本身已分向两侧，不再偏移）
+pub fn relation_paths(data: &AppData, relation_indices: &[usize], visible_project_ids: &[String]) -> HashMap<usize, RelationPath> {
+    let relations: Vec<_> = relation_indices.iter()
+        .filter_map(|&index| Some((index, data.relations.get(index)?)))
+        .collect();
+    let pairs: HashSet<(&str, &str)> = relations.iter()
+        .map(|(_, r)| (r.from_id.as_str(), r.to_id.as_str()))
+        .collect();
+    // 只在有绕行的关系时建立空间索引
+    let index = relations.iter()
+        .any(|(_, r)| r.route == RelationRoute::Routed)
+        .then(|| SpatialIndex::new(data));
+    let visible: HashSet<&str> = visible_project_ids.iter().map(String::as_str).collect();
+
+    let mut paths = HashMap::new();
+    for (relation_index, relation) in relations {
+        let (Some(from), Some(to)) = (data.projects.get(&relation.from_id), data.projects.get(&relation.to_id)) else {
+            continue;
+        };
+        let (dx, dy) = direction(from.position, to.position);
+        if (dx, dy) == (0.0, 0.0) {
+            continue;
+        }
+        let normal = (-dy, dx);
+        let bidirectional = pairs.contains(&(relation.to_id.as_str(), relation.from_id.as_str()));
+        let offset = if bidirectional && relation.route != RelationRoute::Curved { PARALLEL_OFFSET } else { 0.0 };
+        let start = (from.position.0 + normal.0 * offset, from.position.1 + normal.1 * offset);
+        let end = (to.position.0 + normal.0 * offset, to.position.1 + normal.1 * offset);
+
+        let mut points = match (relation.route, &index) {
+            (RelationRoute::Curved, _) => {
+                let bend = distance(start, end) * CURVE_BEND;
+                let control = ((start.0 + end.0) / 2.0 + normal.0 * bend, (start.1 + end.1) / 2.0 + normal.1 * bend);
+                quadratic_bezier(start, control, end)
+            }
+            (RelationRoute::Routed, Some(index)) => {
+                let obstacle = |id: &str| id != from.id && id != to.id && visible.contains(id);
+                let mut waypoints = vec![start];
+                waypoints.extend(detour(index, &obstacle, start, end, normal, ROUTE_DEPTH));
+                waypoints.push(end);
+                catmull_rom(&waypoints)
+            }
+            _ => vec![start, end],
+        };
+        trim_to_circle(&mut points, from);
+        points.reverse();
+        trim_to_circle(&mut points, to);
+        points.reverse();
+        paths.insert(relation_index, RelationPath { points });
+    }
+    paths
+}
+
+/// a 到 b 之间绕开项目圆圈所需的途经点（不含 a、b）
+/// 在最先遇到的项目旁边、离线段较近的一侧放一个途经点，再分别处理两段
+fn detour(index: &SpatialIndex, obstacle: &dyn Fn(&str) -> bool, a: Point, b: Point, normal: Point, depth: usize) -> Vec<Point> {
+    if depth == 0 {
+        return Vec::new();
+    }
+    let (dx, dy) = direction(a, b);
+    let first = index.projects_along(a, b, ROUTE_CLEARANCE).into_iter()
+        .filter(|(id, _, _)| obstacle(id))
+        .min_by(|(_, p, _), (_, q, _)| {
+            let along = |c: &Point| (c.0 - a.0) * dx + (c.1 - a.1) * dy;
+            along(p).total_cmp(&along(q))
+        });
+    let Some((_, center, radius)) = first else {
+        return Vec::new();
+    };
+    // 圆心到线段最近点的方向即绕行方向；线段正好穿过圆心时绕向左侧
+    let t = ((center.0 - a.0) * dx + (center.1 - a.1) * dy).clamp(0.0, distance(a, b));
+    let nearest = (a.0 + dx * t, a.1 + dy * t);
+    let mut side = direction(center, nearest);
+    if side == (0.0, 0.0) {
+        side = normal;
+    }
+    let clearance = radius + ROUTE_CLEARANCE * 2.0;
+    let waypoint = (center.0 + side.0 * clearance, center.1 + side.1 * clearance);
+
+    let mut points = detour(index, obstacle, a, waypoint, normal, depth - 1);
+    points.push(waypoint);
+    points.extend(detour(index, obstacle, waypoint, b, normal, depth - 1));
+    points
+}
+
+/// 二次贝塞尔曲线采样
+fn quadratic_bezier(start: Point, control: Point, end: Point) -> Vec<Point> {
+    (0..=CURVE_SAMPLES)
+        .map(|i| {
+            let t = i as f32 / CURVE_SAMPLES as f32;
+            let (u, v, w) = ((1.0 - t).powi(2), 2.0 * (1.0 - t) * t, t * t);
+            (u * start.0 + v * control.0 + w * end.0, u * start.1 + v * control.1 + w * end.1)
+        })
+        .collect()
+}
+
+/// 经过所有途经点的 Catmull-Rom 样条采样（只有两点时为直线）
+fn catmull_rom(points: &[Point]) -> Vec<Point> {
+    if points.len() <= 2 {
+        return points.to_vec();
+    }
+    let at = |i: isize| points[i.clamp(0, points.len() as isize - 1) as usize];
+    let mut samples = vec![points[0]];
+    for i in 0..points.len() as isize - 1 {
+        let (p0, p1, p2, p3) = (at(i - 1), at(i), at(i + 1), at(i + 2));
+        for step in 1..=SPLINE_SAMPLES {
+            let t = step as f32 / SPLINE_SAMPLES as f32;
+            let (t2, t3) = (t * t, t * t * t);
+            let blend = |a: f32, b: f32, c: f32, d: f32| {
+                0.5 * (2.0 * b + (c - a) * t + (2.0 * a - 5.0 * b + 4.0 * c - d) * t2 + (3.0 * b - a - 3.0 * c + d) * t3)
+            };
+            samples.push((blend(p0.0, p1.0, p2.0, p3.0), blend(p0.1, p1.1, p2.1, p3.1)));
+        }
+    }
+    samples
+}
+
+/// 去掉路径开头落在项目圆圈内的部分，使路径从圆圈边缘开始
+/// （整条路径都在圆圈内时保持不变）
+fn trim_to_circle(points: &mut Vec<Point>, project: &Project) {
+    let center = project.position;
+    let radius = project.radius;
+    let Some(first_outside) = points.iter().position(|p| distance(*p, center) >= radius) else {
+        return;
+    };
+    if first_outside == 0 {
+        return;
+    }
+    // 求线段 inside -> outside 与圆的交点：|inside + t * (outside - inside) - center| = radius
+    let (inside, outside) = (points[first_outside - 1], points[first_outside]);
+    let (dx, dy) = (outside.0 - inside.0, outside.1 - inside.1);
+    let (fx, fy) = (inside.0 - center.0, inside.1 - center.1);
+    let a = dx * dx + dy * dy;
+    let b = 2.0 * (fx * dx + fy * dy);
+    let c = fx * fx + fy * fy - radius * radius;
+    let t = ((-b + (b * b - 4.0 * a * c).max(0.0).sqrt()) / (2.0 * a)).clamp(0.0, 1.0);
+    points.drain(..first_outside);
+    points.insert(0, (inside.0 + dx * t, inside.1 + dy * t));
+}
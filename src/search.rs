@@ -0,0 +1,75 @@
+use crate::models::{AppData, Project};
+use crate::pinyin::pinyin_matches;
+
+/// 规范化文本：全角字符转半角、统一小写、合并空白
+/// 使中英文混排、全角输入（如输入法下的"ＲＮＡ"）都能匹配
+pub fn normalize(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last_space = false;
+    for c in text.chars().map(halfwidth) {
+        if c.is_whitespace() {
+            if !last_space && !result.is_empty() {
+                result.push(' ');
+            }
+            last_space = true;
+        } else {
+            result.extend(c.to_lowercase());
+            last_space = false;
+        }
+    }
+    result.trim_end().to_string()
+}
+
+/// 全角字符转半角（全角空格和全角ASCII），其余字符不变
+pub fn halfwidth(c: char) -> char {
+    match c {
+        '\u{3000}' => ' ',  // 全角空格
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),  // 全角ASCII
+        _ => c,
+    }
+}
+
+/// 是否为中日韩文字
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32, 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF)
+}
+
+/// 汉字按顺序出现即匹配（如"微态"匹配"微生物生态"），用于名称的模糊匹配
+fn cjk_subsequence(haystack: &str, term: &str) -> bool {
+    if term.chars().count() < 2 || !term.chars().all(is_cjk) {
+        return false;
+    }
+    let mut chars = haystack.chars();
+    term.chars().all(|t| chars.any(|h| h == t))
+}
+
+/// 项目的可搜索文本：(名称类文本, 其他文本)
+/// 名称类文本（项目名称、领域名称）支持汉字模糊匹配和拼音匹配，描述、项目标签和关系标签只做包含匹配
+fn searchable_text(data: &AppData, project: &Project) -> (Vec<String>, Vec<String>) {
+    let mut names = vec![normalize(&project.name)];
+    for field_id in &data.effective_field_ids(project) {
+        if let Some(field) = data.fields.get(field_id) {
+            names.push(normalize(&field.name));
+        }
+    }
+    let mut others = vec![normalize(&project.description)];
+    others.extend(project.tags.iter().map(|t| normalize(t)));
+    for relation in &data.relations {
+        if relation.from_id == project.id || relation.to_id == project.id {
+            others.extend(relation.tags.iter().map(|t| normalize(t)));
+        }
+    }
+    (names, others)
+}
+
+/// 项目是否匹配单个搜索词（可包含空格的短语，如查询中的引号字符串）
+/// 查询语言中的普通词即通过此函数匹配，多个词的组合由 query 模块处理
+pub fn text_matches(data: &AppData, project: &Project, term: &str) -> bool {
+    let term = normalize(term);
+    if term.is_empty() {
+        return true;
+    }
+    let (names, others) = searchable_text(data, project);
+    names.iter().any(|n| n.contains(&term) || cjk_subsequence(n, &term) || pinyin_matches(n, &term))
+        || others.iter().any(|o| o.contains(&term))
+}
@@ -0,0 +1,512 @@
+use crate::models::{CompletionPalette, ProjectStatus, StatusPalette};
+use egui::*;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 自定义主题文件所在目录（每个 .yaml 文件一个主题）
+pub const THEMES_DIR: &str = "themes";
+
+// IDE风格主题系统 - 参考VSCode/Cursor设计
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: String,                  // 主题名称
+    pub is_dark: bool,                 // 是否为深色主题
+
+    // 主色调 - VSCode风格
+    pub primary: Color32,              // 主按钮颜色
+    pub primary_hover: Color32,        // 主按钮悬停
+    pub primary_active: Color32,       // 主按钮激活
+
+    // 背景色 - 类似VSCode编辑器
+    pub background: Color32,            // 主背景
+    pub surface: Color32,              // 面板背景
+    pub surface_hover: Color32,        // 悬停背景
+    pub surface_selected: Color32,     // 选中背景
+
+    // 文本颜色
+    pub text_primary: Color32,         // 主文本
+    pub text_secondary: Color32,       // 次要文本
+    pub text_disabled: Color32,        // 禁用文本
+
+    // 边框和分隔线
+    pub border: Color32,               // 边框颜色
+    pub divider: Color32,              // 分隔线
+
+    // 状态颜色
+    pub success: Color32,
+    pub warning: Color32,
+    pub error: Color32,
+    pub info: Color32,
+
+    // 特殊用途
+    pub selection: Color32,            // 选中高亮
+    pub selection_inactive: Color32,   // 非活动选中
+
+    // 可视化画布
+    pub canvas_text: Color32,          // 领域和项目名称
+    pub canvas_text_secondary: Color32, // 关系标签、图例小标题
+    pub canvas_arrow: Color32,         // 默认颜色的关系箭头
+    pub legend_background: Color32,    // 图例背景（半透明）
+    pub legend_border: Color32,        // 图例边框
+
+    // 项目配色
+    pub status_colors: HashMap<String, Color32>,  // 主题指定的项目状态颜色（键为状态ID），未指定时使用状态目录中的颜色
+    pub status_palette: Vec<Color32>,  // 配色方案（按状态序号循环取色），非空时优先于 status_colors
+    pub completion_gradient: Vec<(f32, Color32)>,  // 完成度渐变色标（百分比, 颜色），按百分比升序
+}
+
+impl Theme {
+    // VSCode Light+ 风格主题
+    pub fn light() -> Self {
+        Self {
+            name: "Light+".to_string(),
+            is_dark: false,
+
+            primary: Color32::from_rgb(0, 122, 204),        // VSCode蓝色
+            primary_hover: Color32::from_rgb(0, 102, 184),
+            primary_active: Color32::from_rgb(0, 82, 164),
+
+            background: Color32::from_rgb(255, 255, 255),   // 纯白背景
+            surface: Color32::from_rgb(248, 248, 248),      // 浅灰面板
+            surface_hover: Color32::from_rgb(240, 240, 240),
+            surface_selected: Color32::from_rgb(230, 230, 230),
+
+            text_primary: Color32::from_rgb(51, 51, 51),    // 深灰文本
+            text_secondary: Color32::from_rgb(102, 102, 102),
+            text_disabled: Color32::from_rgb(170, 170, 170),
+
+            border: Color32::from_rgb(214, 214, 214),       // 浅灰边框
+            divider: Color32::from_rgb(214, 214, 214),
+
+            success: Color32::from_rgb(89, 185, 89),
+            warning: Color32::from_rgb(252, 186, 3),
+            error: Color32::from_rgb(244, 63, 94),
+            info: Color32::from_rgb(0, 122, 204),
+
+            selection: Color32::from_rgb(173, 214, 255),    // 浅蓝选中
+            selection_inactive: Color32::from_rgb(230, 230, 230),
+
+            canvas_text: Color32::BLACK,
+            canvas_text_secondary: Color32::DARK_GRAY,
+            canvas_arrow: Color32::BLACK,
+            legend_background: Color32::from_rgba_unmultiplied(255, 255, 255, 240),
+            legend_border: Color32::from_rgb(180, 180, 180),
+
+            status_colors: HashMap::new(),
+            status_palette: Vec::new(),
+            completion_gradient: default_completion_gradient(),
+        }
+    }
+
+    // VSCode Dark+ 风格主题
+    pub fn dark() -> Self {
+        Self {
+            name: "Dark+".to_string(),
+            is_dark: true,
+
+            primary: Color32::from_rgb(0, 122, 204),        // VSCode蓝色
+            primary_hover: Color32::from_rgb(38, 139, 210),
+            primary_active: Color32::from_rgb(0, 102, 184),
+
+            background: Color32::from_rgb(30, 30, 30),      // 深灰背景
+            surface: Color32::from_rgb(37, 37, 38),        // 面板背景
+            surface_hover: Color32::from_rgb(45, 45, 45),
+            surface_selected: Color32::from_rgb(58, 58, 58),
+
+            text_primary: Color32::from_rgb(212, 212, 212), // 浅灰文本
+            text_secondary: Color32::from_rgb(170, 170, 170),
+            text_disabled: Color32::from_rgb(113, 113, 113),
+
+            border: Color32::from_rgb(60, 60, 60),         // 深灰边框
+            divider: Color32::from_rgb(60, 60, 60),
+
+            success: Color32::from_rgb(89, 185, 89),
+            warning: Color32::from_rgb(252, 186, 3),
+            error: Color32::from_rgb(244, 63, 94),
+            info: Color32::from_rgb(0, 122, 204),
+
+            selection: Color32::from_rgb(38, 79, 120),      // 深蓝选中
+            selection_inactive: Color32::from_rgb(58, 58, 58),
+
+            canvas_text: Color32::from_rgb(212, 212, 212),
+            canvas_text_secondary: Color32::from_rgb(160, 160, 160),
+            canvas_arrow: Color32::from_rgb(200, 200, 200),
+            legend_background: Color32::from_rgba_unmultiplied(37, 37, 38, 240),
+            legend_border: Color32::from_rgb(80, 80, 80),
+
+            status_colors: HashMap::new(),
+            status_palette: Vec::new(),
+            completion_gradient: default_completion_gradient(),
+        }
+    }
+
+    pub fn apply_visuals(&self, ctx: &Context) {
+        let mut style = (*ctx.style()).clone();
+
+        // 以egui自带的浅色/深色视觉为基础
+        style.visuals = if self.is_dark { Visuals::dark() } else { Visuals::light() };
+
+        // 按钮样式 - 更扁平化，小圆角
+        style.visuals.widgets.inactive.bg_fill = self.primary;
+        style.visuals.widgets.inactive.weak_bg_fill = self.surface;
+        style.visuals.widgets.inactive.fg_stroke = Stroke::new(1.0, Color32::WHITE);
+        style.visuals.widgets.inactive.rounding = Rounding::same(3.0); // 小圆角
+
+        style.visuals.widgets.hovered.bg_fill = self.primary_hover;
+        style.visuals.widgets.hovered.rounding = Rounding::same(3.0);
+
+        style.visuals.widgets.active.bg_fill = self.primary_active;
+        style.visuals.widgets.active.rounding = Rounding::same(3.0);
+
+        // 文本样式 - 更专业的字体大小
+        style.text_styles.get_mut(&TextStyle::Heading).unwrap().size = 20.0;
+        style.text_styles.get_mut(&TextStyle::Body).unwrap().size = 13.0;
+        style.text_styles.get_mut(&TextStyle::Button).unwrap().size = 13.0;
+        style.text_styles.get_mut(&TextStyle::Small).unwrap().size = 11.0;
+
+        // 间距 - 更紧凑
+        style.spacing.item_spacing = vec2(6.0, 6.0);
+        style.spacing.button_padding = vec2(12.0, 6.0);
+        style.spacing.menu_margin = Margin::same(4.0);
+        style.spacing.window_margin = Margin::same(8.0);
+
+        // 交互
+        style.interaction.resize_grab_radius_side = 6.0;
+
+        // 禁用Tab键的默认焦点导航，由应用自行处理
+        style.interaction.selectable_labels = false;
+
+        // 整体视觉
+        style.visuals.override_text_color = Some(self.text_primary);
+        style.visuals.faint_bg_color = self.surface;
+        style.visuals.extreme_bg_color = self.surface_hover;
+
+        ctx.set_style(style);
+    }
+
+    /// 项目状态颜色：配色方案 > 主题指定颜色 > 状态目录中的颜色
+    /// index 为状态在排序后目录中的序号
+    pub fn status_color(&self, status: &ProjectStatus, index: usize) -> Color32 {
+        if !self.status_palette.is_empty() {
+            return self.status_palette[index % self.status_palette.len()];
+        }
+        self.status_colors.get(&status.id).copied().unwrap_or_else(|| status.color32())
+    }
+
+    /// 根据完成度百分比计算边界颜色（在渐变色标之间线性插值）
+    /// 默认渐变：0% = 红色, 50% = 黄色, 100% = 绿色
+    pub fn completion_percentage_to_color(&self, percentage: f32) -> Color32 {
+        let p = percentage.clamp(0.0, 100.0);
+        let stops = &self.completion_gradient;
+
+        let Some(&(first_at, first_color)) = stops.first() else {
+            return Color32::GRAY;
+        };
+        if p <= first_at {
+            return first_color;
+        }
+        for pair in stops.windows(2) {
+            let (at1, c1) = pair[0];
+            let (at2, c2) = pair[1];
+            if p <= at2 {
+                let ratio = if at2 > at1 { (p - at1) / (at2 - at1) } else { 1.0 };
+                let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * ratio) as u8;
+                return Color32::from_rgba_unmultiplied(
+                    lerp(c1.r(), c2.r()),
+                    lerp(c1.g(), c2.g()),
+                    lerp(c1.b(), c2.b()),
+                    lerp(c1.a(), c2.a()),
+                );
+            }
+        }
+        stops.last().map(|&(_, c)| c).unwrap_or(first_color)
+    }
+
+    /// 应用设置中的配色方案（覆盖主题自带的状态颜色和完成度渐变）
+    pub fn with_palettes(mut self, status_palette: StatusPalette, completion_palette: CompletionPalette) -> Self {
+        if status_palette == StatusPalette::OkabeIto {
+            self.status_palette = okabe_ito_palette();
+        }
+        if completion_palette == CompletionPalette::Viridis {
+            self.completion_gradient = viridis_gradient();
+        }
+        self
+    }
+
+    /// 导出为主题文件（列出所有颜色，便于作为自定义主题的模板）
+    /// statuses 为当前文档按顺序排列的状态目录，用于列出状态颜色
+    pub fn to_file(&self, statuses: &[&ProjectStatus]) -> ThemeFile {
+        let c = |color: Color32| Some(ColorValue::from_color(color));
+        let mut status_colors = IndexMap::new();
+        for (index, status) in statuses.iter().enumerate() {
+            status_colors.insert(status.id.clone(), ColorValue::from_color(self.status_color(status, index)));
+        }
+        ThemeFile {
+            name: Some(self.name.clone()),
+            base: Some(if self.is_dark { "dark" } else { "light" }.to_string()),
+            primary: c(self.primary),
+            primary_hover: c(self.primary_hover),
+            primary_active: c(self.primary_active),
+            background: c(self.background),
+            surface: c(self.surface),
+            surface_hover: c(self.surface_hover),
+            surface_selected: c(self.surface_selected),
+            text_primary: c(self.text_primary),
+            text_secondary: c(self.text_secondary),
+            text_disabled: c(self.text_disabled),
+            border: c(self.border),
+            divider: c(self.divider),
+            success: c(self.success),
+            warning: c(self.warning),
+            error: c(self.error),
+            info: c(self.info),
+            selection: c(self.selection),
+            selection_inactive: c(self.selection_inactive),
+            canvas_text: c(self.canvas_text),
+            canvas_text_secondary: c(self.canvas_text_secondary),
+            canvas_arrow: c(self.canvas_arrow),
+            legend_background: c(self.legend_background),
+            legend_border: c(self.legend_border),
+            status_colors,
+            completion_gradient: self.completion_gradient.iter()
+                .map(|&(at, color)| GradientStop { at, color: ColorValue::from_color(color) })
+                .collect(),
+        }
+    }
+}
+
+/// 在给定填充色上可读的文字颜色（浅色背景用黑色，深色背景用白色）
+pub fn contrast_text_color(fill: Color32) -> Color32 {
+    let luminance = 0.299 * fill.r() as f32 + 0.587 * fill.g() as f32 + 0.114 * fill.b() as f32;
+    if luminance > 186.0 {
+        Color32::BLACK
+    } else {
+        Color32::WHITE
+    }
+}
+
+/// 默认完成度渐变：红 → 黄 → 绿
+fn default_completion_gradient() -> Vec<(f32, Color32)> {
+    vec![
+        (0.0, Color32::from_rgb(255, 0, 0)),
+        (50.0, Color32::from_rgb(255, 255, 0)),
+        (100.0, Color32::from_rgb(0, 255, 0)),
+    ]
+}
+
+/// Okabe–Ito 色盲友好配色（按状态顺序取色，前五个对应默认状态目录）
+fn okabe_ito_palette() -> Vec<Color32> {
+    vec![
+        Color32::from_rgb(0, 158, 115),    // 蓝绿色
+        Color32::from_rgb(0, 114, 178),    // 蓝色
+        Color32::from_rgb(213, 94, 0),     // 朱红色
+        Color32::from_rgb(230, 159, 0),    // 橙色
+        Color32::from_rgb(255, 255, 255),  // 白色
+        Color32::from_rgb(86, 180, 233),   // 天蓝色
+        Color32::from_rgb(204, 121, 167),  // 紫红色
+        Color32::from_rgb(240, 228, 66),   // 黄色
+    ]
+}
+
+/// viridis 完成度渐变（深紫 → 青绿 → 黄）
+fn viridis_gradient() -> Vec<(f32, Color32)> {
+    vec![
+        (0.0, Color32::from_rgb(68, 1, 84)),
+        (25.0, Color32::from_rgb(59, 82, 139)),
+        (50.0, Color32::from_rgb(33, 145, 140)),
+        (75.0, Color32::from_rgb(94, 201, 98)),
+        (100.0, Color32::from_rgb(253, 231, 37)),
+    ]
+}
+
+/// 主题文件中的颜色，支持 "#RRGGBB"、"#RRGGBBAA" 或 [R, G, B] / [R, G, B, A]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ColorValue {
+    Hex(String),
+    Rgba([u8; 4]),
+    Rgb([u8; 3]),
+}
+
+impl ColorValue {
+    fn from_color(color: Color32) -> Self {
+        let [r, g, b, a] = color.to_srgba_unmultiplied();
+        if a == 255 {
+            ColorValue::Hex(format!("#{:02X}{:02X}{:02X}", r, g, b))
+        } else {
+            ColorValue::Hex(format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a))
+        }
+    }
+
+    fn to_color(&self) -> Result<Color32, String> {
+        match self {
+            ColorValue::Rgba([r, g, b, a]) => Ok(Color32::from_rgba_unmultiplied(*r, *g, *b, *a)),
+            ColorValue::Rgb([r, g, b]) => Ok(Color32::from_rgb(*r, *g, *b)),
+            ColorValue::Hex(hex) => {
+                let digits = hex.trim().trim_start_matches('#');
+                let channel = |i: usize| {
+                    digits.get(i..i + 2)
+                        .and_then(|d| u8::from_str_radix(d, 16).ok())
+                        .ok_or_else(|| format!("无效的颜色值: {}", hex))
+                };
+                match digits.len() {
+                    6 => Ok(Color32::from_rgb(channel(0)?, channel(2)?, channel(4)?)),
+                    8 => Ok(Color32::from_rgba_unmultiplied(channel(0)?, channel(2)?, channel(4)?, channel(6)?)),
+                    _ => Err(format!("无效的颜色值: {}", hex)),
+                }
+            }
+        }
+    }
+}
+
+/// 完成度渐变色标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradientStop {
+    pub at: f32,  // 百分比 (0.0 - 100.0)
+    pub color: ColorValue,
+}
+
+/// 自定义主题文件（themes/*.yaml）
+/// 未填写的颜色沿用 base 指定的内置主题（light 或 dark）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeFile {
+    pub name: Option<String>,  // 显示名称，默认使用文件名
+    pub base: Option<String>,  // 基础主题: light / dark
+    pub primary: Option<ColorValue>,
+    pub primary_hover: Option<ColorValue>,
+    pub primary_active: Option<ColorValue>,
+    pub background: Option<ColorValue>,
+    pub surface: Option<ColorValue>,
+    pub surface_hover: Option<ColorValue>,
+    pub surface_selected: Option<ColorValue>,
+    pub text_primary: Option<ColorValue>,
+    pub text_secondary: Option<ColorValue>,
+    pub text_disabled: Option<ColorValue>,
+    pub border: Option<ColorValue>,
+    pub divider: Option<ColorValue>,
+    pub success: Option<ColorValue>,
+    pub warning: Option<ColorValue>,
+    pub error: Option<ColorValue>,
+    pub info: Option<ColorValue>,
+    pub selection: Option<ColorValue>,
+    pub selection_inactive: Option<ColorValue>,
+    pub canvas_text: Option<ColorValue>,
+    pub canvas_text_secondary: Option<ColorValue>,
+    pub canvas_arrow: Option<ColorValue>,
+    pub legend_background: Option<ColorValue>,
+    pub legend_border: Option<ColorValue>,
+    pub status_colors: IndexMap<String, ColorValue>,  // 键为状态ID，如 Published（文档状态目录中没有的ID会被忽略）
+    pub completion_gradient: Vec<GradientStop>,  // 为空时沿用基础主题
+}
+
+impl ThemeFile {
+    /// 转换为主题，颜色格式错误时返回带字段名的错误信息
+    pub fn into_theme(self, default_name: &str) -> Result<Theme, String> {
+        let mut theme = match self.base.as_deref().map(|b| b.trim().to_lowercase()) {
+            None => Theme::light(),
+            Some(base) if base == "light" => Theme::light(),
+            Some(base) if base == "dark" => Theme::dark(),
+            Some(base) => return Err(format!("base 只能是 light 或 dark，实际为 {}", base)),
+        };
+        theme.name = self.name.unwrap_or_else(|| default_name.to_string());
+
+        let colors = [
+            ("primary", self.primary, &mut theme.primary),
+            ("primary_hover", self.primary_hover, &mut theme.primary_hover),
+            ("primary_active", self.primary_active, &mut theme.primary_active),
+            ("background", self.background, &mut theme.background),
+            ("surface", self.surface, &mut theme.surface),
+            ("surface_hover", self.surface_hover, &mut theme.surface_hover),
+            ("surface_selected", self.surface_selected, &mut theme.surface_selected),
+            ("text_primary", self.text_primary, &mut theme.text_primary),
+            ("text_secondary", self.text_secondary, &mut theme.text_secondary),
+            ("text_disabled", self.text_disabled, &mut theme.text_disabled),
+            ("border", self.border, &mut theme.border),
+            ("divider", self.divider, &mut theme.divider),
+            ("success", self.success, &mut theme.success),
+            ("warning", self.warning, &mut theme.warning),
+            ("error", self.error, &mut theme.error),
+            ("info", self.info, &mut theme.info),
+            ("selection", self.selection, &mut theme.selection),
+            ("selection_inactive", self.selection_inactive, &mut theme.selection_inactive),
+            ("canvas_text", self.canvas_text, &mut theme.canvas_text),
+            ("canvas_text_secondary", self.canvas_text_secondary, &mut theme.canvas_text_secondary),
+            ("canvas_arrow", self.canvas_arrow, &mut theme.canvas_arrow),
+            ("legend_background", self.legend_background, &mut theme.legend_background),
+            ("legend_border", self.legend_border, &mut theme.legend_border),
+        ];
+        for (field, value, target) in colors {
+            if let Some(value) = value {
+                *target = value.to_color().map_err(|e| format!("{}: {}", field, e))?;
+            }
+        }
+
+        for (status_id, value) in self.status_colors {
+            let color = value.to_color().map_err(|e| format!("status_colors.{}: {}", status_id, e))?;
+            theme.status_colors.insert(status_id, color);
+        }
+
+        if !self.completion_gradient.is_empty() {
+            let mut stops = Vec::with_capacity(self.completion_gradient.len());
+            for stop in self.completion_gradient {
+                let color = stop.color.to_color().map_err(|e| format!("completion_gradient: {}", e))?;
+                stops.push((stop.at.clamp(0.0, 100.0), color));
+            }
+            stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+            theme.completion_gradient = stops;
+        }
+
+        Ok(theme)
+    }
+}
+
+/// 从 themes 目录加载所有自定义主题
+/// 返回 (成功加载的主题, 错误信息)
+pub fn load_custom_themes() -> (Vec<Theme>, Vec<String>) {
+    let mut themes = Vec::new();
+    let mut errors = Vec::new();
+
+    let Ok(entries) = std::fs::read_dir(THEMES_DIR) else {
+        return (themes, errors);  // 没有主题目录时视为没有自定义主题
+    };
+    let mut paths: Vec<std::path::PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| matches!(p.extension().and_then(|e| e.to_str()), Some("yaml") | Some("yml")))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let file_name = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let result = std::fs::read_to_string(&path)
+            .map_err(|e| format!("读取失败: {}", e))
+            .and_then(|content| serde_yaml::from_str::<ThemeFile>(&content).map_err(|e| format!("解析失败: {}", e)))
+            .and_then(|file| file.into_theme(&file_name));
+        match result {
+            Ok(theme) => {
+                if theme.name == Theme::light().name || theme.name == Theme::dark().name {
+                    errors.push(format!("{}: 主题名称 {} 与内置主题相同，已忽略", path.display(), theme.name));
+                } else if themes.iter().any(|t: &Theme| t.name == theme.name) {
+                    errors.push(format!("{}: 主题名称 {} 重复，已忽略", path.display(), theme.name));
+                } else {
+                    themes.push(theme);
+                }
+            }
+            Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    (themes, errors)
+}
+
+/// 将主题写入 themes 目录，作为自定义主题的模板
+pub fn save_theme_template(theme: &Theme, statuses: &[&ProjectStatus], file_name: &str) -> Result<std::path::PathBuf, String> {
+    std::fs::create_dir_all(THEMES_DIR).map_err(|e| format!("创建主题目录失败: {}", e))?;
+    let path = std::path::Path::new(THEMES_DIR).join(format!("{}.yaml", file_name));
+    let yaml = serde_yaml::to_string(&theme.to_file(statuses)).map_err(|e| format!("序列化主题失败: {}", e))?;
+    std::fs::write(&path, yaml).map_err(|e| format!("保存主题失败: {}", e))?;
+    Ok(path)
+}